@@ -0,0 +1,416 @@
+//! Damage model of player ships
+//!
+//! Every ship has hit points and a regenerating shield. Damage is absorbed by the shield first,
+//! the remainder is subtracted from the hit points. A ship without hit points is destroyed.
+//!
+//! The repository stores the damage taken instead of the remaining hit points, such that newly
+//! created player start with full health independent of the configuration.
+
+use std::{collections::BTreeSet, rc::Rc};
+
+use crate::{
+    entities::Real,
+    physics::CollisionEvent,
+    repo_interfaces::{Marshalling, MissileIdData, PlayerId, PlayerIdData},
+    Error, Result,
+};
+
+/// Configuration object for the damage model
+#[derive(Clone, Copy, Debug)]
+pub struct DamageConfig {
+    /// Hit points of an undamaged ship
+    max_health: Real,
+    /// Shield strength of a fully charged shield
+    max_shield: Real,
+    /// Shield strength regenerated per time unit
+    shield_regeneration: Real,
+    /// Damage dealt by a missile hit
+    missile_damage: Real,
+    /// Damage dealt per time unit in contact with a star
+    star_damage_rate: Real,
+    /// Damage dealt per time unit within a hazard
+    hazard_damage: Real,
+}
+
+impl DamageConfig {
+    /// Create a new damage configuration for ships without shield
+    ///
+    /// Stars deal `star_damage_rate` per time unit of contact, such that the damage does not
+    /// depend on the tick rate.
+    pub fn new(
+        max_health: impl Into<Real>,
+        missile_damage: impl Into<Real>,
+        star_damage_rate: impl Into<Real>,
+    ) -> Self {
+        DamageConfig {
+            max_health: max_health.into(),
+            max_shield: 0.0,
+            shield_regeneration: 0.0,
+            missile_damage: missile_damage.into(),
+            star_damage_rate: star_damage_rate.into(),
+            hazard_damage: 0.0,
+        }
+    }
+
+    /// Equip ships with a shield of strength `max_shield` that regenerates by `regeneration`
+    /// per time unit
+    pub fn with_shield(
+        mut self,
+        max_shield: impl Into<Real>,
+        regeneration: impl Into<Real>,
+    ) -> DamageConfig {
+        self.max_shield = max_shield.into();
+        self.shield_regeneration = regeneration.into();
+        self
+    }
+
+    /// Let hazards deal `damage` per time unit to ships within them
+    pub fn with_hazard_damage(mut self, damage: impl Into<Real>) -> DamageConfig {
+        self.hazard_damage = damage.into();
+        self
+    }
+
+    /// Hit points of an undamaged ship
+    pub fn get_max_health(&self) -> Real {
+        self.max_health
+    }
+
+    /// Shield strength of a fully charged shield
+    pub fn get_max_shield(&self) -> Real {
+        self.max_shield
+    }
+
+    /// Shield strength regenerated per time unit
+    pub fn get_shield_regeneration(&self) -> Real {
+        self.shield_regeneration
+    }
+
+    /// Damage dealt by a missile hit
+    pub fn get_missile_damage(&self) -> Real {
+        self.missile_damage
+    }
+
+    /// Damage dealt per time unit in contact with a star
+    pub fn get_star_damage_rate(&self) -> Real {
+        self.star_damage_rate
+    }
+
+    /// Damage dealt per time unit within a hazard
+    pub fn get_hazard_damage(&self) -> Real {
+        self.hazard_damage
+    }
+}
+
+/// A ship was destroyed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerDestroyed {
+    /// Player whose ship was destroyed
+    pub player: PlayerId,
+    /// Owner of the missile that dealt the final hit, `None` if the ship crashed into a star or
+    /// perished in a hazard
+    pub by: Option<PlayerId>,
+}
+
+/// Damage taken by the hull and the shield of a ship
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ShipDamage {
+    hull: Real,
+    shield: Real,
+}
+
+/// Damage use-case
+///
+/// Applies the hits reported by the collision use-case, regenerates shields and marks ships
+/// without hit points as destroyed. Stars and hazards deal damage for the time a ship spends in
+/// contact with them. Missiles are removed once they hit a player or a star.
+pub struct Damage {
+    config: DamageConfig,
+    repo: DamageDG,
+}
+
+impl Damage {
+    /// Create use case object
+    pub fn new(config: DamageConfig, repo: DamageDG) -> Self {
+        Self { config, repo }
+    }
+
+    /// Run the use case
+    ///
+    /// Returns all ships destroyed in this time step.
+    pub fn execute(
+        &self,
+        events: &[CollisionEvent],
+        delta_time: impl Into<Real>,
+    ) -> Result<Vec<PlayerDestroyed>> {
+        let dt = delta_time.into();
+        let regeneration = self.config.shield_regeneration * dt;
+        let mut ships: Vec<(PlayerId, ShipDamage)> = self
+            .repo
+            .get_player_damage()?
+            .into_iter()
+            .map(|(id, hull, shield)| {
+                let shield = (shield - regeneration).max(0.0);
+                (id.convert(), ShipDamage { hull, shield })
+            })
+            .collect();
+
+        let mut destroyed = Vec::new();
+        let mut spent_missiles = BTreeSet::new();
+        for event in events {
+            let (player, damage, by) = match *event {
+                CollisionEvent::MissileHitPlayer { missile, player } => {
+                    if !spent_missiles.insert(missile) {
+                        continue;
+                    }
+                    (player, self.config.missile_damage, Some(missile.0))
+                }
+                CollisionEvent::MissileHitStar { missile, star: _ } => {
+                    spent_missiles.insert(missile);
+                    continue;
+                }
+                CollisionEvent::PlayerHitStar { player, star: _ } => {
+                    (player, self.config.star_damage_rate * dt, None)
+                }
+                CollisionEvent::PlayerHitHazard { player, hazard: _ } => {
+                    (player, self.config.hazard_damage * dt, None)
+                }
+                CollisionEvent::PlayerHitPlayer { players: _ } => continue,
+            };
+            let Some((_, ship)) = ships.iter_mut().find(|(id, _)| *id == player) else {
+                continue;
+            };
+            if ship.hull >= self.config.max_health {
+                continue;
+            }
+            self.apply_damage(ship, damage);
+            if ship.hull >= self.config.max_health {
+                destroyed.push(PlayerDestroyed { player, by });
+            }
+        }
+
+        self.repo.set_player_damage(
+            ships
+                .into_iter()
+                .map(|(id, ship)| (id, ship.hull, ship.shield).convert())
+                .collect(),
+        )?;
+        self.repo.set_player_destroyed(
+            destroyed
+                .iter()
+                .map(|destruction| destruction.player.convert())
+                .collect(),
+        )?;
+        self.repo.destroy_missiles(
+            spent_missiles
+                .into_iter()
+                .map(|missile| missile.convert())
+                .collect(),
+        )?;
+        Ok(destroyed)
+    }
+
+    /// Remaining `(health, shield)` of a ship that is not destroyed
+    pub fn get_health_and_shield(&self, id: PlayerId) -> Result<(Real, Real)> {
+        self.repo
+            .get_player_damage()?
+            .into_iter()
+            .find(|(p_id, _, _)| *p_id == id.convert())
+            .map(|(_, hull, shield)| {
+                (
+                    self.config.max_health - hull,
+                    self.config.max_shield - shield,
+                )
+            })
+            .ok_or(Error::UnknownPlayer(id.convert()))
+    }
+
+    /// Let the shield absorb as much of the damage as possible and apply the rest to the hull
+    fn apply_damage(&self, ship: &mut ShipDamage, damage: Real) {
+        let absorbed = damage.min(self.config.max_shield - ship.shield).max(0.0);
+        ship.shield += absorbed;
+        ship.hull += damage - absorbed;
+    }
+}
+
+/// Data repository interface for damage use case.
+pub trait DamageDataGateway {
+    /// Return `(id, hull damage, shield damage)` of all player that are not destroyed
+    fn get_player_damage(&self) -> Result<Vec<(PlayerIdData, Real, Real)>>;
+
+    /// Update `(id, hull damage, shield damage)` of player
+    fn set_player_damage(&self, updates: Vec<(PlayerIdData, Real, Real)>) -> Result<()>;
+
+    /// Mark ships of player as destroyed
+    fn set_player_destroyed(&self, ids: Vec<PlayerIdData>) -> Result<()>;
+
+    /// Remove missiles that hit a target
+    fn destroy_missiles(&self, ids: Vec<(PlayerIdData, MissileIdData)>) -> Result<()>;
+}
+
+type DamageDG = Rc<dyn DamageDataGateway>;
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        entities::Real,
+        physics::CollisionEvent,
+        repo_interfaces::{MissileIdData, PlayerIdData},
+        Error, Result,
+    };
+
+    use super::{Damage, DamageConfig, DamageDataGateway, PlayerDestroyed};
+
+    #[derive(Default)]
+    struct MockDataGateway {
+        player: Vec<(PlayerIdData, Real, Real)>,
+        destroyed: Vec<PlayerIdData>,
+        removed_missiles: Vec<(PlayerIdData, MissileIdData)>,
+    }
+
+    impl DamageDataGateway for RefCell<MockDataGateway> {
+        fn get_player_damage(&self) -> Result<Vec<(PlayerIdData, Real, Real)>> {
+            let repo = self.borrow();
+            Ok(repo
+                .player
+                .iter()
+                .filter(|(id, _, _)| !repo.destroyed.contains(id))
+                .copied()
+                .collect())
+        }
+        fn set_player_damage(&self, updates: Vec<(PlayerIdData, Real, Real)>) -> Result<()> {
+            let mut repo = self.borrow_mut();
+            for update in updates {
+                let player = repo
+                    .player
+                    .iter_mut()
+                    .find(|(id, _, _)| *id == update.0)
+                    .ok_or(Error::UnknownPlayer(update.0))?;
+                *player = update;
+            }
+            Ok(())
+        }
+        fn set_player_destroyed(&self, ids: Vec<PlayerIdData>) -> Result<()> {
+            self.borrow_mut().destroyed.extend(ids);
+            Ok(())
+        }
+        fn destroy_missiles(&self, ids: Vec<(PlayerIdData, MissileIdData)>) -> Result<()> {
+            self.borrow_mut().removed_missiles.extend(ids);
+            Ok(())
+        }
+    }
+
+    fn setup_damage_test(
+        player: Vec<(PlayerIdData, Real, Real)>,
+    ) -> (Damage, Rc<RefCell<MockDataGateway>>) {
+        let config = DamageConfig::new(100.0, 30.0, 1000.0).with_shield(20.0, 5.0);
+        let repo = Rc::new(RefCell::new(MockDataGateway {
+            player,
+            ..Default::default()
+        }));
+        (Damage::new(config, repo.clone()), repo)
+    }
+
+    fn missile_hit(owner: usize, missile: usize, player: usize) -> CollisionEvent {
+        CollisionEvent::MissileHitPlayer {
+            missile: (owner, missile),
+            player,
+        }
+    }
+
+    #[test]
+    fn shield_absorbs_damage_before_hull() {
+        let (damage, repo) = setup_damage_test(vec![(1, 0.0, 0.0)]);
+        damage.execute(&[missile_hit(2, 0, 1)], 0.0).unwrap();
+        assert_eq!(repo.borrow().player, vec![(1, 10.0, 20.0)]);
+        assert_eq!(damage.get_health_and_shield(1), Ok((90.0, 0.0)));
+        assert_eq!(repo.borrow().removed_missiles, vec![(2, 0)]);
+    }
+
+    #[test]
+    fn shield_regenerates_up_to_maximum() {
+        let (damage, repo) = setup_damage_test(vec![(1, 10.0, 20.0), (2, 0.0, 3.0)]);
+        damage.execute(&[], 2.0).unwrap();
+        assert_eq!(repo.borrow().player, vec![(1, 10.0, 10.0), (2, 0.0, 0.0)]);
+    }
+
+    #[test]
+    fn ship_destroyed_without_hit_points() {
+        let (damage, repo) = setup_damage_test(vec![(1, 90.0, 20.0), (2, 0.0, 0.0)]);
+        let destroyed = damage
+            .execute(&[missile_hit(2, 0, 1), missile_hit(2, 1, 1)], 0.0)
+            .unwrap();
+        assert_eq!(
+            destroyed,
+            vec![PlayerDestroyed {
+                player: 1,
+                by: Some(2)
+            }]
+        );
+        assert_eq!(repo.borrow().destroyed, vec![1]);
+        assert_eq!(
+            damage.get_health_and_shield(1),
+            Err(Error::UnknownPlayer(1))
+        );
+    }
+
+    #[test]
+    fn star_contact_destroys_ship() {
+        let (damage, repo) = setup_damage_test(vec![(1, 0.0, 0.0)]);
+        let contact = [CollisionEvent::PlayerHitStar { player: 1, star: 0 }];
+        damage.execute(&contact, 0.05).unwrap();
+        assert_eq!(repo.borrow().player, vec![(1, 30.0, 20.0)]);
+        let destroyed = damage.execute(&contact, 0.1).unwrap();
+        assert_eq!(
+            destroyed,
+            vec![PlayerDestroyed {
+                player: 1,
+                by: None
+            }]
+        );
+    }
+
+    #[test]
+    fn hazard_damage_scales_with_time() {
+        let (_, repo) = setup_damage_test(vec![(1, 0.0, 20.0)]);
+        let damage = Damage::new(
+            DamageConfig::new(100.0, 30.0, 1000.0).with_hazard_damage(40.0),
+            repo.clone(),
+        );
+        let in_hazard = [CollisionEvent::PlayerHitHazard {
+            player: 1,
+            hazard: 0,
+        }];
+        damage.execute(&in_hazard, 0.5).unwrap();
+        assert_eq!(repo.borrow().player, vec![(1, 20.0, 20.0)]);
+        let destroyed = damage.execute(&in_hazard, 2.0).unwrap();
+        assert_eq!(
+            destroyed,
+            vec![PlayerDestroyed {
+                player: 1,
+                by: None
+            }]
+        );
+    }
+
+    #[test]
+    fn missile_hits_only_once() {
+        let (damage, repo) = setup_damage_test(vec![(1, 0.0, 20.0), (3, 0.0, 20.0)]);
+        damage
+            .execute(
+                &[
+                    missile_hit(2, 0, 1),
+                    missile_hit(2, 0, 3),
+                    CollisionEvent::MissileHitStar {
+                        missile: (2, 1),
+                        star: 0,
+                    },
+                ],
+                0.0,
+            )
+            .unwrap();
+        assert_eq!(repo.borrow().player, vec![(1, 30.0, 20.0), (3, 0.0, 20.0)]);
+        assert_eq!(repo.borrow().removed_missiles, vec![(2, 0), (2, 1)]);
+    }
+}
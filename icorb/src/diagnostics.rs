@@ -0,0 +1,354 @@
+//! Diagnostics of the physics simulation
+//!
+//! Records the total energy of all ships and missiles and their angular momentum about each star
+//! once per tick. Without thrust, moving stars and objects entering or leaving the game, the
+//! energy is conserved and its drift measures the error of the integration. The angular momentum
+//! about a star is only conserved if there are no other attractants.
+
+use std::rc::Rc;
+
+use crate::{
+    entities::{Real, Vec2},
+    physics::{GravityDataGateway, IntegrateDataGateway, PhysicsConfig, Star},
+    repo_interfaces::Marshalling,
+    Result,
+};
+
+/// Configuration object for diagnostics
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DiagnosticsConfig {
+    /// Physics the simulation runs with
+    physics: PhysicsConfig,
+    /// Largest tolerated relative drift of the total energy
+    energy_threshold: Option<Real>,
+    /// Largest tolerated relative drift of the angular momentum
+    angular_momentum_threshold: Option<Real>,
+}
+
+impl DiagnosticsConfig {
+    /// Create configuration for a simulation with the given physics, no drift is flagged
+    pub fn new(physics: PhysicsConfig) -> Self {
+        DiagnosticsConfig {
+            physics,
+            ..Default::default()
+        }
+    }
+
+    /// Flag relative drift of the total energy beyond `threshold`
+    pub fn with_energy_threshold(mut self, threshold: impl Into<Real>) -> Self {
+        self.energy_threshold = Some(threshold.into());
+        self
+    }
+
+    /// Flag relative drift of the angular momentum about any star beyond `threshold`
+    pub fn with_angular_momentum_threshold(mut self, threshold: impl Into<Real>) -> Self {
+        self.angular_momentum_threshold = Some(threshold.into());
+        self
+    }
+
+    /// Physics the simulation runs with
+    pub fn get_physics(&self) -> PhysicsConfig {
+        self.physics
+    }
+
+    /// Largest tolerated relative drift of the total energy
+    pub fn get_energy_threshold(&self) -> Option<Real> {
+        self.energy_threshold
+    }
+
+    /// Largest tolerated relative drift of the angular momentum
+    pub fn get_angular_momentum_threshold(&self) -> Option<Real> {
+        self.angular_momentum_threshold
+    }
+}
+
+/// Conserved quantities at one point in time
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticsSample {
+    time: Real,
+    kinetic_energy: Real,
+    potential_energy: Real,
+    angular_momentum: Vec<Real>,
+}
+
+impl DiagnosticsSample {
+    /// Time since the first sample
+    pub fn get_time(&self) -> Real {
+        self.time
+    }
+
+    /// Total kinetic energy of ships and missiles
+    pub fn get_kinetic_energy(&self) -> Real {
+        self.kinetic_energy
+    }
+
+    /// Total potential energy of ships and missiles
+    pub fn get_potential_energy(&self) -> Real {
+        self.potential_energy
+    }
+
+    /// Sum of kinetic and potential energy
+    pub fn get_total_energy(&self) -> Real {
+        self.kinetic_energy + self.potential_energy
+    }
+
+    /// Angular momentum of ships and missiles about each star, indexed by star id
+    pub fn get_angular_momentum(&self) -> &[Real] {
+        &self.angular_momentum
+    }
+}
+
+/// Diagnostics use-case
+///
+/// Ships and missiles without mass are treated as objects of unit mass, i.e. the diagnostics
+/// sum specific energies and angular momenta.
+pub struct Diagnostics {
+    config: DiagnosticsConfig,
+    samples: Vec<DiagnosticsSample>,
+    repo: DiagnosticsDG,
+}
+
+impl Diagnostics {
+    /// Create use case object
+    pub fn new(config: DiagnosticsConfig, repo: DiagnosticsDG) -> Self {
+        Self {
+            config,
+            samples: Vec::new(),
+            repo,
+        }
+    }
+
+    /// Record a sample `delta_time` after the previous one
+    ///
+    /// The time of the first sample is 0, regardless of `delta_time`.
+    pub fn execute(&mut self, delta_time: impl Into<Real>) -> Result<&DiagnosticsSample> {
+        let time = self
+            .samples
+            .last()
+            .map_or(0.0, |last| last.time + delta_time.into());
+        let physics = &self.config.physics;
+        let stars: Vec<Star> = self.repo.get_stars_position_and_mass()?.convert();
+        let bodies: Vec<(Vec2, Vec2, Real)> = self
+            .repo
+            .get_player_info()?
+            .into_iter()
+            .map(|(_, pos, vel, _)| (pos, vel, physics.get_player_mass()))
+            .chain(
+                self.repo
+                    .get_missile_info()?
+                    .into_iter()
+                    .map(|(_, _, pos, vel, _)| (pos, vel, physics.get_missile_mass())),
+            )
+            .map(|(pos, vel, mass)| (pos.convert(), vel.convert(), mass))
+            .collect();
+
+        let weight = |mass: Real| if mass > 0.0 { mass } else { 1.0 };
+        let potential = |mass: Real, from: Vec2, to: Vec2| {
+            let r2 = physics.separation(from, to).len2() + physics.get_softening().powi(2);
+            -physics.get_gravity_constant() * mass / r2.sqrt()
+        };
+        let kinetic_energy = bodies
+            .iter()
+            .map(|&(_, vel, mass)| 0.5 * weight(mass) * vel.len2())
+            .sum();
+        let mut potential_energy: Real = bodies
+            .iter()
+            .flat_map(|&(pos, _, mass)| {
+                stars
+                    .iter()
+                    .map(move |star| weight(mass) * potential(star.mass, star.pos, pos))
+            })
+            .sum();
+        if physics.is_n_body() {
+            for (i, &(pos_i, _, mass_i)) in bodies.iter().enumerate() {
+                for &(pos_j, _, mass_j) in bodies.iter().skip(i + 1) {
+                    if mass_i > 0.0 && mass_j > 0.0 {
+                        potential_energy += mass_i * potential(mass_j, pos_i, pos_j);
+                    }
+                }
+            }
+        }
+        let angular_momentum = stars
+            .iter()
+            .map(|star| {
+                bodies
+                    .iter()
+                    .map(|&(pos, vel, mass)| {
+                        let r = physics.separation(star.pos, pos);
+                        weight(mass) * (r.get_x() * vel.get_y() - r.get_y() * vel.get_x())
+                    })
+                    .sum()
+            })
+            .collect();
+
+        self.samples.push(DiagnosticsSample {
+            time,
+            kinetic_energy,
+            potential_energy,
+            angular_momentum,
+        });
+        Ok(&self.samples[self.samples.len() - 1])
+    }
+
+    /// All samples recorded so far
+    pub fn get_samples(&self) -> &[DiagnosticsSample] {
+        &self.samples
+    }
+
+    /// Discard all samples, e.g. after objects entered or left the game
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Largest relative deviation of the total energy from the first sample
+    pub fn get_energy_drift(&self) -> Real {
+        self.drift(|sample| vec![sample.get_total_energy()])
+    }
+
+    /// Largest relative deviation of the angular momentum about any star from the first sample
+    pub fn get_angular_momentum_drift(&self) -> Real {
+        self.drift(|sample| sample.angular_momentum.clone())
+    }
+
+    /// Whether any drift exceeds its configured threshold
+    pub fn has_drifted(&self) -> bool {
+        self.config
+            .energy_threshold
+            .is_some_and(|threshold| self.get_energy_drift() > threshold)
+            || self
+                .config
+                .angular_momentum_threshold
+                .is_some_and(|threshold| self.get_angular_momentum_drift() > threshold)
+    }
+
+    /// Largest relative deviation of the quantities from their values in the first sample
+    ///
+    /// Deviations of quantities which are initially 0 are absolute.
+    fn drift(&self, quantities: impl Fn(&DiagnosticsSample) -> Vec<Real>) -> Real {
+        let Some(first) = self.samples.first().map(&quantities) else {
+            return 0.0;
+        };
+        self.samples
+            .iter()
+            .flat_map(|sample| {
+                quantities(sample)
+                    .into_iter()
+                    .zip(first.iter())
+                    .map(|(value, &initial)| {
+                        let deviation = (value - initial).abs();
+                        if initial != 0.0 {
+                            deviation / initial.abs()
+                        } else {
+                            deviation
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .fold(0.0, Real::max)
+    }
+}
+
+/// Data repository interface for diagnostics use case
+///
+/// Implemented by all types implementing the gravity and integrate data gateways.
+pub trait DiagnosticsDataGateway: GravityDataGateway + IntegrateDataGateway {}
+
+impl<T: GravityDataGateway + IntegrateDataGateway + ?Sized> DiagnosticsDataGateway for T {}
+
+type DiagnosticsDG = Rc<dyn DiagnosticsDataGateway>;
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        entities::Real,
+        physics::{Gravity, Integrate, Integrator, PhysicsConfig, StarData},
+        repo::GameState,
+        repo_interfaces::IntegrateDataGateway,
+    };
+
+    use super::{Diagnostics, DiagnosticsConfig};
+
+    /// Orbit a ship around a star and record diagnostics after each tick
+    fn orbit(physics: PhysicsConfig, ticks: usize) -> Diagnostics {
+        let mut state = GameState::with_players(&[1]);
+        state.add_star(StarData::new([0.0, 0.0], 1000.0, 5.0));
+        let state = Rc::new(RefCell::new(state));
+        let speed = Real::sqrt(10.0);
+        state
+            .set_player_info(vec![(1, [100.0, 0.0], [0.0, speed], [0.0, 0.0])])
+            .unwrap();
+        let gravity = Gravity::new(physics, state.clone());
+        let integrate = Integrate::new(physics, state.clone());
+        let config = DiagnosticsConfig::new(physics)
+            .with_energy_threshold(1e-3)
+            .with_angular_momentum_threshold(1e-3);
+        let mut diagnostics = Diagnostics::new(config, state);
+        diagnostics.execute(0.1).unwrap();
+        for _ in 0..ticks {
+            gravity.execute().unwrap();
+            integrate.execute(0.1).unwrap();
+            diagnostics.execute(0.1).unwrap();
+        }
+        diagnostics
+    }
+
+    #[test]
+    fn conserved_quantities_computed_correctly() {
+        let diagnostics = orbit(PhysicsConfig::new(), 0);
+        let sample = &diagnostics.get_samples()[0];
+        assert_eq!(sample.get_time(), 0.0);
+        assert!((sample.get_kinetic_energy() - 5.0).abs() < 1e-5);
+        assert_eq!(sample.get_potential_energy(), -10.0);
+        assert!((sample.get_total_energy() + 5.0).abs() < 1e-5);
+        assert!((sample.get_angular_momentum()[0] - 100.0 * Real::sqrt(10.0)).abs() < 1e-3);
+        assert_eq!(diagnostics.get_energy_drift(), 0.0);
+    }
+
+    #[test]
+    fn symplectic_integration_does_not_drift() {
+        let physics = PhysicsConfig::new().with_integrator(Integrator::VelocityVerlet);
+        let diagnostics = orbit(physics, 1000);
+        assert_eq!(diagnostics.get_samples().len(), 1001);
+        assert!((diagnostics.get_samples()[1000].get_time() - 100.0).abs() < 1e-3);
+        assert!(
+            !diagnostics.has_drifted(),
+            "energy drift {}, angular momentum drift {}",
+            diagnostics.get_energy_drift(),
+            diagnostics.get_angular_momentum_drift()
+        );
+    }
+
+    #[test]
+    fn explicit_euler_drift_flagged() {
+        let physics = PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler);
+        let mut diagnostics = orbit(physics, 1000);
+        assert!(diagnostics.get_energy_drift() > 1e-3);
+        assert!(diagnostics.has_drifted());
+        diagnostics.reset();
+        assert!(diagnostics.get_samples().is_empty());
+        assert!(!diagnostics.has_drifted());
+    }
+
+    #[test]
+    fn mutual_potential_energy_included() {
+        let physics = PhysicsConfig::new()
+            .with_gravity_constant(2.0)
+            .with_player_mass(3.0);
+        let mut state = GameState::with_players(&[1, 2]);
+        state.add_star(StarData::new([0.0, 0.0], 0.0, 1.0));
+        let state = Rc::new(RefCell::new(state));
+        state
+            .set_player_info(vec![
+                (1, [0.0, 10.0], [1.0, 0.0], [0.0, 0.0]),
+                (2, [0.0, -10.0], [-1.0, 0.0], [0.0, 0.0]),
+            ])
+            .unwrap();
+        let mut diagnostics = Diagnostics::new(DiagnosticsConfig::new(physics), state);
+        let sample = diagnostics.execute(0.0).unwrap();
+        assert_eq!(sample.get_kinetic_energy(), 3.0);
+        assert!((sample.get_potential_energy() + 2.0 * 3.0 * 3.0 / 20.0).abs() < 1e-6);
+        assert_eq!(sample.get_angular_momentum(), &[-60.0]);
+    }
+}
@@ -1,23 +1,114 @@
 //! Core bussiness objects of Interception Orbit
 
 use std::{
+    fmt::Debug,
     iter::Sum,
-    ops::{Add, AddAssign, Div, Mul, Sub},
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
 };
 
-pub const PI: f32 = std::f32::consts::PI;
-pub const TWO_PI: f32 = 2_f32 * PI;
+pub mod fixed;
+
+/// Floating point type used throughout the crate, `f64` with the `f64` feature, `f32` otherwise
+#[cfg(not(feature = "f64"))]
+pub type Real = f32;
+/// Floating point type used throughout the crate, `f64` with the `f64` feature, `f32` otherwise
+#[cfg(feature = "f64")]
+pub type Real = f64;
+
+pub const PI: Real = <Real as Float>::PI;
+pub const TWO_PI: Real = 2.0 * PI;
+/// Default gravitational constant
+pub const GRAVITY_CONSTANT: Real = 1.0;
+
+/// Floating point scalar of [`Vec2`], implemented for `f32` and `f64`
+pub trait Float:
+    Copy
+    + Debug
+    + PartialOrd
+    + From<f32>
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + MulAssign
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const PI: Self;
+
+    fn sqrt(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn recip(self) -> Self;
+    fn is_finite(self) -> bool;
+    /// Lossless conversion to `f64`
+    fn to_f64(self) -> f64;
+    /// Conversion from `f64`, rounding to the nearest representable value
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_float {
+    ($float:ident) => {
+        impl Float for $float {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const PI: Self = std::$float::consts::PI;
+
+            fn sqrt(self) -> Self {
+                $float::sqrt(self)
+            }
+            fn sin(self) -> Self {
+                $float::sin(self)
+            }
+            fn cos(self) -> Self {
+                $float::cos(self)
+            }
+            fn atan2(self, other: Self) -> Self {
+                $float::atan2(self, other)
+            }
+            fn recip(self) -> Self {
+                $float::recip(self)
+            }
+            fn is_finite(self) -> bool {
+                $float::is_finite(self)
+            }
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+            fn from_f64(value: f64) -> Self {
+                value as $float
+            }
+        }
+
+        impl Mul<Vec2<$float>> for $float {
+            type Output = Vec2<$float>;
+
+            /// Scalar multiplication
+            fn mul(self, rhs: Vec2<$float>) -> Self::Output {
+                rhs * self
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
 
 /// Cartesian 2D Vector type
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Vec2 {
-    x: f32,
-    y: f32,
+pub struct Vec2<T: Float = Real> {
+    x: T,
+    y: T,
 }
 
-impl Vec2 {
+impl<T: Float> Vec2<T> {
     /// Create a new 2D vector
-    pub fn new(x: impl Into<f32>, y: impl Into<f32>) -> Self {
+    pub fn new(x: impl Into<T>, y: impl Into<T>) -> Self {
         Vec2 {
             x: x.into(),
             y: y.into(),
@@ -26,21 +117,29 @@ impl Vec2 {
 
     /// Zero vector (0.0, 0.0)
     pub fn zero() -> Self {
-        Vec2 { x: 0.0, y: 0.0 }
+        Vec2 {
+            x: T::ZERO,
+            y: T::ZERO,
+        }
     }
 
     /// Square of the length of a [`Vec2`]
-    pub fn len2(self) -> f32 {
+    pub fn len2(self) -> T {
         self.x * self.x + self.y * self.y
     }
 
     /// Length of a [`Vec2`]
-    pub fn len(self) -> f32 {
+    pub fn len(self) -> T {
         self.len2().sqrt()
     }
 
+    /// Whether neither component is infinite or NaN
+    pub fn is_finite(self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
     /// Return new [`Vec2`] with same direction but unit length
-    pub fn norm(self) -> Vec2 {
+    pub fn norm(self) -> Self {
         let len = self.len();
         Vec2 {
             x: self.x / len,
@@ -48,8 +147,85 @@ impl Vec2 {
         }
     }
 
+    /// Like [`Self::norm`], but `None` for the zero vector instead of NaN components
+    pub fn try_norm(self) -> Option<Self> {
+        (self.len2() > T::ZERO).then(|| self.norm())
+    }
+
+    /// Distance between two points
+    pub fn distance(self, rhs: Self) -> T {
+        (rhs - self).len()
+    }
+
+    /// Scale vector down to length `max` if it is longer
+    pub fn clamp_length(self, max: T) -> Self {
+        if self.len2() > max * max {
+            self.norm() * max
+        } else {
+            self
+        }
+    }
+
+    /// z-component of the 3D cross product, positive if `rhs` points counterclockwise of `self`
+    pub fn cross(self, rhs: Self) -> T {
+        self.x * rhs.y - self.y * rhs.x
+    }
+
+    /// Perpendicular vector of same length, i.e. rotated counterclockwise by 90°
+    pub fn perp(self) -> Self {
+        Vec2 {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Signed angle in radians in the range `[-PI, PI]` to rotate `self` onto the direction of
+    /// `rhs`, positive for counterclockwise rotations
+    pub fn angle_between(self, rhs: Self) -> T {
+        self.cross(rhs).atan2(self * rhs)
+    }
+
+    /// Linear interpolation, `self` for `t = 0` and `rhs` for `t = 1`
+    pub fn lerp(self, rhs: Self, t: T) -> Self {
+        self + (rhs - self) * t
+    }
+
+    /// Spherical interpolation, rotating from `self` towards `rhs` with constant angular speed
+    /// while interpolating the length linearly
+    ///
+    /// Falls back to [`Self::lerp`] if `self` is the zero vector.
+    pub fn slerp(self, rhs: Self, t: T) -> Self {
+        match self.try_norm() {
+            Some(dir) => {
+                let len = self.len() + (rhs.len() - self.len()) * t;
+                dir.rotate(self.angle_between(rhs) * t) * len
+            }
+            None => self.lerp(rhs, t),
+        }
+    }
+
+    /// Component of `self` parallel to `onto`, zero if `onto` is the zero vector
+    pub fn project(self, onto: Self) -> Self {
+        let len2 = onto.len2();
+        if len2 == T::ZERO {
+            Vec2::zero()
+        } else {
+            onto * ((self * onto) / len2)
+        }
+    }
+
+    /// Component of `self` perpendicular to `from`
+    pub fn reject(self, from: Self) -> Self {
+        self - self.project(from)
+    }
+
+    /// Mirror `self` at the line perpendicular to `normal`, e.g. bounce off a wall with that normal
+    pub fn reflect(self, normal: Self) -> Self {
+        self - self.project(normal) * T::from(2.0)
+    }
+
     /// Rotate vector by `angle` in radians
-    pub fn rotate(self, angle: f32) -> Self {
+    pub fn rotate(self, angle: T) -> Self {
         let Self { x, y } = self;
         let cos_angle = angle.cos();
         let sin_angle = angle.sin();
@@ -58,144 +234,194 @@ impl Vec2 {
     }
 
     /// getter for x value
-    pub fn get_x(&self) -> f32 {
+    pub fn get_x(&self) -> T {
         self.x
     }
 
     /// getter for x value
-    pub fn get_y(&self) -> f32 {
+    pub fn get_y(&self) -> T {
         self.y
     }
 
     /// Get angle with x-axis in radians
-    pub fn angle(&self) -> f32 {
+    pub fn angle(&self) -> T {
         trim_angle(self.y.atan2(self.x))
     }
+
+    /// Convert to a vector of another precision, e.g. between `f64` servers and `f32` clients
+    pub fn cast<U: Float>(self) -> Vec2<U> {
+        Vec2 {
+            x: U::from_f64(self.x.to_f64()),
+            y: U::from_f64(self.y.to_f64()),
+        }
+    }
 }
 
-impl Add<Vec2> for Vec2 {
-    type Output = Vec2;
+impl<T: Float> Add<Vec2<T>> for Vec2<T> {
+    type Output = Vec2<T>;
 
     /// Vector addition
-    fn add(self, rhs: Vec2) -> Self::Output {
+    fn add(self, rhs: Vec2<T>) -> Self::Output {
         Self::Output::new(self.x + rhs.x, self.y + rhs.y)
     }
 }
 
-impl AddAssign<Vec2> for Vec2 {
-    fn add_assign(&mut self, rhs: Vec2) {
+impl<T: Float> AddAssign<Vec2<T>> for Vec2<T> {
+    fn add_assign(&mut self, rhs: Vec2<T>) {
         self.x += rhs.x;
         self.y += rhs.y;
     }
 }
 
-impl<'a> Sum<&'a Vec2> for Vec2 {
+impl<'a, T: Float> Sum<&'a Vec2<T>> for Vec2<T> {
     fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
-        let mut result = Vec2::new(0.0, 0.0);
+        let mut result = Vec2::zero();
         iter.for_each(|item| result += *item);
         result
     }
 }
 
-impl Sum<Vec2> for Vec2 {
-    fn sum<I: Iterator<Item = Vec2>>(iter: I) -> Self {
-        let mut result = Vec2::new(0.0, 0.0);
+impl<T: Float> Sum<Vec2<T>> for Vec2<T> {
+    fn sum<I: Iterator<Item = Vec2<T>>>(iter: I) -> Self {
+        let mut result = Vec2::zero();
         iter.for_each(|item| result += item);
         result
     }
 }
 
-impl Sub<Vec2> for Vec2 {
-    type Output = Vec2;
+impl<T: Float> Sub<Vec2<T>> for Vec2<T> {
+    type Output = Vec2<T>;
 
     /// Vector difference
-    fn sub(self, rhs: Vec2) -> Self::Output {
+    fn sub(self, rhs: Vec2<T>) -> Self::Output {
         Self::Output::new(self.x - rhs.x, self.y - rhs.y)
     }
 }
 
-impl Mul<f32> for Vec2 {
-    type Output = Vec2;
+impl<T: Float> SubAssign<Vec2<T>> for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Vec2<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Float> Neg for Vec2<T> {
+    type Output = Vec2<T>;
 
-    /// Scalar multiplication
-    fn mul(self, rhs: f32) -> Self::Output {
-        Self::Output::new(self.x * rhs, self.y * rhs)
+    /// Vector pointing in the opposite direction
+    fn neg(self) -> Self::Output {
+        Self::Output::new(-self.x, -self.y)
     }
 }
 
-impl Mul<Vec2> for f32 {
-    type Output = Vec2;
+impl<T: Float> Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
 
     /// Scalar multiplication
-    fn mul(self, rhs: Vec2) -> Self::Output {
-        rhs * self
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::Output::new(self.x * rhs, self.y * rhs)
     }
 }
 
-impl Div<f32> for Vec2 {
-    type Output = Vec2;
+impl<T: Float> MulAssign<T> for Vec2<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T: Float> Div<T> for Vec2<T> {
+    type Output = Vec2<T>;
 
     /// Scalar division
-    fn div(self, rhs: f32) -> Self::Output {
-        if rhs == 0.0 {
-            Self::Output::new(0.0, 0.0)
+    fn div(self, rhs: T) -> Self::Output {
+        if rhs == T::ZERO {
+            Self::Output::zero()
         } else {
             self * rhs.recip()
         }
     }
 }
 
-impl Mul<Vec2> for Vec2 {
-    type Output = f32;
+impl<T: Float> Mul<Vec2<T>> for Vec2<T> {
+    type Output = T;
 
-    fn mul(self, rhs: Vec2) -> Self::Output {
+    fn mul(self, rhs: Vec2<T>) -> Self::Output {
         self.x * rhs.x + self.y * rhs.y
     }
 }
 
 /// Trim angle in radians to [0, 2*PI]
-pub fn trim_angle(angle: impl Into<f32>) -> f32 {
-    let mut result = angle.into() % TWO_PI;
-    while result < 0_f32 {
-        result += TWO_PI
+pub fn trim_angle<T: Float>(angle: T) -> T {
+    let two_pi = T::from(2.0) * T::PI;
+    let mut result = angle % two_pi;
+    while result < T::ZERO {
+        result += two_pi
     }
     result
 }
 
 /// gravitational acceleration
-pub fn gravity(attractant_position: Vec2, attractant_mass: f32, body_position: Vec2) -> Vec2 {
-    const GRAVITY_CONSTANT: f32 = 1.0;
+///
+/// A body at the position of the attractant is not accelerated.
+#[cfg(test)]
+pub fn gravity<T: Float>(
+    attractant_position: Vec2<T>,
+    attractant_mass: T,
+    body_position: Vec2<T>,
+) -> Vec2<T> {
+    softened_gravity(
+        attractant_position,
+        T::from_f64(GRAVITY_CONSTANT.to_f64()) * attractant_mass,
+        body_position,
+        T::ZERO,
+    )
+}
+
+/// gravitational acceleration with Plummer softening
+///
+/// `gravitational_parameter` is the product of gravitational constant and mass of the
+/// attractant. Within the softening length the acceleration stays finite and drops to zero
+/// towards the position of the attractant.
+pub fn softened_gravity<T: Float>(
+    attractant_position: Vec2<T>,
+    gravitational_parameter: T,
+    body_position: Vec2<T>,
+    softening: T,
+) -> Vec2<T> {
     let r = attractant_position - body_position;
-    let r2 = r.len2();
-    r.norm() * GRAVITY_CONSTANT * attractant_mass / r2
+    let d2 = r.len2() + softening * softening;
+    if d2 == T::ZERO {
+        return Vec2::zero();
+    }
+    r * (gravitational_parameter / (d2 * d2.sqrt()))
 }
 
 #[cfg(test)]
 mod test {
 
-    use std::f32::{
-        consts::{FRAC_PI_2, FRAC_PI_4},
-        EPSILON,
-    };
+    const EPSILON: Real = Real::EPSILON;
+    const FRAC_PI_2: Real = PI / 2.0;
+    const FRAC_PI_4: Real = PI / 4.0;
 
-    use super::{gravity, trim_angle, Vec2, PI, TWO_PI};
+    use super::{gravity, softened_gravity, trim_angle, Real, Vec2, PI, TWO_PI};
 
     #[test]
     fn vec2_can_be_created_with_other_input() {
-        assert_eq!(Vec2::new(1_i16, 0.0), Vec2::new(1.0, 0.0))
+        assert_eq!(Vec2::<Real>::new(1_i16, 0.0), Vec2::new(1.0, 0.0))
     }
 
     #[test]
     fn vec2_len_is_correct() {
-        assert_eq!(Vec2::new(2.0, 0.0).len(), 2.0);
-        assert_eq!(Vec2::new(0.0, 2.0).len(), 2.0);
-        assert_eq!(Vec2::new(0.0, -2.0).len(), 2.0);
-        assert_eq!(Vec2::new(5.0, -2.0).len(), (29.0_f32).sqrt());
+        assert_eq!(Vec2::<Real>::new(2.0, 0.0).len(), 2.0);
+        assert_eq!(Vec2::<Real>::new(0.0, 2.0).len(), 2.0);
+        assert_eq!(Vec2::<Real>::new(0.0, -2.0).len(), 2.0);
+        assert_eq!(Vec2::<Real>::new(5.0, -2.0).len(), Real::sqrt(29.0));
     }
 
     #[test]
     fn vec2_normalizes_correct() {
-        assert_eq!(Vec2::new(5.0, -2.0).norm().len(), 1.0);
+        assert_eq!(Vec2::<Real>::new(5.0, -2.0).norm().len(), 1.0);
     }
 
     #[test]
@@ -214,12 +440,12 @@ mod test {
 
     #[test]
     fn angle_of_vec2_correctly_computed() {
-        assert!(Vec2::new(1.0, 0.0).angle() == 0.);
-        assert!((Vec2::new(0.0, 1.0).angle() - FRAC_PI_2).abs() < EPSILON);
-        assert!((Vec2::new(-1.0, 0.0).angle() - PI).abs() < 2.1 * EPSILON);
-        assert!((Vec2::new(0.0, -1.0).angle() - 3.0 * FRAC_PI_2).abs() < EPSILON);
-        assert!(Vec2::new(1.0, 1.0).angle() - FRAC_PI_4 < EPSILON);
-        assert!(Vec2::new(-1.0, -1.0).angle() - 5.0 * FRAC_PI_4 < EPSILON);
+        assert!(Vec2::<Real>::new(1.0, 0.0).angle() == 0.);
+        assert!((Vec2::<Real>::new(0.0, 1.0).angle() - FRAC_PI_2).abs() < EPSILON);
+        assert!((Vec2::<Real>::new(-1.0, 0.0).angle() - PI).abs() < 2.1 * EPSILON);
+        assert!((Vec2::<Real>::new(0.0, -1.0).angle() - 3.0 * FRAC_PI_2).abs() < EPSILON);
+        assert!(Vec2::<Real>::new(1.0, 1.0).angle() - FRAC_PI_4 < EPSILON);
+        assert!(Vec2::<Real>::new(-1.0, -1.0).angle() - 5.0 * FRAC_PI_4 < EPSILON);
     }
 
     #[test]
@@ -236,6 +462,90 @@ mod test {
         )
     }
 
+    #[test]
+    fn vec2_casts_between_precisions() {
+        let far = Vec2::<f64>::new(1e8 + 0.5, 0.5);
+        assert_eq!(far.cast::<f32>(), Vec2::new(1e8, 0.5));
+        assert_eq!(far.cast::<f32>().cast::<f64>(), Vec2::new(1e8, 0.5));
+        assert_eq!(
+            gravity(Vec2::<f64>::zero(), 10.0, Vec2::new(2.0, 0.0)).cast(),
+            gravity(Vec2::<f32>::zero(), 10.0, Vec2::new(2.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn vec2_try_norm_handles_zero_vector() {
+        assert_eq!(Vec2::<Real>::zero().try_norm(), None);
+        assert!(Vec2::<Real>::zero().norm().get_x().is_nan());
+        assert_eq!(
+            Vec2::<Real>::new(0.0, -3.0).try_norm(),
+            Some(Vec2::new(0.0, -1.0))
+        );
+    }
+
+    #[test]
+    fn vec2_operators() {
+        let mut vec: Vec2 = Vec2::new(1.0, -2.0);
+        assert_eq!(-vec, Vec2::new(-1.0, 2.0));
+        vec -= Vec2::new(3.0, 1.0);
+        assert_eq!(vec, Vec2::new(-2.0, -3.0));
+        vec *= 2.0;
+        assert_eq!(vec, Vec2::new(-4.0, -6.0));
+    }
+
+    #[test]
+    fn vec2_distance_and_clamp_length() {
+        let vec: Vec2 = Vec2::new(3.0, 4.0);
+        assert_eq!(vec.distance(Vec2::new(-3.0, -4.0)), 10.0);
+        assert_eq!(vec.clamp_length(10.0), vec);
+        assert!((vec.clamp_length(2.5) - Vec2::new(1.5, 2.0)).len() < EPSILON);
+        assert_eq!(Vec2::<Real>::zero().clamp_length(0.0), Vec2::zero());
+    }
+
+    #[test]
+    fn vec2_cross_perp_and_angle_between() {
+        let x: Vec2 = Vec2::new(2.0, 0.0);
+        let y: Vec2 = Vec2::new(0.0, 3.0);
+        assert_eq!(x.cross(y), 6.0);
+        assert_eq!(y.cross(x), -6.0);
+        assert_eq!(x.cross(x * 4.0), 0.0);
+        assert_eq!(x.perp(), Vec2::new(0.0, 2.0));
+        assert_eq!(x.perp() * x, 0.0);
+        assert!((x.angle_between(y) - FRAC_PI_2).abs() < EPSILON);
+        assert!((y.angle_between(x) + FRAC_PI_2).abs() < EPSILON);
+        assert!((x.angle_between(Vec2::new(-1.0, -1.0)) + 3.0 * FRAC_PI_4).abs() < 2.0 * EPSILON);
+        assert!((x.angle_between(-x) - PI).abs() < EPSILON);
+    }
+
+    #[test]
+    fn vec2_interpolation() {
+        let from: Vec2 = Vec2::new(2.0, 0.0);
+        let to: Vec2 = Vec2::new(0.0, 4.0);
+        assert_eq!(from.lerp(to, 0.0), from);
+        assert_eq!(from.lerp(to, 1.0), to);
+        assert_eq!(from.lerp(to, 0.5), Vec2::new(1.0, 2.0));
+        assert!((from.slerp(to, 1.0) - to).len() < 4.0 * EPSILON);
+        let half = from.slerp(to, 0.5);
+        assert!((half.len() - 3.0).abs() < 4.0 * EPSILON);
+        assert!((half.angle() - FRAC_PI_4).abs() < EPSILON);
+        assert_eq!(Vec2::zero().slerp(to, 0.5), Vec2::new(0.0, 2.0));
+    }
+
+    #[test]
+    fn vec2_project_reject_and_reflect() {
+        let vec: Vec2 = Vec2::new(3.0, -4.0);
+        let axis: Vec2 = Vec2::new(2.0, 0.0);
+        assert_eq!(vec.project(axis), Vec2::new(3.0, 0.0));
+        assert_eq!(vec.reject(axis), Vec2::new(0.0, -4.0));
+        assert_eq!(vec.project(Vec2::zero()), Vec2::zero());
+        assert_eq!(vec.reject(Vec2::zero()), vec);
+        // Bounce off a floor with upward normal of arbitrary length
+        assert_eq!(vec.reflect(Vec2::new(0.0, 5.0)), Vec2::new(3.0, 4.0));
+        let diagonal = vec.reflect(Vec2::new(1.0, 1.0));
+        assert!((diagonal - Vec2::new(4.0, -3.0)).len() < 4.0 * EPSILON);
+        assert_eq!(diagonal.len(), vec.len());
+    }
+
     #[test]
     fn gravity_computed_correctly() {
         let pos1 = Vec2::new(0.0, 0.0);
@@ -243,4 +553,22 @@ mod test {
         let mass = 10.0;
         assert_eq!(gravity(pos1, mass, pos2), Vec2::new(-10.0 / 4.0, 0.0));
     }
+
+    #[test]
+    fn gravity_vanishes_at_attractant() {
+        let pos = Vec2::new(1.0, 2.0);
+        assert_eq!(gravity(pos, 10.0, pos), Vec2::zero());
+        assert_eq!(softened_gravity(pos, 10.0, pos, 0.5), Vec2::zero());
+    }
+
+    #[test]
+    fn softening_bounds_gravity() {
+        let star = Vec2::zero();
+        let near = softened_gravity(star, 10.0, Vec2::new(1e-3, 0.0), 1.0);
+        assert!(near.len() < 0.01);
+        let far = Vec2::new(1000.0, 0.0);
+        let relative = (softened_gravity(star, 10.0, far, 1.0) - gravity(star, 10.0, far)).len()
+            / gravity(star, 10.0, far).len();
+        assert!(relative < 1e-5);
+    }
 }
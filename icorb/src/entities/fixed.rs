@@ -0,0 +1,502 @@
+//! Deterministic fixed-point arithmetic
+//!
+//! Transcendental functions like `sin`, `cos` or `atan2` of floating point numbers may return
+//! different results on different platforms. [`Fixed`] numbers use integer arithmetic only and
+//! compute trigonometric functions with the CORDIC algorithm, such that all results are
+//! bit-identical everywhere. Operations saturate instead of overflowing.
+
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub},
+};
+
+use super::{Float, Vec2};
+
+/// Number of fractional bits
+const FRAC_BITS: u32 = 32;
+
+/// `atan(2^-i)` for all CORDIC iterations `i`
+const ATAN_TABLE: [i64; 32] = [
+    3373259426, 1991351318, 1052175346, 534100635, 268086748, 134174063, 67103403, 33553749,
+    16777131, 8388597, 4194303, 2097152, 1048576, 524288, 262144, 131072, 65536, 32768, 16384,
+    8192, 4096, 2048, 1024, 512, 256, 128, 64, 32, 16, 8, 4, 2,
+];
+
+/// Inverse of the CORDIC gain after all iterations
+const CORDIC_SCALE: i64 = 2608131496;
+
+/// Fixed-point number with 32 integer and 32 fractional bits
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(1 << FRAC_BITS);
+    pub const HALF_PI: Fixed = Fixed(6746518852);
+    pub const PI: Fixed = Fixed(13493037705);
+    pub const TWO_PI: Fixed = Fixed(26986075409);
+
+    /// Fixed-point number with the raw bits `bits`
+    pub const fn from_bits(bits: i64) -> Self {
+        Fixed(bits)
+    }
+
+    /// Raw bits of the fixed-point number
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Clamp a raw value to the representable range
+    fn saturate(raw: i128) -> Self {
+        Fixed(raw.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+    }
+
+    /// Nearest floating point value
+    pub fn to_float<T: Float>(self) -> T {
+        T::from_f64(self.0 as f64 / Self::ONE.0 as f64)
+    }
+
+    /// Absolute value
+    pub fn abs(self) -> Self {
+        Fixed(self.0.saturating_abs())
+    }
+
+    /// Nearest integer, rounding half-way cases up
+    pub fn round(self) -> Self {
+        let half = Self::ONE.0 >> 1;
+        Fixed(self.0.saturating_add(half) & !(Self::ONE.0 - 1))
+    }
+
+    /// Least nonnegative remainder of `self` divided by `rhs`
+    pub fn rem_euclid(self, rhs: Fixed) -> Self {
+        Fixed(self.0.rem_euclid(rhs.0))
+    }
+
+    /// Square root, 0 for negative numbers
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        Fixed((((self.0 as u128) << FRAC_BITS).isqrt()) as i64)
+    }
+
+    /// Sine and cosine of an angle in radians
+    pub fn sin_cos(self) -> (Fixed, Fixed) {
+        // Reduce the angle to [-PI/2, PI/2], within the convergence range of CORDIC
+        let mut angle = (self + Self::PI).rem_euclid(Self::TWO_PI) - Self::PI;
+        let mut sign = 1;
+        if angle > Self::HALF_PI {
+            angle = angle - Self::PI;
+            sign = -1;
+        } else if angle < -Self::HALF_PI {
+            angle += Self::PI;
+            sign = -1;
+        }
+        let (mut x, mut y, mut z) = (CORDIC_SCALE, 0, angle.0);
+        for (i, &atan) in ATAN_TABLE.iter().enumerate() {
+            let (dx, dy) = (y >> i, x >> i);
+            if z >= 0 {
+                (x, y, z) = (x - dx, y + dy, z - atan);
+            } else {
+                (x, y, z) = (x + dx, y - dy, z + atan);
+            }
+        }
+        (Fixed(sign * y), Fixed(sign * x))
+    }
+
+    /// Sine of an angle in radians
+    pub fn sin(self) -> Self {
+        self.sin_cos().0
+    }
+
+    /// Cosine of an angle in radians
+    pub fn cos(self) -> Self {
+        self.sin_cos().1
+    }
+
+    /// Four quadrant arctangent of `self` (y) and `other` (x) in the range `(-PI, PI]`
+    pub fn atan2(self, other: Fixed) -> Self {
+        let (mut x, mut y) = (other.0, self.0);
+        if x == 0 && y == 0 {
+            return Self::ZERO;
+        }
+        // Normalize for full precision without overflow by the CORDIC gain
+        while x.unsigned_abs().max(y.unsigned_abs()) >= 1 << 61 {
+            (x, y) = (x >> 1, y >> 1);
+        }
+        while x.unsigned_abs().max(y.unsigned_abs()) < 1 << 60 {
+            (x, y) = (x << 1, y << 1);
+        }
+        let mut z = 0;
+        if x < 0 {
+            z = if y >= 0 { Self::PI.0 } else { -Self::PI.0 };
+            (x, y) = (-x, -y);
+        }
+        for (i, &atan) in ATAN_TABLE.iter().enumerate() {
+            let (dx, dy) = (y >> i, x >> i);
+            if y > 0 {
+                (x, y, z) = (x + dx, y - dy, z + atan);
+            } else {
+                (x, y, z) = (x - dx, y + dy, z - atan);
+            }
+        }
+        Fixed(z)
+    }
+}
+
+impl<T: Float> From<T> for Fixed {
+    /// Nearest fixed-point number, saturating for large and 0 for NaN values
+    fn from(value: T) -> Self {
+        Fixed((value.to_f64() * Self::ONE.0 as f64) as i64)
+    }
+}
+
+impl Add<Fixed> for Fixed {
+    type Output = Fixed;
+
+    fn add(self, rhs: Fixed) -> Self::Output {
+        Fixed(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl AddAssign<Fixed> for Fixed {
+    fn add_assign(&mut self, rhs: Fixed) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Fixed> for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, rhs: Fixed) -> Self::Output {
+        Fixed(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Self::Output {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl Mul<Fixed> for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, rhs: Fixed) -> Self::Output {
+        Self::saturate((self.0 as i128 * rhs.0 as i128) >> FRAC_BITS)
+    }
+}
+
+impl Div<Fixed> for Fixed {
+    type Output = Fixed;
+
+    /// Quotient, division by zero saturates
+    fn div(self, rhs: Fixed) -> Self::Output {
+        if rhs.0 == 0 {
+            return Self::saturate(self.0.signum() as i128 * i128::MAX);
+        }
+        Self::saturate(((self.0 as i128) << FRAC_BITS) / rhs.0 as i128)
+    }
+}
+
+/// Two dimensional vector of fixed-point numbers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FixedVec2 {
+    x: Fixed,
+    y: Fixed,
+}
+
+impl FixedVec2 {
+    /// Create new vector
+    pub fn new(x: impl Into<Fixed>, y: impl Into<Fixed>) -> Self {
+        FixedVec2 {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Zero vector
+    pub fn zero() -> Self {
+        FixedVec2::default()
+    }
+
+    /// Squared length
+    pub fn len2(self) -> Fixed {
+        self * self
+    }
+
+    /// Length, exact for vectors too long to square
+    pub fn len(self) -> Fixed {
+        Fixed::saturate(self.raw_len2().isqrt() as i128)
+    }
+
+    /// Normalized vector, zero for the zero vector
+    pub fn norm(self) -> Self {
+        let len = self.len();
+        if len == Fixed::ZERO {
+            return self;
+        }
+        self / len
+    }
+
+    /// Rotate vector by `angle` in radians
+    pub fn rotate(self, angle: Fixed) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        FixedVec2 {
+            x: cos * self.x - sin * self.y,
+            y: sin * self.x + cos * self.y,
+        }
+    }
+
+    /// Angle of the vector in the range `[0, 2*PI)`
+    pub fn angle(self) -> Fixed {
+        self.y.atan2(self.x).rem_euclid(Fixed::TWO_PI)
+    }
+
+    /// x component
+    pub fn get_x(&self) -> Fixed {
+        self.x
+    }
+
+    /// y component
+    pub fn get_y(&self) -> Fixed {
+        self.y
+    }
+
+    /// Squared length with 64 fractional bits
+    fn raw_len2(self) -> u128 {
+        let (x, y) = (
+            self.x.0.unsigned_abs() as u128,
+            self.y.0.unsigned_abs() as u128,
+        );
+        x * x + y * y
+    }
+}
+
+impl<T: Float> From<Vec2<T>> for FixedVec2 {
+    fn from(value: Vec2<T>) -> Self {
+        FixedVec2::new(value.x, value.y)
+    }
+}
+
+impl<T: Float> From<FixedVec2> for Vec2<T> {
+    fn from(value: FixedVec2) -> Self {
+        Vec2::new(value.x.to_float::<T>(), value.y.to_float::<T>())
+    }
+}
+
+impl Add<FixedVec2> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn add(self, rhs: FixedVec2) -> Self::Output {
+        FixedVec2 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl AddAssign<FixedVec2> for FixedVec2 {
+    fn add_assign(&mut self, rhs: FixedVec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum<FixedVec2> for FixedVec2 {
+    fn sum<I: Iterator<Item = FixedVec2>>(iter: I) -> Self {
+        iter.fold(FixedVec2::zero(), |a, b| a + b)
+    }
+}
+
+impl Sub<FixedVec2> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn sub(self, rhs: FixedVec2) -> Self::Output {
+        FixedVec2 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl Mul<Fixed> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn mul(self, rhs: Fixed) -> Self::Output {
+        FixedVec2 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl Div<Fixed> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn div(self, rhs: Fixed) -> Self::Output {
+        FixedVec2 {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+/// Dot product
+impl Mul<FixedVec2> for FixedVec2 {
+    type Output = Fixed;
+
+    fn mul(self, rhs: FixedVec2) -> Self::Output {
+        self.x * rhs.x + self.y * rhs.y
+    }
+}
+
+/// Fixed-point variant of [`super::softened_gravity`]
+///
+/// Intermediate results are kept in 128 bit to cover distances whose cube exceeds the range of
+/// [`Fixed`].
+pub fn softened_gravity(
+    attractant_position: FixedVec2,
+    gravitational_parameter: Fixed,
+    body_position: FixedVec2,
+    softening: Fixed,
+) -> FixedVec2 {
+    let r = attractant_position - body_position;
+    let softening = softening.0.unsigned_abs() as u128;
+    let d2 = r.raw_len2() + softening * softening;
+    if d2 == 0 {
+        return FixedVec2::zero();
+    }
+    // Shift the squared distance further before multiplying if the product would overflow
+    let d3 = match (d2 >> FRAC_BITS).checked_mul(d2.isqrt()) {
+        Some(d3) => d3 >> FRAC_BITS,
+        None => (d2 >> (2 * FRAC_BITS)) * d2.isqrt(),
+    };
+    if d3 == 0 {
+        return FixedVec2::zero();
+    }
+    let component =
+        |r: Fixed| Fixed::saturate(r.0 as i128 * gravitational_parameter.0 as i128 / d3 as i128);
+    FixedVec2 {
+        x: component(r.x),
+        y: component(r.y),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{softened_gravity, Fixed, FixedVec2};
+    use crate::entities::{self, Vec2};
+
+    fn assert_close(actual: Fixed, expected: f64) {
+        let actual = actual.0 as f64 / Fixed::ONE.0 as f64;
+        assert!((actual - expected).abs() < 1e-8, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn arithmetic_is_exact() {
+        let (a, b) = (Fixed::from(6.5), Fixed::from(-2.0));
+        assert_eq!((a + b).to_float::<f32>(), 4.5);
+        assert_eq!((a - b).to_float::<f32>(), 8.5);
+        assert_eq!((a * b).to_float::<f32>(), -13.0);
+        assert_eq!((a / b).to_float::<f32>(), -3.25);
+        assert_eq!((-a).to_float::<f32>(), -6.5);
+        assert_eq!(Fixed::from(2.5).round().to_float::<f32>(), 3.0);
+        assert_eq!(Fixed::from(-2.5).round().to_float::<f32>(), -2.0);
+        assert_eq!(
+            Fixed::from(-1.0)
+                .rem_euclid(Fixed::from(3.0))
+                .to_float::<f32>(),
+            2.0
+        );
+    }
+
+    #[test]
+    fn overflow_saturates() {
+        let large = Fixed::from(2e9);
+        assert_eq!((large * large).0, i64::MAX);
+        assert_eq!((-large * large).0, i64::MIN);
+        assert_eq!((Fixed::ONE / Fixed::ZERO).0, i64::MAX);
+        assert_eq!(Fixed::from(f32::NAN), Fixed::ZERO);
+    }
+
+    #[test]
+    fn square_root_computed_correctly() {
+        assert_eq!(Fixed::from(16.0).sqrt().to_float::<f32>(), 4.0);
+        assert_close(Fixed::from(2.0).sqrt(), 2f64.sqrt());
+        assert_eq!(Fixed::from(-1.0).sqrt(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn trigonometric_functions_match_float() {
+        for i in -100..=100 {
+            let angle = i as f64 * 0.1;
+            let (sin, cos) = Fixed::from(angle as f32).sin_cos();
+            let angle = angle as f32 as f64;
+            assert_close(sin, angle.sin());
+            assert_close(cos, angle.cos());
+        }
+    }
+
+    #[test]
+    fn arctangent_matches_float() {
+        for i in 0..64 {
+            let angle = i as f64 * std::f64::consts::TAU / 64.0;
+            let (x, y) = (3.0 * angle.cos(), 3.0 * angle.sin());
+            let fixed = Fixed::from(y as f32).atan2(Fixed::from(x as f32));
+            assert_close(fixed, (y as f32 as f64).atan2(x as f32 as f64));
+        }
+        assert_eq!(Fixed::ZERO.atan2(Fixed::ZERO), Fixed::ZERO);
+        assert_close(Fixed::ZERO.atan2(Fixed::from(-1.0)), std::f64::consts::PI);
+    }
+
+    #[test]
+    fn vectors_rotate_and_measure_angles() {
+        let v = FixedVec2::new(2.0, 0.0).rotate(Fixed::HALF_PI);
+        assert_close(v.get_x(), 0.0);
+        assert_close(v.get_y(), 2.0);
+        assert_close(v.angle(), std::f64::consts::FRAC_PI_2);
+        assert_close(
+            FixedVec2::new(0.0, -1.0).angle(),
+            1.5 * std::f64::consts::PI,
+        );
+        assert_eq!(FixedVec2::new(3.0, -4.0).len().to_float::<f32>(), 5.0);
+        assert_eq!(FixedVec2::new(3e5, 4e5).len().to_float::<f32>(), 5e5);
+        assert!(FixedVec2::new(2e9, -2e9).len() > Fixed::from(2e9));
+        assert_close(FixedVec2::new(3.0, -4.0).norm().len(), 1.0);
+    }
+
+    #[test]
+    fn gravity_matches_float() {
+        let cases = [
+            ([0.0, 0.0], 1000.0, [3.0, 4.0], 0.0),
+            ([10.0, -5.0], 5.0, [2e3, 1e3], 2.0),
+            ([0.0, 0.0], 1e9, [6e6, -8e6], 0.0),
+        ];
+        for (attractant, mass, body, softening) in cases {
+            let expected = entities::softened_gravity(
+                Vec2::new(attractant[0], attractant[1]),
+                mass,
+                Vec2::new(body[0], body[1]),
+                softening,
+            );
+            let actual: Vec2 = softened_gravity(
+                FixedVec2::new(attractant[0], attractant[1]),
+                Fixed::from(mass),
+                FixedVec2::new(body[0], body[1]),
+                Fixed::from(softening),
+            )
+            .into();
+            // Relative accuracy limited by the resolution of 2^-32
+            assert!((actual - expected).len() <= 1e-5 * expected.len() + 1e-9);
+        }
+        let at_attractant = softened_gravity(
+            FixedVec2::zero(),
+            Fixed::ONE,
+            FixedVec2::zero(),
+            Fixed::ZERO,
+        );
+        assert_eq!(at_attractant, FixedVec2::zero());
+    }
+}
@@ -0,0 +1,62 @@
+//! Domain errors of Interception Orbit
+
+use std::fmt;
+
+use crate::repo_interfaces::{MissileIdData, PlayerIdData, StarId};
+
+/// Errors raised by data gateways and propagated by use-cases
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// No player with the given id exists
+    UnknownPlayer(PlayerIdData),
+    /// No star with the given id exists
+    UnknownStar(StarId),
+    /// A player with the given id already exists
+    PlayerAlreadyExists(PlayerIdData),
+    /// The player has no missile with the given id
+    UnknownMissile(PlayerIdData, MissileIdData),
+    /// No more objects of the named kind can be stored
+    CapacityExceeded(&'static str),
+    /// The operation is not allowed in the current state
+    InvalidState(&'static str),
+    /// A value is outside of its valid range
+    InvalidValue(String),
+    /// A computation resulted in an infinite value or NaN
+    NonFinite(String),
+    /// A snapshot can't be parsed or its checksum does not match
+    CorruptSnapshot(String),
+    /// A snapshot was written in an unsupported format version
+    UnsupportedSnapshotVersion(u32),
+    /// A map file can't be parsed
+    MapParse { line: usize, message: String },
+    /// Reading or writing failed
+    Io(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownPlayer(id) => write!(f, "unknown player {id}"),
+            Error::UnknownStar(id) => write!(f, "unknown star {id}"),
+            Error::PlayerAlreadyExists(id) => write!(f, "player {id} already exists"),
+            Error::UnknownMissile(p_id, m_id) => {
+                write!(f, "unknown missile {m_id} of player {p_id}")
+            }
+            Error::CapacityExceeded(kind) => write!(f, "capacity for {kind} exceeded"),
+            Error::InvalidState(msg) => write!(f, "invalid state: {msg}"),
+            Error::InvalidValue(msg) => write!(f, "invalid value: {msg}"),
+            Error::NonFinite(msg) => write!(f, "non-finite {msg}"),
+            Error::CorruptSnapshot(msg) => write!(f, "corrupt snapshot: {msg}"),
+            Error::UnsupportedSnapshotVersion(version) => {
+                write!(f, "unsupported snapshot version {version}")
+            }
+            Error::MapParse { line, message } => write!(f, "map line {line}: {message}"),
+            Error::Io(msg) => write!(f, "I/O error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Result type of all fallible operations
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,585 @@
+//! Match rules: rounds, scoring and win conditions
+//!
+//! A match starts in the lobby. Once started, every round begins with a countdown and ends as
+//! soon as at most one ship survives. The last survivor wins the round; the first player to win
+//! the configured number of rounds wins the match.
+
+use std::rc::Rc;
+
+use crate::{
+    damage::PlayerDestroyed,
+    entities::{Real, Vec2},
+    repo_interfaces::{Marshalling, PlayerId, PlayerIdData, Vec2Data},
+    Error, Result,
+};
+
+/// Configuration object for a match
+#[derive(Clone, Debug)]
+pub struct MatchConfig {
+    /// Number of round wins required to win the match
+    rounds_to_win: u32,
+    /// Duration of the countdown before a round
+    countdown: Real,
+    /// Duration of the pause after a round
+    round_over_duration: Real,
+    /// Minimum number of player required to start the match
+    min_players: usize,
+    /// `(position, orientation)` of the ships at the start of a round
+    spawn_points: Vec<(Vec2, Real)>,
+}
+
+impl MatchConfig {
+    /// Create a new match configuration for two or more player
+    ///
+    /// Rounds start immediately and without a pause in between. Without spawn points, every
+    /// round starts at the origin.
+    pub fn new(rounds_to_win: u32) -> Self {
+        MatchConfig {
+            rounds_to_win,
+            countdown: 0.0,
+            round_over_duration: 0.0,
+            min_players: 2,
+            spawn_points: Vec::new(),
+        }
+    }
+
+    /// Wait `countdown` before and `round_over_duration` after each round
+    pub fn with_durations(
+        mut self,
+        countdown: impl Into<Real>,
+        round_over_duration: impl Into<Real>,
+    ) -> MatchConfig {
+        self.countdown = countdown.into();
+        self.round_over_duration = round_over_duration.into();
+        self
+    }
+
+    /// Require at least `min_players` player to start the match
+    pub fn with_min_players(mut self, min_players: usize) -> MatchConfig {
+        self.min_players = min_players;
+        self
+    }
+
+    /// Start rounds with a ship at `position` with orientation `angle` in radians
+    pub fn with_spawn_point(mut self, position: Vec2Data, angle: impl Into<Real>) -> MatchConfig {
+        self.spawn_points.push((position.convert(), angle.into()));
+        self
+    }
+
+    /// Number of round wins required to win the match
+    pub fn get_rounds_to_win(&self) -> u32 {
+        self.rounds_to_win
+    }
+
+    /// Duration of the countdown before a round
+    pub fn get_countdown(&self) -> Real {
+        self.countdown
+    }
+
+    /// Duration of the pause after a round
+    pub fn get_round_over_duration(&self) -> Real {
+        self.round_over_duration
+    }
+
+    /// Minimum number of player required to start the match
+    pub fn get_min_players(&self) -> usize {
+        self.min_players
+    }
+
+    /// `(position, orientation)` of all spawn points
+    pub fn get_spawn_points(&self) -> Vec<(Vec2Data, Real)> {
+        self.spawn_points
+            .iter()
+            .map(|(pos, angle)| (pos.convert(), *angle))
+            .collect()
+    }
+}
+
+/// Phase of a match
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MatchPhase {
+    /// Waiting for player to join
+    #[default]
+    Lobby,
+    /// Round starts after the remaining time
+    Countdown { remaining: Real },
+    /// Round in progress
+    Playing,
+    /// Round is over, next countdown starts after the remaining time. `winner` is `None` for a
+    /// draw.
+    RoundOver {
+        remaining: Real,
+        winner: Option<PlayerId>,
+    },
+    /// Match is decided
+    MatchOver { winner: PlayerId },
+}
+
+/// Score of a single player
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScoreData {
+    pub rounds_won: u32,
+    pub kills: u32,
+    pub deaths: u32,
+}
+
+/// Match use-case
+///
+/// Keeps track of the match phase. The phase and the scoreboard are stored in the data
+/// repository, such that a restored game continues the match where it was saved.
+pub struct Match {
+    config: MatchConfig,
+    repo: MatchDG,
+}
+
+impl Match {
+    /// Create use case object for the match in the data repository
+    pub fn new(config: MatchConfig, repo: MatchDG) -> Self {
+        Self { config, repo }
+    }
+
+    /// Current phase of the match
+    pub fn get_phase(&self) -> Result<MatchPhase> {
+        self.repo.get_phase()
+    }
+
+    /// Leave the lobby and start the countdown of the first round
+    pub fn start(&self) -> Result<()> {
+        if self.repo.get_phase()? != MatchPhase::Lobby {
+            return Err(Error::InvalidState("match already started"));
+        }
+        if self.repo.get_player_ids()?.len() < self.config.min_players {
+            return Err(Error::InvalidState("not enough player to start match"));
+        }
+        self.repo.reset_score()?;
+        self.reset_round()?;
+        self.repo.set_phase(MatchPhase::Countdown {
+            remaining: self.config.countdown,
+        })
+    }
+
+    /// Advance the match by `delta_time` and account for the ships destroyed in this time step
+    pub fn update(&self, delta_time: impl Into<Real>, destroyed: &[PlayerDestroyed]) -> Result<()> {
+        let dt = delta_time.into();
+        let phase = match self.repo.get_phase()? {
+            MatchPhase::Countdown { remaining } if remaining > dt => MatchPhase::Countdown {
+                remaining: remaining - dt,
+            },
+            MatchPhase::Countdown { .. } => MatchPhase::Playing,
+            MatchPhase::Playing => {
+                self.record_destroyed(destroyed)?;
+                self.check_round_over()?
+            }
+            MatchPhase::RoundOver { remaining, winner } if remaining > dt => {
+                MatchPhase::RoundOver {
+                    remaining: remaining - dt,
+                    winner,
+                }
+            }
+            MatchPhase::RoundOver { .. } => {
+                self.reset_round()?;
+                MatchPhase::Countdown {
+                    remaining: self.config.countdown,
+                }
+            }
+            phase @ (MatchPhase::Lobby | MatchPhase::MatchOver { .. }) => phase,
+        };
+        self.repo.set_phase(phase)
+    }
+
+    /// Score of all player
+    pub fn get_scoreboard(&self) -> Result<Vec<(PlayerId, ScoreData)>> {
+        Ok(self
+            .repo
+            .get_score()?
+            .into_iter()
+            .map(|(id, score)| (id.convert(), score))
+            .collect())
+    }
+
+    /// Restore all ships and place them on the spawn points
+    ///
+    /// Ships are assigned to the spawn points in the order of their ids. If there are more ships
+    /// than spawn points, the spawn points are used repeatedly.
+    fn reset_round(&self) -> Result<()> {
+        let mut ids: Vec<PlayerId> = self.repo.get_player_ids()?.convert();
+        ids.sort_unstable();
+        let spawn_point = |index: usize| match self.config.spawn_points.as_slice() {
+            [] => (Vec2::zero(), 0.0),
+            spawn_points => spawn_points[index % spawn_points.len()],
+        };
+        self.repo.reset_round(
+            ids.into_iter()
+                .enumerate()
+                .map(|(index, id)| {
+                    let (pos, angle) = spawn_point(index);
+                    (id.convert(), pos.convert(), angle.convert())
+                })
+                .collect(),
+        )
+    }
+
+    /// Count kills and deaths
+    fn record_destroyed(&self, destroyed: &[PlayerDestroyed]) -> Result<()> {
+        if destroyed.is_empty() {
+            return Ok(());
+        }
+        let mut score: Vec<(PlayerId, ScoreData)> = self.get_scoreboard()?;
+        for PlayerDestroyed { player, by } in destroyed {
+            score
+                .iter_mut()
+                .filter(|(id, _)| id == player)
+                .for_each(|(_, score)| score.deaths += 1);
+            score
+                .iter_mut()
+                .filter(|(id, _)| Some(*id) == *by && id != player)
+                .for_each(|(_, score)| score.kills += 1);
+        }
+        self.repo.set_score(
+            score
+                .into_iter()
+                .map(|(id, score)| (id.convert(), score))
+                .collect(),
+        )
+    }
+
+    /// End the round if at most one ship survived
+    fn check_round_over(&self) -> Result<MatchPhase> {
+        let survivors = self.repo.get_surviving_player_ids()?;
+        if survivors.len() > 1 {
+            return Ok(MatchPhase::Playing);
+        }
+        let winner = match survivors.first() {
+            Some(id) => id.convert(),
+            None => {
+                return Ok(MatchPhase::RoundOver {
+                    remaining: self.config.round_over_duration,
+                    winner: None,
+                })
+            }
+        };
+        let mut rounds_won = 0;
+        let score = self
+            .get_scoreboard()?
+            .into_iter()
+            .map(|(id, mut score)| {
+                if id == winner {
+                    score.rounds_won += 1;
+                    rounds_won = score.rounds_won;
+                }
+                (id.convert(), score)
+            })
+            .collect();
+        self.repo.set_score(score)?;
+        if rounds_won >= self.config.rounds_to_win {
+            Ok(MatchPhase::MatchOver { winner })
+        } else {
+            Ok(MatchPhase::RoundOver {
+                remaining: self.config.round_over_duration,
+                winner: Some(winner),
+            })
+        }
+    }
+}
+
+/// Data repository interface for match use case.
+pub trait MatchDataGateway {
+    /// Return ids of all player
+    fn get_player_ids(&self) -> Result<Vec<PlayerIdData>>;
+
+    /// Return ids of all player whose ships are not destroyed
+    fn get_surviving_player_ids(&self) -> Result<Vec<PlayerIdData>>;
+
+    /// Return score of all player
+    fn get_score(&self) -> Result<Vec<(PlayerIdData, ScoreData)>>;
+
+    /// Update score of player
+    fn set_score(&self, updates: Vec<(PlayerIdData, ScoreData)>) -> Result<()>;
+
+    /// Reset score of all player to zero
+    fn reset_score(&self) -> Result<()>;
+
+    /// Restore all ships for a new round
+    ///
+    /// Every ship is repaired and placed at rest at `(id, position, angle)`. All missiles are
+    /// removed.
+    fn reset_round(&self, spawns: Vec<(PlayerIdData, Vec2Data, Real)>) -> Result<()>;
+
+    /// Return the phase of the match, [`MatchPhase::Lobby`] for a new game
+    fn get_phase(&self) -> Result<MatchPhase>;
+
+    /// Update the phase of the match
+    fn set_phase(&self, phase: MatchPhase) -> Result<()>;
+}
+
+type MatchDG = Rc<dyn MatchDataGateway>;
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        damage::PlayerDestroyed,
+        entities::Real,
+        repo_interfaces::{PlayerIdData, Vec2Data},
+        Error, Result,
+    };
+
+    use super::{Match, MatchConfig, MatchDataGateway, MatchPhase, ScoreData};
+
+    #[derive(Default)]
+    struct MockDataGateway {
+        player: Vec<(PlayerIdData, ScoreData, bool)>,
+        rounds_started: usize,
+        spawns: Vec<(PlayerIdData, Vec2Data, Real)>,
+        phase: MatchPhase,
+    }
+
+    impl MatchDataGateway for RefCell<MockDataGateway> {
+        fn get_player_ids(&self) -> Result<Vec<PlayerIdData>> {
+            Ok(self.borrow().player.iter().map(|(id, _, _)| *id).collect())
+        }
+        fn get_surviving_player_ids(&self) -> Result<Vec<PlayerIdData>> {
+            Ok(self
+                .borrow()
+                .player
+                .iter()
+                .filter(|(_, _, destroyed)| !destroyed)
+                .map(|(id, _, _)| *id)
+                .collect())
+        }
+        fn get_score(&self) -> Result<Vec<(PlayerIdData, ScoreData)>> {
+            Ok(self
+                .borrow()
+                .player
+                .iter()
+                .map(|(id, score, _)| (*id, *score))
+                .collect())
+        }
+        fn set_score(&self, updates: Vec<(PlayerIdData, ScoreData)>) -> Result<()> {
+            let mut repo = self.borrow_mut();
+            for (id, score) in updates {
+                repo.player
+                    .iter_mut()
+                    .find(|(p_id, _, _)| *p_id == id)
+                    .ok_or(Error::UnknownPlayer(id))?
+                    .1 = score;
+            }
+            Ok(())
+        }
+        fn reset_score(&self) -> Result<()> {
+            self.borrow_mut()
+                .player
+                .iter_mut()
+                .for_each(|(_, score, _)| *score = ScoreData::default());
+            Ok(())
+        }
+        fn reset_round(&self, spawns: Vec<(PlayerIdData, Vec2Data, Real)>) -> Result<()> {
+            let mut repo = self.borrow_mut();
+            repo.rounds_started += 1;
+            repo.spawns = spawns;
+            repo.player
+                .iter_mut()
+                .for_each(|(_, _, destroyed)| *destroyed = false);
+            Ok(())
+        }
+        fn get_phase(&self) -> Result<MatchPhase> {
+            Ok(self.borrow().phase)
+        }
+        fn set_phase(&self, phase: MatchPhase) -> Result<()> {
+            self.borrow_mut().phase = phase;
+            Ok(())
+        }
+    }
+
+    fn setup_match_test(player: &[PlayerIdData]) -> (Match, Rc<RefCell<MockDataGateway>>) {
+        let repo = Rc::new(RefCell::new(MockDataGateway {
+            player: player
+                .iter()
+                .map(|id| (*id, ScoreData::default(), false))
+                .collect(),
+            ..Default::default()
+        }));
+        let config = MatchConfig::new(2).with_durations(3.0, 1.0);
+        (Match::new(config, repo.clone()), repo)
+    }
+
+    /// Destroy the ship of `player` by a missile of `by` and update the match
+    fn destroy(game: &Match, repo: &RefCell<MockDataGateway>, player: usize, by: usize) {
+        repo.borrow_mut()
+            .player
+            .iter_mut()
+            .filter(|(id, _, _)| *id == player)
+            .for_each(|(_, _, destroyed)| *destroyed = true);
+        game.update(
+            0.1,
+            &[PlayerDestroyed {
+                player,
+                by: Some(by),
+            }],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn match_requires_enough_player() {
+        let (game, _) = setup_match_test(&[1]);
+        assert!(matches!(game.start(), Err(Error::InvalidState(_))));
+        assert_eq!(game.get_phase(), Ok(MatchPhase::Lobby));
+    }
+
+    #[test]
+    fn countdown_leads_to_playing() {
+        let (game, _) = setup_match_test(&[1, 2]);
+        game.start().unwrap();
+        game.update(2.0, &[]).unwrap();
+        assert_eq!(
+            game.get_phase(),
+            Ok(MatchPhase::Countdown { remaining: 1.0 })
+        );
+        game.update(1.0, &[]).unwrap();
+        assert_eq!(game.get_phase(), Ok(MatchPhase::Playing));
+        assert!(matches!(game.start(), Err(Error::InvalidState(_))));
+    }
+
+    #[test]
+    fn last_survivor_wins_round() {
+        let (game, repo) = setup_match_test(&[1, 2, 3]);
+        game.start().unwrap();
+        game.update(3.0, &[]).unwrap();
+
+        destroy(&game, &repo, 2, 1);
+        assert_eq!(game.get_phase(), Ok(MatchPhase::Playing));
+        destroy(&game, &repo, 3, 1);
+        assert_eq!(
+            game.get_phase(),
+            Ok(MatchPhase::RoundOver {
+                remaining: 1.0,
+                winner: Some(1)
+            })
+        );
+        assert_eq!(
+            game.get_scoreboard(),
+            Ok(vec![
+                (
+                    1,
+                    ScoreData {
+                        rounds_won: 1,
+                        kills: 2,
+                        deaths: 0
+                    }
+                ),
+                (
+                    2,
+                    ScoreData {
+                        rounds_won: 0,
+                        kills: 0,
+                        deaths: 1
+                    }
+                ),
+                (
+                    3,
+                    ScoreData {
+                        rounds_won: 0,
+                        kills: 0,
+                        deaths: 1
+                    }
+                ),
+            ])
+        );
+
+        game.update(1.0, &[]).unwrap();
+        assert_eq!(
+            game.get_phase(),
+            Ok(MatchPhase::Countdown { remaining: 3.0 })
+        );
+        assert_eq!(repo.borrow().rounds_started, 2);
+    }
+
+    #[test]
+    fn rounds_start_on_spawn_points() {
+        let (_, repo) = setup_match_test(&[3, 1, 2]);
+        let config = MatchConfig::new(2)
+            .with_spawn_point([-10.0, 0.0], 0.0)
+            .with_spawn_point([10.0, 0.0], 3.0);
+        let game = Match::new(config, repo.clone());
+        game.start().unwrap();
+        assert_eq!(
+            repo.borrow().spawns,
+            vec![
+                (1, [-10.0, 0.0], 0.0),
+                (2, [10.0, 0.0], 3.0),
+                (3, [-10.0, 0.0], 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn match_over_after_rounds_to_win() {
+        let (game, repo) = setup_match_test(&[1, 2]);
+        game.start().unwrap();
+        for _ in 0..2 {
+            game.update(3.0, &[]).unwrap();
+            destroy(&game, &repo, 1, 2);
+            game.update(1.0, &[]).unwrap();
+        }
+        assert_eq!(game.get_phase(), Ok(MatchPhase::MatchOver { winner: 2 }));
+        game.update(10.0, &[]).unwrap();
+        assert_eq!(game.get_phase(), Ok(MatchPhase::MatchOver { winner: 2 }));
+    }
+
+    #[test]
+    fn match_continues_in_stored_phase() {
+        let (_, repo) = setup_match_test(&[1, 2]);
+        repo.borrow_mut().phase = MatchPhase::RoundOver {
+            remaining: 0.5,
+            winner: Some(1),
+        };
+        let game = Match::new(MatchConfig::new(2).with_durations(3.0, 1.0), repo.clone());
+        game.update(0.5, &[]).unwrap();
+        assert_eq!(
+            game.get_phase(),
+            Ok(MatchPhase::Countdown { remaining: 3.0 })
+        );
+        assert_eq!(repo.borrow().rounds_started, 1);
+    }
+
+    #[test]
+    fn round_without_survivor_is_draw() {
+        let (game, repo) = setup_match_test(&[1, 2]);
+        game.start().unwrap();
+        game.update(3.0, &[]).unwrap();
+        repo.borrow_mut()
+            .player
+            .iter_mut()
+            .for_each(|(_, _, destroyed)| *destroyed = true);
+        game.update(
+            0.1,
+            &[
+                PlayerDestroyed {
+                    player: 1,
+                    by: None,
+                },
+                PlayerDestroyed {
+                    player: 2,
+                    by: Some(2),
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            game.get_phase(),
+            Ok(MatchPhase::RoundOver {
+                remaining: 1.0,
+                winner: None
+            })
+        );
+        let kills: u32 = game
+            .get_scoreboard()
+            .unwrap()
+            .iter()
+            .map(|(_, score)| score.kills)
+            .sum();
+        assert_eq!(kills, 0);
+    }
+}
@@ -1,25 +1,66 @@
 //! Domain rules for Interception Orbit game
 
+pub mod damage;
+pub mod diagnostics;
 mod entities;
+mod error;
+pub mod game_match;
+pub mod lobby;
+pub mod orbit;
 pub mod physics;
 pub mod repo;
+pub mod respawn;
+pub mod simulation;
 pub mod user_input;
 
+pub use error::{Error, Result};
+
 /// Reexport data gateway interfaces and data types
 ///
 /// Also provides Marshalling implementations for common data types.
 pub mod repo_interfaces {
     pub use super::user_input::{
-        MissileLaunchData, PlayerMovementDataGateway, PlayerPosAndVelocityData, ShootDataGateway,
+        FixedMissileLaunchData, FixedPlayerPosAndVelocityData, MissileLaunchData,
+        PlayerMovementDataGateway, PlayerPosAndVelocityData, ShootDataGateway,
+    };
+
+    pub use super::damage::DamageDataGateway;
+
+    pub use super::diagnostics::DiagnosticsDataGateway;
+
+    pub use super::game_match::{MatchDataGateway, MatchPhase, ScoreData};
+
+    pub use super::lobby::PlayerManagementDataGateway;
+
+    pub use super::respawn::RespawnDataGateway;
+
+    pub use super::physics::{
+        BoundaryDataGateway, CollisionDataGateway, ExpiryDataGateway, GravityDataGateway,
+        IntegrateDataGateway, StarData, StarMotion, StarMotionDataGateway,
     };
 
-    pub use super::physics::{GravityDataGateway, IntegrateDataGateway, StarData};
+    pub use super::simulation::SimulationDataGateway;
 
-    use crate::entities::Vec2;
+    use crate::entities::{
+        fixed::{Fixed, FixedVec2},
+        Float, Real, Vec2,
+    };
 
     /// Supertrait for all in-game state
     pub trait InGameState:
-        PlayerMovementDataGateway + ShootDataGateway + GravityDataGateway + IntegrateDataGateway
+        PlayerManagementDataGateway
+        + DamageDataGateway
+        + MatchDataGateway
+        + RespawnDataGateway
+        + PlayerMovementDataGateway
+        + ShootDataGateway
+        + GravityDataGateway
+        + IntegrateDataGateway
+        + CollisionDataGateway
+        + ExpiryDataGateway
+        + StarMotionDataGateway
+        + BoundaryDataGateway
+        + SimulationDataGateway
     {
     }
 
@@ -29,18 +70,67 @@ pub mod repo_interfaces {
     }
 
     /// Intermediate format for exchange with storage backend
-    pub type Vec2Data = [f32; 2];
-    impl Marshalling<Vec2> for Vec2Data {
-        fn convert(&self) -> Vec2 {
+    pub type Vec2Data = [Real; 2];
+    impl<T: Float> Marshalling<Vec2<T>> for [T; 2] {
+        fn convert(&self) -> Vec2<T> {
             Vec2::new(self[0], self[1])
         }
     }
-    impl Marshalling<Vec2Data> for Vec2 {
-        fn convert(&self) -> Vec2Data {
+    impl<T: Float> Marshalling<[T; 2]> for Vec2<T> {
+        fn convert(&self) -> [T; 2] {
             [self.get_x(), self.get_y()]
         }
     }
 
+    /// Exchange format for deterministic fixed-point numbers, the raw bits of a number with 32
+    /// fractional bits
+    pub type FixedData = i64;
+    impl Marshalling<Fixed> for FixedData {
+        fn convert(&self) -> Fixed {
+            Fixed::from_bits(*self)
+        }
+    }
+    impl Marshalling<FixedData> for Fixed {
+        fn convert(&self) -> FixedData {
+            self.to_bits()
+        }
+    }
+
+    /// Exchange format for deterministic fixed-point vectors
+    pub type FixedVec2Data = [FixedData; 2];
+    impl Marshalling<FixedVec2> for FixedVec2Data {
+        fn convert(&self) -> FixedVec2 {
+            FixedVec2::new(Fixed::from_bits(self[0]), Fixed::from_bits(self[1]))
+        }
+    }
+    impl Marshalling<FixedVec2Data> for FixedVec2 {
+        fn convert(&self) -> FixedVec2Data {
+            [self.get_x().to_bits(), self.get_y().to_bits()]
+        }
+    }
+
+    /// Round floating point data to the nearest fixed-point number
+    impl Marshalling<FixedData> for Real {
+        fn convert(&self) -> FixedData {
+            Fixed::from(*self).to_bits()
+        }
+    }
+    impl Marshalling<Real> for FixedData {
+        fn convert(&self) -> Real {
+            Fixed::from_bits(*self).to_float()
+        }
+    }
+    impl Marshalling<FixedVec2Data> for Vec2Data {
+        fn convert(&self) -> FixedVec2Data {
+            [self[0].convert(), self[1].convert()]
+        }
+    }
+    impl Marshalling<Vec2Data> for FixedVec2Data {
+        fn convert(&self) -> Vec2Data {
+            [self[0].convert(), self[1].convert()]
+        }
+    }
+
     /// Player Id
     pub type PlayerId = usize;
     /// Exchange format for Player Id
@@ -54,6 +144,12 @@ pub mod repo_interfaces {
     pub type MissileId = usize;
     pub type MissileIdData = MissileId;
 
+    /// Star Id, i.e. the index of the star in the list of all stars
+    pub type StarId = usize;
+
+    /// Hazard Id, i.e. the index of the hazard in the list of all hazards
+    pub type HazardId = usize;
+
     /// Generic implementations for tuples (this could be done much more elegantly with a macro)
     impl<I1, O1> Marshalling<(O1,)> for (I1,)
     where
@@ -129,8 +225,8 @@ pub mod repo_interfaces {
     }
 
     // Blanket implementation for Into implementers
-    impl Marshalling<f32> for f32 {
-        fn convert(&self) -> f32 {
+    impl<T: Float> Marshalling<T> for T {
+        fn convert(&self) -> T {
             *self
         }
     }
@@ -0,0 +1,228 @@
+//! Use-cases related to the game lobby
+//!
+//! Use-cases are:
+//! -  Player joins the game
+//! -  Player leaves the game
+
+use std::rc::Rc;
+
+use crate::{
+    entities::{Real, Vec2},
+    repo_interfaces::{Marshalling, PlayerId, PlayerIdData, Vec2Data},
+    Error, Result,
+};
+
+/// Configuration object for the lobby
+#[derive(Clone, Debug)]
+pub struct LobbyConfig {
+    /// Maximum number of player in a game
+    max_players: usize,
+    /// Possible `(position, orientation)` of newly joined player
+    spawn_points: Vec<(Vec2, Real)>,
+}
+
+impl LobbyConfig {
+    /// Create a new lobby configuration without spawn points
+    ///
+    /// Without spawn points, player join at the origin.
+    pub fn new(max_players: usize) -> Self {
+        LobbyConfig {
+            max_players,
+            spawn_points: Vec::new(),
+        }
+    }
+
+    /// Add a spawn point at `position` with the initial player orientation `angle` in radians
+    pub fn with_spawn_point(mut self, position: Vec2Data, angle: impl Into<Real>) -> Self {
+        self.spawn_points.push((position.convert(), angle.into()));
+        self
+    }
+
+    /// Maximum number of player in a game
+    pub fn get_max_players(&self) -> usize {
+        self.max_players
+    }
+
+    /// `(position, orientation)` of all spawn points
+    pub fn get_spawn_points(&self) -> Vec<(Vec2Data, Real)> {
+        self.spawn_points
+            .iter()
+            .map(|(pos, angle)| (pos.convert(), *angle))
+            .collect()
+    }
+}
+
+/// Player management use-case
+pub struct PlayerManagement {
+    config: LobbyConfig,
+    repo: Rc<dyn PlayerManagementDataGateway>,
+}
+
+impl PlayerManagement {
+    /// Create a new player management use-case
+    pub fn new(config: LobbyConfig, repo: Rc<dyn PlayerManagementDataGateway>) -> Self {
+        Self { config, repo }
+    }
+
+    /// Add a new player to the game
+    ///
+    /// The player is placed at the spawn point farthest away from all other player.
+    pub fn join(&self, id: PlayerId) -> Result<()> {
+        let player: Vec<(PlayerId, Vec2)> = self.repo.get_players()?.convert();
+        if player.iter().any(|(p_id, _)| *p_id == id) {
+            return Err(Error::PlayerAlreadyExists(id.convert()));
+        }
+        if player.len() >= self.config.max_players {
+            return Err(Error::CapacityExceeded("player"));
+        }
+        let (pos, angle) = self.select_spawn_point(&player);
+        self.repo
+            .add_player(&id.convert(), pos.convert(), angle.convert())
+    }
+
+    /// Remove a player and all its missiles from the game
+    pub fn leave(&self, id: PlayerId) -> Result<()> {
+        self.repo.remove_player(&id.convert())
+    }
+
+    /// Ids of all player in the game
+    pub fn get_player_ids(&self) -> Result<Vec<PlayerId>> {
+        Ok(self
+            .repo
+            .get_players()?
+            .into_iter()
+            .map(|(id, _)| id.convert())
+            .collect())
+    }
+
+    /// Spawn point with the largest distance to the closest player
+    fn select_spawn_point(&self, player: &[(PlayerId, Vec2)]) -> (Vec2, Real) {
+        let distance_to_closest_player = |pos: Vec2| {
+            player
+                .iter()
+                .map(|&(_, p_pos)| (p_pos - pos).len2())
+                .fold(Real::INFINITY, Real::min)
+        };
+        self.config
+            .spawn_points
+            .iter()
+            .copied()
+            .fold(None, |best: Option<(Vec2, Real, Real)>, (pos, angle)| {
+                let distance = distance_to_closest_player(pos);
+                match best {
+                    Some((_, _, best_distance)) if best_distance >= distance => best,
+                    _ => Some((pos, angle, distance)),
+                }
+            })
+            .map_or((Vec2::zero(), 0.0), |(pos, angle, _)| (pos, angle))
+    }
+}
+
+/// Interface of data gateway for player management use-case
+pub trait PlayerManagementDataGateway {
+    /// Return `(id, position)` of all player
+    fn get_players(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>>;
+
+    /// Add a player at `position` with orientation `angle`
+    ///
+    /// Must fail with [`Error::PlayerAlreadyExists`] if the id is already in use.
+    fn add_player(&self, id: &PlayerIdData, position: Vec2Data, angle: Real) -> Result<()>;
+
+    /// Remove a player together with all of its missiles
+    fn remove_player(&self, id: &PlayerIdData) -> Result<()>;
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        entities::Real,
+        repo_interfaces::{PlayerIdData, Vec2Data},
+        Error, Result,
+    };
+
+    use super::{LobbyConfig, PlayerManagement, PlayerManagementDataGateway};
+
+    #[derive(Default)]
+    struct MockDataGateway {
+        player: Vec<(PlayerIdData, Vec2Data, Real)>,
+    }
+
+    impl PlayerManagementDataGateway for RefCell<MockDataGateway> {
+        fn get_players(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+            Ok(self
+                .borrow()
+                .player
+                .iter()
+                .map(|&(id, pos, _)| (id, pos))
+                .collect())
+        }
+        fn add_player(&self, id: &PlayerIdData, position: Vec2Data, angle: Real) -> Result<()> {
+            self.borrow_mut().player.push((*id, position, angle));
+            Ok(())
+        }
+        fn remove_player(&self, id: &PlayerIdData) -> Result<()> {
+            let mut repo = self.borrow_mut();
+            let index = repo
+                .player
+                .iter()
+                .position(|(p_id, _, _)| p_id == id)
+                .ok_or(Error::UnknownPlayer(*id))?;
+            repo.player.remove(index);
+            Ok(())
+        }
+    }
+
+    fn setup_lobby_test(max_players: usize) -> (PlayerManagement, Rc<RefCell<MockDataGateway>>) {
+        let config = LobbyConfig::new(max_players)
+            .with_spawn_point([-10.0, 0.0], 0.0)
+            .with_spawn_point([10.0, 0.0], 3.0)
+            .with_spawn_point([0.0, 1.0], 1.0);
+        let repo = Rc::new(RefCell::new(MockDataGateway::default()));
+        (PlayerManagement::new(config, repo.clone()), repo)
+    }
+
+    #[test]
+    fn player_joins_at_farthest_spawn_point() {
+        let (lobby, repo) = setup_lobby_test(3);
+        lobby.join(4).unwrap();
+        lobby.join(7).unwrap();
+        lobby.join(1).unwrap();
+        assert_eq!(
+            repo.borrow().player,
+            vec![
+                (4, [-10.0, 0.0], 0.0),
+                (7, [10.0, 0.0], 3.0),
+                (1, [0.0, 1.0], 1.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn player_count_limited_by_config() {
+        let (lobby, _) = setup_lobby_test(2);
+        lobby.join(1).unwrap();
+        lobby.join(2).unwrap();
+        assert_eq!(lobby.join(3), Err(Error::CapacityExceeded("player")));
+    }
+
+    #[test]
+    fn player_id_can_not_join_twice() {
+        let (lobby, _) = setup_lobby_test(2);
+        lobby.join(1).unwrap();
+        assert_eq!(lobby.join(1), Err(Error::PlayerAlreadyExists(1)));
+    }
+
+    #[test]
+    fn player_can_rejoin_after_leaving() {
+        let (lobby, _) = setup_lobby_test(2);
+        lobby.join(1).unwrap();
+        lobby.join(2).unwrap();
+        lobby.leave(1).unwrap();
+        assert_eq!(lobby.get_player_ids(), Ok(vec![2]));
+        lobby.join(1).unwrap();
+        assert_eq!(lobby.get_player_ids(), Ok(vec![2, 1]));
+        assert_eq!(lobby.leave(5), Err(Error::UnknownPlayer(5)));
+    }
+}
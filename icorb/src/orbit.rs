@@ -0,0 +1,272 @@
+//! Orbital mechanics for display purposes
+//!
+//! The orbit of a ship is approximated by the Kepler orbit around the dominant star, i.e. the
+//! star exerting the strongest gravitational pull. All other stars are ignored.
+
+use crate::{
+    entities::{Real, Vec2, TWO_PI},
+    physics::{PhysicsConfig, Star, StarData},
+    repo_interfaces::{Marshalling, StarId, Vec2Data},
+};
+
+/// Deviation of the eccentricity from 1 up to which an orbit counts as parabolic
+const PARABOLIC_TOLERANCE: Real = 1e-4;
+
+/// Shape of a Kepler orbit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrbitKind {
+    /// Closed orbit, eccentricity below 1
+    Elliptic,
+    /// Escape trajectory with exactly the escape velocity, eccentricity of 1
+    Parabolic,
+    /// Escape trajectory exceeding the escape velocity, eccentricity above 1
+    Hyperbolic,
+}
+
+/// Orbital elements of an object around a star
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitalElements {
+    star: StarId,
+    kind: OrbitKind,
+    eccentricity: Real,
+    semi_major_axis: Real,
+    periapsis: Real,
+    argument_of_periapsis: Real,
+}
+
+impl OrbitalElements {
+    /// Compute the orbit of an object around the dominant star
+    ///
+    /// Returns `None` if there is no star with positive mass or the object sits in the center of
+    /// the dominant star.
+    pub fn new(
+        config: &PhysicsConfig,
+        position: Vec2Data,
+        velocity: Vec2Data,
+        stars: &[StarData],
+    ) -> Option<Self> {
+        let position: Vec2 = position.convert();
+        let velocity: Vec2 = velocity.convert();
+        let (star, r) = dominant_star(config, position, stars)?;
+        let mu = config.get_gravity_constant() * stars[star].get_mass();
+
+        let dist = r.len();
+        let speed2 = velocity.len2();
+        let angular_momentum = r.get_x() * velocity.get_y() - r.get_y() * velocity.get_x();
+        let eccentricity_vector = ((speed2 - mu / dist) * r - (r * velocity) * velocity) / mu;
+        let eccentricity = eccentricity_vector.len();
+        let semi_latus_rectum = angular_momentum * angular_momentum / mu;
+        let energy = 0.5 * speed2 - mu / dist;
+
+        let kind = if (eccentricity - 1.0).abs() <= PARABOLIC_TOLERANCE {
+            OrbitKind::Parabolic
+        } else if eccentricity < 1.0 {
+            OrbitKind::Elliptic
+        } else {
+            OrbitKind::Hyperbolic
+        };
+        let semi_major_axis = match kind {
+            OrbitKind::Parabolic => Real::INFINITY,
+            _ => -0.5 * mu / energy,
+        };
+        Some(OrbitalElements {
+            star,
+            kind,
+            eccentricity,
+            semi_major_axis,
+            periapsis: semi_latus_rectum / (1.0 + eccentricity),
+            argument_of_periapsis: if eccentricity > 0.0 {
+                eccentricity_vector.angle()
+            } else {
+                0.0
+            },
+        })
+    }
+
+    /// Id of the star the object orbits
+    pub fn get_star(&self) -> StarId {
+        self.star
+    }
+
+    /// Shape of the orbit
+    pub fn get_kind(&self) -> OrbitKind {
+        self.kind
+    }
+
+    /// Whether the object stays in orbit, as opposed to being on an escape trajectory
+    pub fn is_bound(&self) -> bool {
+        self.kind == OrbitKind::Elliptic
+    }
+
+    /// Eccentricity of the orbit
+    pub fn get_eccentricity(&self) -> Real {
+        self.eccentricity
+    }
+
+    /// Semi-major axis of the orbit
+    ///
+    /// The semi-major axis is infinite on parabolic and negative on hyperbolic trajectories.
+    pub fn get_semi_major_axis(&self) -> Real {
+        self.semi_major_axis
+    }
+
+    /// Smallest distance to the center of the star
+    pub fn get_periapsis(&self) -> Real {
+        self.periapsis
+    }
+
+    /// Largest distance to the center of the star, `None` on escape trajectories
+    pub fn get_apoapsis(&self) -> Option<Real> {
+        self.is_bound()
+            .then_some(self.semi_major_axis * (1.0 + self.eccentricity))
+    }
+
+    /// Direction from the star to the periapsis as angle in the range `[0, 2*PI)`
+    ///
+    /// The angle is 0 for circular orbits.
+    pub fn get_argument_of_periapsis(&self) -> Real {
+        self.argument_of_periapsis
+    }
+
+    /// Time for one revolution, `None` on escape trajectories
+    pub fn get_period(&self, config: &PhysicsConfig, stars: &[StarData]) -> Option<Real> {
+        let mu = config.get_gravity_constant() * stars.get(self.star)?.get_mass();
+        self.is_bound()
+            .then(|| TWO_PI * (self.semi_major_axis.powi(3) / mu).sqrt())
+    }
+}
+
+/// Star with the strongest pull on `position` and the vector from the star to `position`
+fn dominant_star(
+    config: &PhysicsConfig,
+    position: Vec2,
+    stars: &[StarData],
+) -> Option<(StarId, Vec2)> {
+    stars
+        .iter()
+        .map(|star| -> Star { star.convert() })
+        .enumerate()
+        .filter(|(_, star)| star.mass > 0.0)
+        .map(|(id, star)| {
+            let r = config.separation(star.pos, position);
+            (id, r, star.mass / r.len2())
+        })
+        .fold(
+            None,
+            |best: Option<(StarId, Vec2, Real)>, candidate| match best {
+                Some(best) if best.2 >= candidate.2 => Some(best),
+                _ => Some(candidate),
+            },
+        )
+        .filter(|&(_, r, _)| r.len2() > 0.0)
+        .map(|(id, r, _)| (id, r))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        entities::{Real, PI, TWO_PI},
+        physics::{Arena, BoundaryMode, PhysicsConfig, StarData},
+    };
+
+    use super::{OrbitKind, OrbitalElements};
+
+    fn assert_close(actual: Real, expected: Real) {
+        assert!(
+            (actual - expected).abs() <= 1e-3 * expected.abs().max(1.0),
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn circular_orbit() {
+        let config = PhysicsConfig::new().with_gravity_constant(2.0);
+        let stars = [StarData::new([10.0, 10.0], 50.0, 1.0)];
+        // Circular velocity sqrt(mu / r) = sqrt(100 / 25)
+        let orbit = OrbitalElements::new(&config, [10.0, 35.0], [2.0, 0.0], &stars).unwrap();
+        assert_eq!(orbit.get_kind(), OrbitKind::Elliptic);
+        assert!(orbit.is_bound());
+        assert_close(orbit.get_eccentricity(), 0.0);
+        assert_close(orbit.get_semi_major_axis(), 25.0);
+        assert_close(orbit.get_periapsis(), 25.0);
+        assert_close(orbit.get_apoapsis().unwrap(), 25.0);
+        assert_close(
+            orbit.get_period(&config, &stars).unwrap(),
+            TWO_PI * 25.0 / 2.0,
+        );
+    }
+
+    #[test]
+    fn elliptic_orbit() {
+        let config = PhysicsConfig::new();
+        let stars = [StarData::new([0.0, 0.0], 100.0, 1.0)];
+        // At apoapsis r = 20 with v^2 = mu * (2 / r - 1 / a) for a = 15
+        let speed = Real::sqrt(100.0 * (2.0 / 20.0 - 1.0 / 15.0));
+        let orbit = OrbitalElements::new(&config, [20.0, 0.0], [0.0, speed], &stars).unwrap();
+        assert_eq!(orbit.get_kind(), OrbitKind::Elliptic);
+        assert_close(orbit.get_semi_major_axis(), 15.0);
+        assert_close(orbit.get_eccentricity(), 1.0 / 3.0);
+        assert_close(orbit.get_periapsis(), 10.0);
+        assert_close(orbit.get_apoapsis().unwrap(), 20.0);
+        assert_close(orbit.get_argument_of_periapsis(), PI);
+    }
+
+    #[test]
+    fn escape_trajectories() {
+        let config = PhysicsConfig::new();
+        let stars = [StarData::new([0.0, 0.0], 100.0, 1.0)];
+        // Escape velocity sqrt(2 * mu / r) = sqrt(2)
+        let escape = Real::sqrt(2.0);
+        let orbit = OrbitalElements::new(&config, [0.0, 100.0], [escape, 0.0], &stars).unwrap();
+        assert_eq!(orbit.get_kind(), OrbitKind::Parabolic);
+        assert!(!orbit.is_bound());
+        assert_eq!(orbit.get_semi_major_axis(), Real::INFINITY);
+        assert_close(orbit.get_periapsis(), 100.0);
+        assert_eq!(orbit.get_apoapsis(), None);
+        assert_eq!(orbit.get_period(&config, &stars), None);
+
+        let orbit = OrbitalElements::new(&config, [0.0, 100.0], [2.0, 0.0], &stars).unwrap();
+        assert_eq!(orbit.get_kind(), OrbitKind::Hyperbolic);
+        assert!(orbit.get_eccentricity() > 1.0);
+        assert_close(orbit.get_semi_major_axis(), -50.0);
+        assert_close(orbit.get_periapsis(), 100.0);
+        assert_eq!(orbit.get_apoapsis(), None);
+    }
+
+    #[test]
+    fn orbit_around_dominant_star() {
+        let config = PhysicsConfig::new();
+        let stars = [
+            StarData::new([0.0, 0.0], 100.0, 1.0),
+            StarData::new([100.0, 0.0], 1000.0, 1.0),
+            StarData::new([20.0, 0.0], 0.0, 1.0),
+        ];
+        let orbit = OrbitalElements::new(&config, [20.0, 0.0], [0.0, 1.0], &stars).unwrap();
+        assert_eq!(orbit.get_star(), 0);
+        let orbit = OrbitalElements::new(&config, [60.0, 0.0], [0.0, 1.0], &stars).unwrap();
+        assert_eq!(orbit.get_star(), 1);
+    }
+
+    #[test]
+    fn orbit_uses_nearest_image_when_wrapping() {
+        let arena = Arena::new([0.0, 0.0], [100.0, 100.0], BoundaryMode::Wrap).unwrap();
+        let config = PhysicsConfig::new().with_arena(arena);
+        let stars = [StarData::new([95.0, 50.0], 100.0, 1.0)];
+        let orbit = OrbitalElements::new(&config, [5.0, 50.0], [0.0, 10.0], &stars).unwrap();
+        assert_close(orbit.get_periapsis(), 10.0);
+    }
+
+    #[test]
+    fn no_orbit_without_star() {
+        let config = PhysicsConfig::new();
+        assert_eq!(
+            OrbitalElements::new(&config, [0.0, 0.0], [1.0, 0.0], &[]),
+            None
+        );
+        let stars = [StarData::new([0.0, 0.0], 100.0, 1.0)];
+        assert_eq!(
+            OrbitalElements::new(&config, [0.0, 0.0], [1.0, 0.0], &stars),
+            None
+        );
+    }
+}
@@ -3,48 +3,408 @@
 //! Use-cases are:
 //! -  adding gravitational acceleration to all objects
 //! -  update velocity and position of all movable objects
+//! -  detect collisions between missiles, players and stars
+//! -  burn missile fuel and remove expired missiles
+//! -  move stars along orbits and scripted paths
+//! -  keep objects within the bounds of the arena
+//!
+//! Additionally, [`predict_trajectory`] computes the future path of an object without altering
+//! the game state.
 
 // Reexport public API
-pub use gravity::{Gravity, GravityDataGateway, StarData};
-pub use integrate::{Integrate, IntegrateDataGateway};
+pub use boundary::{Arena, Boundary, BoundaryDataGateway, BoundaryMode};
+pub use collision::{Collision, CollisionConfig, CollisionDataGateway, CollisionEvent};
+pub use config::PhysicsConfig;
+pub use expiry::{Expiry, ExpiryDataGateway};
+pub(crate) use gravity::{limited_star_field, Star};
+pub use gravity::{Gravity, GravityDataGateway, GravitySolver, StarData};
+pub use integrate::{Integrate, IntegrateDataGateway, Integrator};
+pub use prediction::{predict_trajectory, Trajectory};
+pub use star_motion::{StarMotion, StarMotionDataGateway, StarMovement};
+
+mod config {
+    use super::{Arena, GravitySolver, Integrator};
+    use crate::{
+        entities::{
+            fixed::{Fixed, FixedVec2},
+            Real, Vec2, GRAVITY_CONSTANT,
+        },
+        Error, Result,
+    };
+
+    /// Configuration object for the gravity and integrate use-cases
+    ///
+    /// By default only stars attract. Giving ships or missiles a mass lets them attract every
+    /// other object as well.
+    ///
+    /// Close to an attractant the acceleration grows without bounds. A softening length smooths
+    /// the potential within that distance (Plummer softening) and a maximum acceleration caps the
+    /// total gravitational acceleration of an object. Adaptive substeps resolve close passes by a
+    /// star without shortening the time step in open space.
+    ///
+    /// In fixed-point mode, gravity and integration are computed with deterministic fixed-point
+    /// arithmetic, such that all platforms compute bit-identical results. This mode always sums up
+    /// gravity directly and uses substeps of equal length.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct PhysicsConfig {
+        /// Gravitational constant
+        gravity_constant: Real,
+        /// Mass of player objects
+        player_mass: Real,
+        /// Mass of missile objects
+        missile_mass: Real,
+        /// Method to sum up the gravitational acceleration
+        solver: GravitySolver,
+        /// Plummer softening length
+        softening: Real,
+        /// Upper limit of the gravitational acceleration of an object
+        max_acceleration: Option<Real>,
+        /// Numerical scheme to integrate position and velocity
+        integrator: Integrator,
+        /// Upper limit of the speed of an object
+        max_speed: Option<Real>,
+        /// Number of integration steps per time step
+        substeps: u32,
+        /// Largest substep as fraction of the local dynamical time, fixed substeps if `None`
+        step_accuracy: Option<Real>,
+        /// Upper limit of adaptive substeps per time step
+        max_substeps: u32,
+        /// Bounds of the arena, unbounded if `None`
+        arena: Option<Arena>,
+        /// Whether to compute with deterministic fixed-point arithmetic
+        fixed_point: bool,
+    }
+
+    impl Default for PhysicsConfig {
+        fn default() -> Self {
+            PhysicsConfig {
+                gravity_constant: GRAVITY_CONSTANT,
+                player_mass: 0.0,
+                missile_mass: 0.0,
+                solver: GravitySolver::Direct,
+                softening: 0.0,
+                max_acceleration: None,
+                integrator: Integrator::SemiImplicitEuler,
+                max_speed: None,
+                substeps: 1,
+                step_accuracy: None,
+                max_substeps: 1,
+                arena: None,
+                fixed_point: false,
+            }
+        }
+    }
+
+    impl PhysicsConfig {
+        /// Create a new configuration in which only stars attract and objects are integrated
+        /// with the semi-implicit Euler method in a single step
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Scale all gravitational attraction by `constant`
+        pub fn with_gravity_constant(mut self, constant: impl Into<Real>) -> Self {
+            self.gravity_constant = constant.into();
+            self
+        }
+
+        /// Let player objects attract with mass `mass`
+        pub fn with_player_mass(mut self, mass: impl Into<Real>) -> Self {
+            self.player_mass = mass.into();
+            self
+        }
+
+        /// Let missile objects attract with mass `mass`
+        pub fn with_missile_mass(mut self, mass: impl Into<Real>) -> Self {
+            self.missile_mass = mass.into();
+            self
+        }
+
+        /// Use `solver` to sum up the gravitational acceleration
+        ///
+        /// Fails if the opening angle of [`GravitySolver::BarnesHut`] is negative or not finite.
+        pub fn with_solver(mut self, solver: GravitySolver) -> Result<Self> {
+            if let GravitySolver::BarnesHut { theta } = solver {
+                if !(theta >= 0.0 && theta.is_finite()) {
+                    return Err(Error::InvalidValue(format!(
+                        "opening angle {theta} is not a finite, non-negative number"
+                    )));
+                }
+            }
+            self.solver = solver;
+            Ok(self)
+        }
+
+        /// Smooth the gravitational potential within distance `length` of an attractant
+        pub fn with_softening(mut self, length: impl Into<Real>) -> Self {
+            self.softening = length.into();
+            self
+        }
+
+        /// Limit the gravitational acceleration of an object to `max`
+        pub fn with_max_acceleration(mut self, max: impl Into<Real>) -> Self {
+            self.max_acceleration = Some(max.into());
+            self
+        }
+
+        /// Integrate position and velocity with `integrator`
+        pub fn with_integrator(mut self, integrator: Integrator) -> Self {
+            self.integrator = integrator;
+            self
+        }
+
+        /// Limit the speed of an object to `max`
+        pub fn with_max_speed(mut self, max: impl Into<Real>) -> Self {
+            self.max_speed = Some(max.into());
+            self
+        }
+
+        /// Split every time step into `substeps` integration steps, at least one
+        pub fn with_substeps(mut self, substeps: u32) -> Self {
+            self.substeps = substeps.max(1);
+            self
+        }
+
+        /// Shorten substeps of each object to at most `accuracy` times its local dynamical time
+        /// `sqrt(d / a)`, with the distance `d` to the nearest star and the gravitational
+        /// acceleration `a`. Substeps are never longer than fixed substeps and a time step is
+        /// split into at most `max_substeps` substeps. Variable substeps spoil the energy
+        /// conservation of the semi-implicit Euler method, use a higher order integrator.
+        pub fn with_adaptive_substeps(
+            mut self,
+            accuracy: impl Into<Real>,
+            max_substeps: u32,
+        ) -> Self {
+            self.step_accuracy = Some(accuracy.into());
+            self.max_substeps = max_substeps;
+            self
+        }
+
+        /// Measure distances within `arena`. In wrap mode, objects attract the nearest periodic
+        /// image of each other.
+        ///
+        /// This is the single source of the arena, [`super::Boundary`] and
+        /// [`super::CollisionConfig::with_arena_of`] take it from here.
+        pub fn with_arena(mut self, arena: Arena) -> Self {
+            self.arena = Some(arena);
+            self
+        }
+
+        /// Compute gravity and integration with deterministic fixed-point arithmetic
+        ///
+        /// Settings are rounded to fixed-point numbers. Builds with single and double precision
+        /// only agree if all of them are exact binary fractions like `0.125` instead of `0.1`.
+        pub fn with_fixed_point(mut self) -> Self {
+            self.fixed_point = true;
+            self
+        }
+
+        /// Gravitational constant
+        pub fn get_gravity_constant(&self) -> Real {
+            self.gravity_constant
+        }
+
+        /// Mass of player objects
+        pub fn get_player_mass(&self) -> Real {
+            self.player_mass
+        }
+
+        /// Mass of missile objects
+        pub fn get_missile_mass(&self) -> Real {
+            self.missile_mass
+        }
+
+        /// Method to sum up the gravitational acceleration
+        pub fn get_solver(&self) -> GravitySolver {
+            self.solver
+        }
+
+        /// Plummer softening length
+        pub fn get_softening(&self) -> Real {
+            self.softening
+        }
+
+        /// Upper limit of the gravitational acceleration of an object, if any
+        pub fn get_max_acceleration(&self) -> Option<Real> {
+            self.max_acceleration
+        }
+
+        /// Numerical scheme to integrate position and velocity
+        pub fn get_integrator(&self) -> Integrator {
+            self.integrator
+        }
+
+        /// Upper limit of the speed of an object, if any
+        pub fn get_max_speed(&self) -> Option<Real> {
+            self.max_speed
+        }
+
+        /// Number of integration steps per time step
+        pub fn get_substeps(&self) -> u32 {
+            self.substeps
+        }
+
+        /// Largest adaptive substep as fraction of the local dynamical time, if any
+        pub fn get_step_accuracy(&self) -> Option<Real> {
+            self.step_accuracy
+        }
+
+        /// Upper limit of adaptive substeps per time step
+        pub fn get_max_substeps(&self) -> u32 {
+            self.max_substeps.max(self.substeps)
+        }
+
+        /// Bounds of the arena, if any
+        pub fn get_arena(&self) -> Option<Arena> {
+            self.arena
+        }
+
+        /// Whether player or missile objects attract each other
+        pub fn is_n_body(&self) -> bool {
+            self.player_mass > 0.0 || self.missile_mass > 0.0
+        }
+
+        /// Whether gravity and integration use deterministic fixed-point arithmetic
+        pub fn is_fixed_point(&self) -> bool {
+            self.fixed_point
+        }
+
+        /// Vector from `from` to the nearest periodic image of `to`
+        pub(crate) fn separation(&self, from: Vec2, to: Vec2) -> Vec2 {
+            match &self.arena {
+                Some(arena) => arena.separation(from, to),
+                None => to - from,
+            }
+        }
+
+        /// Periodic image of `pos` nearest to `reference`
+        pub(crate) fn nearest_image(&self, pos: Vec2, reference: Vec2) -> Vec2 {
+            match &self.arena {
+                Some(arena) => arena.nearest_image(pos, reference),
+                None => pos,
+            }
+        }
+
+        /// Scale a gravitational acceleration down to the maximum acceleration
+        pub(crate) fn limit_acceleration(&self, acc: Vec2) -> Vec2 {
+            match self.max_acceleration {
+                Some(max) => acc.clamp_length(max),
+                None => acc,
+            }
+        }
+
+        /// Scale a velocity down to the maximum speed
+        pub(crate) fn limit_speed(&self, vel: Vec2) -> Vec2 {
+            match self.max_speed {
+                Some(max) => vel.clamp_length(max),
+                None => vel,
+            }
+        }
+
+        /// Fixed-point variant of [`Self::separation`]
+        pub(crate) fn fixed_separation(&self, from: FixedVec2, to: FixedVec2) -> FixedVec2 {
+            match &self.arena {
+                Some(arena) => arena.fixed_separation(from, to),
+                None => to - from,
+            }
+        }
+
+        /// Fixed-point variant of [`Self::limit_acceleration`]
+        pub(crate) fn fixed_limit_acceleration(&self, acc: FixedVec2) -> FixedVec2 {
+            fixed_limit(acc, self.max_acceleration)
+        }
+
+        /// Fixed-point variant of [`Self::limit_speed`]
+        pub(crate) fn fixed_limit_speed(&self, vel: FixedVec2) -> FixedVec2 {
+            fixed_limit(vel, self.max_speed)
+        }
+    }
+
+    /// Scale a vector down to length `max`, if any
+    fn fixed_limit(vec: FixedVec2, max: Option<Real>) -> FixedVec2 {
+        match max.map(Fixed::from) {
+            Some(max) if vec.len() > max => vec.norm() * max,
+            _ => vec,
+        }
+    }
+}
 
 mod gravity {
+    use super::{barnes_hut::QuadTree, PhysicsConfig};
     use crate::{
-        entities::{gravity, Vec2},
+        entities::{
+            fixed::{self, Fixed, FixedVec2},
+            softened_gravity, Real, Vec2,
+        },
         repo_interfaces::{
-            Marshalling, MissileId, MissileIdData, PlayerId, PlayerIdData, Vec2Data,
+            FixedVec2Data, Marshalling, MissileId, MissileIdData, PlayerId, PlayerIdData, Vec2Data,
         },
+        Error, Result,
     };
-    use std::rc::Rc;
+    use std::{fmt::Debug, rc::Rc};
+
+    type FixedPlayerData = (PlayerIdData, FixedVec2Data, FixedVec2Data);
+    type FixedMissileData = (PlayerIdData, MissileIdData, FixedVec2Data, FixedVec2Data);
+
+    /// Method to sum up the gravitational acceleration of all attracting bodies
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub enum GravitySolver {
+        /// Exact sum over all attracting bodies
+        #[default]
+        Direct,
+        /// Barnes-Hut approximation with a quadtree. Groups of bodies whose extent is smaller
+        /// than `theta` times their distance are treated as single body. Larger opening angles
+        /// are faster but less accurate, typical values are between 0.3 and 1.
+        BarnesHut { theta: Real },
+    }
 
-    /// A star with a position and mass.
+    /// A star with a position, mass and radius.
     ///
     /// Stars excert gravitational attration to objects
     #[derive(Clone, Copy, Debug)]
-    struct Star {
-        pos: Vec2,
-        mass: f32,
+    pub(crate) struct Star {
+        pub(crate) pos: Vec2,
+        pub(crate) mass: Real,
+        pub(crate) radius: Real,
     }
 
     /// Data representation of a [`Star`] object
     #[derive(Clone, Copy, Debug, PartialEq)]
     pub struct StarData {
         pos: Vec2Data,
-        mass: f32,
+        mass: Real,
+        radius: Real,
     }
     impl StarData {
-        pub fn new(position: Vec2Data, mass: f32) -> Self {
+        pub fn new(position: Vec2Data, mass: Real, radius: Real) -> Self {
             StarData {
                 pos: position,
-                mass: mass,
+                mass,
+                radius,
             }
         }
+
+        /// Position of the star
+        pub fn get_position(&self) -> Vec2Data {
+            self.pos
+        }
+
+        /// Mass of the star
+        pub fn get_mass(&self) -> Real {
+            self.mass
+        }
+
+        /// Radius of the star
+        pub fn get_radius(&self) -> Real {
+            self.radius
+        }
     }
     impl Marshalling<Star> for StarData {
         fn convert(&self) -> Star {
             Star {
                 pos: self.pos.convert(),
                 mass: self.mass,
+                radius: self.radius,
             }
         }
     }
@@ -87,111 +447,373 @@ mod gravity {
     ///
     /// This use case adds the acceleration by gravity to all player and missile objects
     pub struct Gravity {
+        config: PhysicsConfig,
         repo: GravityDG,
     }
     impl Gravity {
         /// Create use case object
-        pub fn new(repo: GravityDG) -> Self {
-            Self { repo: repo.clone() }
+        pub fn new(config: PhysicsConfig, repo: GravityDG) -> Self {
+            Self {
+                config,
+                repo: repo.clone(),
+            }
         }
 
-        /// Add gravitational acceleration to all player and missile objects
-        pub fn execute(&self) {
-            let stars = self.get_stars();
-            self.apply_gravitation_to_players(&stars);
-            self.apply_gravitation_to_missiles(&stars);
+        /// Configuration of the use case
+        pub fn get_config(&self) -> PhysicsConfig {
+            self.config
         }
 
-        /// Get all attractants
-        fn get_stars(&self) -> Vec<Star> {
-            self.repo.get_stars_position_and_mass().convert()
+        /// Add gravitational acceleration to all player and missile objects
+        ///
+        /// Fails without changing any object if an acceleration is not finite.
+        pub fn execute(&self) -> Result<()> {
+            let stars = self.get_stars()?;
+            if self.config.is_fixed_point() {
+                return self.execute_fixed(&stars);
+            }
+            let players: Vec<PlayerData> = self.get_player_pos_and_acc()?.collect();
+            let missiles: Vec<MissileData> = self.get_missile_pos_and_acc()?.collect();
+            let mut fields = self.compute_fields(&stars, &players, &missiles).into_iter();
+            let player_updates = self.add_fields(players, fields.by_ref(), "player")?;
+            let missile_updates = self.add_fields(missiles, fields, "missile")?;
+            self.set_acceleration_for_player(player_updates.into_iter())?;
+            self.set_acceleration_for_missiles(missile_updates.into_iter())
         }
 
-        /// Add gravitation acceleration to player objects
-        fn apply_gravitation_to_players(&self, stars: &[Star]) {
-            let player_objs = self.get_player_pos_and_acc();
-            let acc_updates = self.comput_gravitation_for_objects(player_objs, stars);
-            self.set_acceleration_for_player(acc_updates)
+        /// Clamped gravitational acceleration of all player objects followed by all missile objects
+        fn compute_fields(
+            &self,
+            stars: &[Star],
+            players: &[PlayerData],
+            missiles: &[MissileData],
+        ) -> Vec<Vec2> {
+            let bodies: Vec<(Vec2, Real)> = players
+                .iter()
+                .map(|player| (player.pos, self.config.get_player_mass()))
+                .chain(
+                    missiles
+                        .iter()
+                        .map(|missile| (missile.pos, self.config.get_missile_mass())),
+                )
+                .collect();
+            let fields: Vec<Vec2> = match self.config.get_solver() {
+                GravitySolver::BarnesHut { theta } => {
+                    let attractants: Vec<(Vec2, Real)> = stars
+                        .iter()
+                        .map(|star| (star.pos, star.mass))
+                        .chain(bodies.iter().copied())
+                        .collect();
+                    let tree = QuadTree::new(&attractants, theta, self.config);
+                    bodies
+                        .iter()
+                        .map(|&(pos, mass)| tree.acceleration(pos, mass))
+                        .collect()
+                }
+                GravitySolver::Direct => {
+                    let star_field = |pos| star_field(&self.config, stars, pos);
+                    if self.config.is_n_body() {
+                        mutual_gravitation(&bodies, &self.config)
+                            .into_iter()
+                            .zip(&bodies)
+                            .map(|(acc, &(pos, _))| acc + star_field(pos))
+                            .collect()
+                    } else {
+                        bodies.iter().map(|&(pos, _)| star_field(pos)).collect()
+                    }
+                }
+            };
+            fields
+                .into_iter()
+                .map(|field| self.config.limit_acceleration(field))
+                .collect()
         }
 
-        /// Get position and acceleration of all player objects
-        fn get_player_pos_and_acc(&self) -> impl Iterator<Item = PlayerData> {
-            self.repo
-                .get_player_pos_and_acc()
+        /// Fixed-point variant of [`Self::execute`]
+        ///
+        /// Positions and accelerations are exchanged with the data repository in fixed-point
+        /// representation, such that no rounding to floating point numbers takes place.
+        fn execute_fixed(&self, stars: &[Star]) -> Result<()> {
+            let players = self.repo.get_fixed_player_pos_and_acc()?;
+            let missiles = self.repo.get_fixed_missile_pos_and_acc()?;
+            let bodies: Vec<(FixedVec2, Real)> = players
+                .iter()
+                .map(|(_, pos, _)| (pos.convert(), self.config.get_player_mass()))
+                .chain(
+                    missiles
+                        .iter()
+                        .map(|(_, _, pos, _)| (pos.convert(), self.config.get_missile_mass())),
+                )
+                .collect();
+            let mut fields = fixed_fields(&self.config, stars, &bodies).into_iter();
+            let add_field = |acc: FixedVec2Data, field: FixedVec2| -> FixedVec2Data {
+                let acc: FixedVec2 = acc.convert();
+                (acc + field).convert()
+            };
+            let player_updates = players
                 .into_iter()
-                .map(|data| data.convert())
+                .zip(fields.by_ref())
+                .map(|((id, _, acc), field)| (id, add_field(acc, field)))
+                .collect();
+            let missile_updates = missiles
+                .into_iter()
+                .zip(fields)
+                .map(|((p_id, m_id, _, acc), field)| (p_id, m_id, add_field(acc, field)))
+                .collect();
+            self.repo
+                .set_fixed_acceleration_for_player(player_updates)?;
+            self.repo
+                .set_fixed_acceleration_for_missiles(missile_updates)
         }
 
-        /// Update acceleration for player objects
-        fn set_acceleration_for_player(&self, updates: impl Iterator<Item = (PlayerId, Vec2)>) {
-            self.repo
-                .set_acceleration_for_player(updates.map(|data| data.convert()).collect());
+        /// Add the gravitational acceleration to the acceleration of objects
+        fn add_fields<T: Copy + Debug>(
+            &self,
+            objs: Vec<ObjInfo<T>>,
+            fields: impl Iterator<Item = Vec2>,
+            kind: &str,
+        ) -> Result<Vec<(T, Vec2)>> {
+            objs.into_iter()
+                .zip(fields)
+                .map(|(obj, field)| {
+                    let acc = obj.acc + field;
+                    if acc.is_finite() {
+                        Ok((obj.id, acc))
+                    } else {
+                        Err(Error::NonFinite(format!(
+                            "acceleration of {kind} {:?}",
+                            obj.id
+                        )))
+                    }
+                })
+                .collect()
+        }
+
+        /// Get all attractants
+        fn get_stars(&self) -> Result<Vec<Star>> {
+            Ok(self.repo.get_stars_position_and_mass()?.convert())
         }
 
-        /// Add gravitational acceleration to missile objects
-        fn apply_gravitation_to_missiles(&self, stars: &[Star]) {
-            let missile_objs = self.get_missile_pos_and_acc();
-            let acc_updates = self.comput_gravitation_for_objects(missile_objs, stars);
-            self.set_acceleration_for_missiles(acc_updates)
+        /// Get position and acceleration of all player objects
+        fn get_player_pos_and_acc(&self) -> Result<impl Iterator<Item = PlayerData>> {
+            Ok(self
+                .repo
+                .get_player_pos_and_acc()?
+                .into_iter()
+                .map(|data| data.convert()))
         }
 
-        fn comput_gravitation_for_objects<'a, T>(
+        /// Update acceleration for player objects
+        fn set_acceleration_for_player(
             &self,
-            objs: impl Iterator<Item = ObjInfo<T>> + 'a,
-            stars: &'a [Star],
-        ) -> impl Iterator<Item = (T, Vec2)> + 'a {
-            objs.map(|obj| {
-                (
-                    obj.id,
-                    obj.acc
-                        + stars
-                            .iter()
-                            .map(|star| gravity(star.pos, star.mass, obj.pos))
-                            .sum(),
-                )
-            })
+            updates: impl Iterator<Item = (PlayerId, Vec2)>,
+        ) -> Result<()> {
+            self.repo
+                .set_acceleration_for_player(updates.map(|data| data.convert()).collect())
         }
 
         /// Get position and acceleration of all missiles. IDs are folded into single composit key.
-        fn get_missile_pos_and_acc(&self) -> impl Iterator<Item = MissileData> {
-            self.repo
-                .get_missile_pos_and_acc()
+        fn get_missile_pos_and_acc(&self) -> Result<impl Iterator<Item = MissileData>> {
+            Ok(self
+                .repo
+                .get_missile_pos_and_acc()?
                 .into_iter()
-                .map(|data| data.convert())
+                .map(|data| data.convert()))
         }
 
         /// Update acceleration for missile objects
         fn set_acceleration_for_missiles(
             &self,
             updates: impl Iterator<Item = ((PlayerId, MissileId), Vec2)>,
-        ) {
+        ) -> Result<()> {
             self.repo
-                .set_acceleration_for_missiles(updates.map(|data| data.convert()).collect());
+                .set_acceleration_for_missiles(updates.map(|data| data.convert()).collect())
+        }
+    }
+
+    /// Gravitational acceleration of all stars at a position
+    pub(super) fn star_field(config: &PhysicsConfig, stars: &[Star], pos: Vec2) -> Vec2 {
+        stars
+            .iter()
+            .map(|star| {
+                softened_gravity(
+                    config.nearest_image(star.pos, pos),
+                    config.get_gravity_constant() * star.mass,
+                    pos,
+                    config.get_softening(),
+                )
+            })
+            .sum()
+    }
+
+    /// Clamped gravitational acceleration of all stars at a position
+    pub(crate) fn limited_star_field(config: &PhysicsConfig, stars: &[Star], pos: Vec2) -> Vec2 {
+        if config.is_fixed_point() {
+            let stars = fixed_stars(config, stars);
+            config
+                .fixed_limit_acceleration(fixed_star_field(config, &stars, pos.into()))
+                .into()
+        } else {
+            config.limit_acceleration(star_field(config, stars, pos))
+        }
+    }
+
+    /// Position and gravitational parameter of all stars in fixed-point representation
+    pub(super) fn fixed_stars(config: &PhysicsConfig, stars: &[Star]) -> Vec<(FixedVec2, Fixed)> {
+        let gravity_constant = Fixed::from(config.get_gravity_constant());
+        stars
+            .iter()
+            .map(|star| (star.pos.into(), gravity_constant * Fixed::from(star.mass)))
+            .collect()
+    }
+
+    /// Fixed-point variant of [`star_field`] for stars given by [`fixed_stars`]
+    pub(super) fn fixed_star_field(
+        config: &PhysicsConfig,
+        stars: &[(FixedVec2, Fixed)],
+        pos: FixedVec2,
+    ) -> FixedVec2 {
+        let softening = Fixed::from(config.get_softening());
+        stars
+            .iter()
+            .map(|&(star_pos, gravitational_parameter)| {
+                fixed::softened_gravity(
+                    pos + config.fixed_separation(pos, star_pos),
+                    gravitational_parameter,
+                    pos,
+                    softening,
+                )
+            })
+            .sum()
+    }
+
+    /// Clamped gravitational acceleration of bodies given by `(position, mass)` computed by
+    /// direct summation in fixed-point arithmetic
+    fn fixed_fields(
+        config: &PhysicsConfig,
+        stars: &[Star],
+        bodies: &[(FixedVec2, Real)],
+    ) -> Vec<FixedVec2> {
+        let stars = fixed_stars(config, stars);
+        let gravity_constant = Fixed::from(config.get_gravity_constant());
+        let softening = Fixed::from(config.get_softening());
+        let bodies: Vec<(FixedVec2, Fixed)> = bodies
+            .iter()
+            .map(|&(pos, mass)| (pos, gravity_constant * Fixed::from(mass)))
+            .collect();
+        let mut fields: Vec<FixedVec2> = bodies
+            .iter()
+            .map(|&(pos, _)| fixed_star_field(config, &stars, pos))
+            .collect();
+        for (i, &(pos_i, mu_i)) in bodies.iter().enumerate() {
+            for (j, &(pos_j, mu_j)) in bodies.iter().enumerate().skip(i + 1) {
+                if mu_i == Fixed::ZERO && mu_j == Fixed::ZERO {
+                    continue;
+                }
+                let r = config.fixed_separation(pos_i, pos_j);
+                if r == FixedVec2::zero() {
+                    continue;
+                }
+                fields[i] += fixed::softened_gravity(pos_i + r, mu_j, pos_i, softening);
+                fields[j] += fixed::softened_gravity(pos_j - r, mu_i, pos_j, softening);
+            }
+        }
+        fields
+            .into_iter()
+            .map(|field| config.fixed_limit_acceleration(field))
+            .collect()
+    }
+
+    /// Gravitational acceleration of bodies given by `(position, mass)` by each other
+    ///
+    /// Every pair is visited only once and both bodies receive their share of the interaction.
+    /// Massive bodies are visited first, such that pairs of massless bodies are skipped entirely
+    /// and massless missiles only add cost linear in the number of massive bodies. Bodies at the
+    /// same position do not attract each other.
+    pub(super) fn mutual_gravitation(bodies: &[(Vec2, Real)], config: &PhysicsConfig) -> Vec<Vec2> {
+        let gravity_constant = config.get_gravity_constant();
+        let softening = config.get_softening();
+        let (massive, massless): (Vec<usize>, Vec<usize>) =
+            (0..bodies.len()).partition(|&index| bodies[index].1 > 0.0);
+        let order: Vec<usize> = massive.iter().chain(massless.iter()).copied().collect();
+        let mut acc = vec![Vec2::zero(); bodies.len()];
+        for (n, &i) in order.iter().enumerate().take(massive.len()) {
+            let (pos_i, mass_i) = bodies[i];
+            for &j in &order[n + 1..] {
+                let (pos_j, mass_j) = bodies[j];
+                let r = config.separation(pos_i, pos_j);
+                let r2 = r.len2();
+                if r2 == 0.0 {
+                    continue;
+                }
+                let d2 = r2 + softening * softening;
+                let scale = gravity_constant / (d2 * d2.sqrt());
+                acc[i] += r * (mass_j * scale);
+                acc[j] += r * (-mass_i * scale);
+            }
         }
+        acc
     }
 
     /// Data repository interface for gravity use case.
     pub trait GravityDataGateway {
         /// Get all [`Star`] objects
-        fn get_stars_position_and_mass(&self) -> Vec<StarData>;
+        fn get_stars_position_and_mass(&self) -> Result<Vec<StarData>>;
         /// Get position and acceleration of all player objects
-        fn get_player_pos_and_acc(&self) -> Vec<(PlayerIdData, Vec2Data, Vec2Data)>;
+        fn get_player_pos_and_acc(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Vec2Data)>>;
         /// Get position and acceleration of all miissile objects
-        fn get_missile_pos_and_acc(&self)
-            -> Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>;
+        fn get_missile_pos_and_acc(
+            &self,
+        ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>>;
         /// Update acceleration of player objects
-        fn set_acceleration_for_player(&self, updates: Vec<(PlayerIdData, Vec2Data)>);
+        fn set_acceleration_for_player(&self, updates: Vec<(PlayerIdData, Vec2Data)>)
+            -> Result<()>;
         /// Update acceleration of missile objects
         fn set_acceleration_for_missiles(
             &self,
             updates: Vec<(PlayerIdData, MissileIdData, Vec2Data)>,
-        );
+        ) -> Result<()>;
+
+        /// Fixed-point variant of [`Self::get_player_pos_and_acc`]
+        ///
+        /// Rounds through the floating point methods by default. Backends that keep the exact
+        /// fixed-point state override the fixed-point methods.
+        fn get_fixed_player_pos_and_acc(&self) -> Result<Vec<FixedPlayerData>> {
+            Ok(self.get_player_pos_and_acc()?.convert())
+        }
+        /// Fixed-point variant of [`Self::get_missile_pos_and_acc`]
+        fn get_fixed_missile_pos_and_acc(&self) -> Result<Vec<FixedMissileData>> {
+            Ok(self.get_missile_pos_and_acc()?.convert())
+        }
+        /// Fixed-point variant of [`Self::set_acceleration_for_player`]
+        fn set_fixed_acceleration_for_player(
+            &self,
+            updates: Vec<(PlayerIdData, FixedVec2Data)>,
+        ) -> Result<()> {
+            self.set_acceleration_for_player(updates.convert())
+        }
+        /// Fixed-point variant of [`Self::set_acceleration_for_missiles`]
+        fn set_fixed_acceleration_for_missiles(
+            &self,
+            updates: Vec<(PlayerIdData, MissileIdData, FixedVec2Data)>,
+        ) -> Result<()> {
+            self.set_acceleration_for_missiles(updates.convert())
+        }
     }
     type GravityDG = Rc<dyn GravityDataGateway>;
 
     #[cfg(test)]
     mod test_gravity {
-        use super::{Gravity, GravityDataGateway, MissileIdData, PlayerIdData, StarData, Vec2Data};
+        use super::{
+            mutual_gravitation, Gravity, GravityDataGateway, GravitySolver, MissileIdData,
+            PhysicsConfig, PlayerIdData, StarData, Vec2Data,
+        };
+        use crate::{
+            entities::{gravity, Real, Vec2},
+            physics::{Arena, BoundaryMode},
+            repo_interfaces::Marshalling,
+            Error, Result,
+        };
         use std::{cell::RefCell, rc::Rc};
 
         #[derive(Default)]
@@ -206,11 +828,11 @@ mod gravity {
             data: MockData,
         }
         impl GravityDataGateway for RefCell<MockDataGateway> {
-            fn get_stars_position_and_mass(&self) -> Vec<StarData> {
-                self.borrow().data.stars.clone()
+            fn get_stars_position_and_mass(&self) -> Result<Vec<StarData>> {
+                Ok(self.borrow().data.stars.clone())
             }
 
-            fn get_player_pos_and_acc(&self) -> Vec<(PlayerIdData, Vec2Data, Vec2Data)> {
+            fn get_player_pos_and_acc(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Vec2Data)>> {
                 let state = self.borrow();
                 state
                     .data
@@ -222,49 +844,65 @@ mod gravity {
                             .player_acc
                             .iter()
                             .position(|&(id2, _)| id2 == id)
-                            .unwrap();
-                        (id, pos, state.data.player_acc[index].1)
+                            .ok_or(Error::UnknownPlayer(id))?;
+                        Ok((id, pos, state.data.player_acc[index].1))
                     })
                     .collect()
             }
 
-            fn set_acceleration_for_player(&self, updates: Vec<(PlayerIdData, Vec2Data)>) {
+            fn set_acceleration_for_player(
+                &self,
+                updates: Vec<(PlayerIdData, Vec2Data)>,
+            ) -> Result<()> {
                 self.borrow_mut().data.player_acc = updates;
+                Ok(())
             }
 
             fn get_missile_pos_and_acc(
                 &self,
-            ) -> Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)> {
-                self.borrow().data.missile.clone()
+            ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>> {
+                Ok(self.borrow().data.missile.clone())
             }
 
             fn set_acceleration_for_missiles(
                 &self,
                 updates: Vec<(PlayerIdData, MissileIdData, Vec2Data)>,
-            ) {
+            ) -> Result<()> {
                 self.borrow_mut()
                     .data
                     .missile
                     .iter_mut()
-                    .for_each(|missile| {
+                    .try_for_each(|missile| {
                         let pos = updates
                             .iter()
                             .position(|&item| (item.0 == missile.0) & (item.1 == missile.1))
-                            .unwrap();
+                            .ok_or(Error::UnknownMissile(missile.0, missile.1))?;
                         missile.3 = updates[pos].2;
-                    });
+                        Ok(())
+                    })
             }
         }
 
         fn setup_gravity_test(data: MockData) -> Rc<RefCell<MockDataGateway>> {
-            Rc::new(RefCell::new(MockDataGateway { data: data }))
+            Rc::new(RefCell::new(MockDataGateway { data }))
         }
 
         #[test]
         fn gravity_not_failing_when_repo_is_empty() {
             let repo = setup_gravity_test(MockData::default());
-            let gravity = Gravity::new(repo.clone());
-            gravity.execute();
+            let gravity = Gravity::new(PhysicsConfig::new(), repo.clone());
+            gravity.execute().unwrap();
+        }
+
+        #[test]
+        fn gravity_propagates_gateway_errors() {
+            let data = MockData {
+                player_pos: vec![(1, [11.0, 0.0])],
+                ..MockData::default()
+            };
+            let repo = setup_gravity_test(data);
+            let gravity = Gravity::new(PhysicsConfig::new(), repo.clone());
+            assert_eq!(gravity.execute(), Err(Error::UnknownPlayer(1)));
         }
 
         #[test]
@@ -273,184 +911,983 @@ mod gravity {
                 stars: vec![StarData {
                     pos: [1.0, 0.0],
                     mass: 1.0,
+                    radius: 0.5,
                 }],
                 player_pos: vec![(1, [11.0, 0.0]), (2, [1.0, -10.0])],
                 player_acc: vec![(1, [0.0, 1.0]), (2, [1.0, 4.0])],
                 ..MockData::default()
             };
             let repo = setup_gravity_test(data);
-            let gravity = Gravity::new(repo.clone());
-            gravity.execute();
+            let gravity = Gravity::new(PhysicsConfig::new(), repo.clone());
+            gravity.execute().unwrap();
         }
-    }
-}
-
-mod integrate {
-
-    use crate::{
-        entities::Vec2,
-        repo_interfaces::{
-            Marshalling, MissileId, MissileIdData, PlayerId, PlayerIdData, Vec2Data,
-        },
-    };
-    use std::rc::Rc;
-
-    type ObjInfo<T> = (T, Vec2, Vec2, Vec2);
-    type PlayerInfo = ObjInfo<PlayerId>;
-    type MissileInfo = ObjInfo<(PlayerId, MissileId)>;
-    type PlayerInfoData = (PlayerIdData, Vec2Data, Vec2Data, Vec2Data);
-    type MissileInfoData = (PlayerIdData, MissileIdData, Vec2Data, Vec2Data, Vec2Data);
 
-    /// Integrate use-case
-    ///
-    /// Integrates position and velocity of all objects. This will also set the acceleration to zero.
-    pub struct Integrate {
-        // delta_time: f32,
-        repo: Rc<dyn IntegrateDataGateway>,
-    }
-    impl Integrate {
-        /// Create a new integration use case
-        pub fn new(repo: Rc<dyn IntegrateDataGateway>) -> Self {
-            Self { repo }
+        #[test]
+        fn ships_attract_each_other_with_mass() {
+            let data = MockData {
+                player_pos: vec![(1, [0.0, 0.0]), (2, [2.0, 0.0])],
+                player_acc: vec![(1, [0.0, 0.0]), (2, [0.0, 0.0])],
+                missile: vec![(1, 0, [0.0, 4.0], [0.0, 0.0])],
+                ..MockData::default()
+            };
+            let repo = setup_gravity_test(data);
+            let config = PhysicsConfig::new().with_player_mass(4.0);
+            Gravity::new(config, repo.clone()).execute().unwrap();
+            let data = &repo.borrow().data;
+            // Massless missiles are pulled without pulling back
+            assert_eq!(data.player_acc, vec![(1, [1.0, 0.0]), (2, [-1.0, 0.0])]);
+            let missile_acc = data.missile[0].3;
+            assert!(missile_acc[0] > 0.0 && missile_acc[1] < 0.0);
         }
 
-        /// Run the use case
-        pub fn execute(&self, delta_time: impl Into<f32>) {
-            let dt = delta_time.into();
-            self.integrate_player(dt);
-            self.integrate_missiles(dt);
+        #[test]
+        fn missiles_attract_with_mass() {
+            let data = MockData {
+                player_pos: vec![(1, [0.0, 0.0])],
+                player_acc: vec![(1, [0.0, 0.0])],
+                missile: vec![(2, 0, [0.0, 2.0], [0.0, 0.0])],
+                ..MockData::default()
+            };
+            let repo = setup_gravity_test(data);
+            let config = PhysicsConfig::new().with_missile_mass(8.0);
+            Gravity::new(config, repo.clone()).execute().unwrap();
+            let data = &repo.borrow().data;
+            assert_eq!(data.player_acc, vec![(1, [0.0, 2.0])]);
+            assert_eq!(data.missile[0].3, [0.0, 0.0]);
         }
 
-        /// Integrate a vector in time using eulers method
-        fn integrate(v: Vec2, inc: Vec2, delta_time: f32) -> Vec2 {
-            v + inc * delta_time
-        }
-        /// Integrate position and velocity and set acceleration to zero    
-        fn integrate_pos_vel_and_acc<T>(
-            &self,
-            (id, pos, vel, acc): (T, Vec2, Vec2, Vec2),
-            delta_time: f32,
-        ) -> ObjInfo<T> {
-            (
-                id,
-                Self::integrate(pos, vel, delta_time),
-                Self::integrate(vel, acc, delta_time),
-                Vec2::zero(),
-            )
+        #[test]
+        fn gravity_scales_with_gravity_constant() {
+            let data = MockData {
+                stars: vec![StarData::new([0.0, 0.0], 8.0, 1.0)],
+                player_pos: vec![(1, [2.0, 0.0])],
+                player_acc: vec![(1, [0.0, 0.0])],
+                ..MockData::default()
+            };
+            let repo = setup_gravity_test(data);
+            let config = PhysicsConfig::new().with_gravity_constant(0.5);
+            Gravity::new(config, repo.clone()).execute().unwrap();
+            assert_eq!(repo.borrow().data.player_acc, vec![(1, [-1.0, 0.0])]);
         }
 
-        /// Integrate position and velocity of all player objects. Also set acceleration to zero.
-        fn integrate_player(&self, delta_time: f32) {
-            let player_objs = self.get_player_info();
-            let updated_info =
-                player_objs.map(|data| self.integrate_pos_vel_and_acc(data, delta_time));
-            self.set_player_info(updated_info);
+        #[test]
+        fn gravity_attracts_nearest_image_when_wrapping() {
+            let data = MockData {
+                stars: vec![StarData::new([-9.0, 0.0], 4.0, 1.0)],
+                player_pos: vec![(1, [9.0, 0.0])],
+                player_acc: vec![(1, [0.0, 0.0])],
+                ..MockData::default()
+            };
+            let repo = setup_gravity_test(data);
+            let arena = Arena::new([-10.0, -10.0], [10.0, 10.0], BoundaryMode::Wrap).unwrap();
+            let config = PhysicsConfig::new().with_arena(arena);
+            Gravity::new(config, repo.clone()).execute().unwrap();
+            assert_eq!(repo.borrow().data.player_acc, vec![(1, [1.0, 0.0])]);
         }
 
-        /// Iterator of position, velocity and acceleration of all player objects
-        fn get_player_info(&self) -> impl Iterator<Item = PlayerInfo> {
-            self.repo
-                .get_player_info()
-                .into_iter()
-                .map(|item| item.convert())
+        #[test]
+        fn softening_and_clamp_limit_acceleration() {
+            let data = || MockData {
+                stars: vec![StarData::new([0.0, 0.0], 100.0, 1.0)],
+                player_pos: vec![(1, [0.01, 0.0])],
+                player_acc: vec![(1, [0.0, 0.0])],
+                ..MockData::default()
+            };
+            let acc_with = |config| {
+                let repo = setup_gravity_test(data());
+                Gravity::new(config, repo.clone()).execute().unwrap();
+                let acc: Vec2 = repo.borrow().data.player_acc[0].1.convert();
+                acc.len()
+            };
+            assert!(acc_with(PhysicsConfig::new()) > 1e5);
+            assert!(acc_with(PhysicsConfig::new().with_softening(1.0)) < 1.0);
+            let clamped = acc_with(PhysicsConfig::new().with_max_acceleration(50.0));
+            assert!((clamped - 50.0).abs() < 1e-3);
         }
-        /// Update position, velocity and acceleration for all player objects
-        fn set_player_info(&self, data: impl Iterator<Item = PlayerInfo>) {
-            self.repo
-                .set_player_info(data.map(|item| item.convert()).collect())
+
+        #[test]
+        fn non_finite_acceleration_rejected() {
+            let data = MockData {
+                stars: vec![StarData::new([0.0, 0.0], 100.0, 1.0)],
+                player_pos: vec![(1, [1.0, 0.0]), (2, [Real::NAN, 0.0])],
+                player_acc: vec![(1, [0.0, 0.0]), (2, [0.0, 0.0])],
+                ..MockData::default()
+            };
+            let repo = setup_gravity_test(data);
+            let gravity = Gravity::new(PhysicsConfig::new(), repo.clone());
+            assert_eq!(
+                gravity.execute(),
+                Err(Error::NonFinite("acceleration of player 2".into()))
+            );
+            assert_eq!(
+                repo.borrow().data.player_acc,
+                vec![(1, [0.0, 0.0]), (2, [0.0, 0.0])]
+            );
         }
 
-        /// Integrate position and velocity of all missile objects. Also set acceleration to zero.
-        fn integrate_missiles(&self, delta_time: f32) {
-            let missile_objs = self.get_missile_info();
-            let updated_info =
-                missile_objs.map(|data| self.integrate_pos_vel_and_acc(data, delta_time));
-            self.set_missile_info(updated_info);
+        #[test]
+        fn barnes_hut_solver_matches_direct_solver() {
+            let data = || MockData {
+                stars: vec![
+                    StarData::new([0.0, 0.0], 100.0, 5.0),
+                    StarData::new([50.0, 20.0], 30.0, 2.0),
+                ],
+                player_pos: vec![(1, [20.0, 0.0]), (2, [-30.0, 10.0])],
+                player_acc: vec![(1, [0.0, 1.0]), (2, [0.0, 0.0])],
+                missile: vec![(1, 0, [25.0, 5.0], [0.0, 0.0])],
+            };
+            let solve = |solver| {
+                let repo = setup_gravity_test(data());
+                let config = PhysicsConfig::new()
+                    .with_player_mass(2.0)
+                    .with_solver(solver)
+                    .unwrap();
+                Gravity::new(config, repo.clone()).execute().unwrap();
+                let data = &repo.borrow().data;
+                let mut acc: Vec<Vec2> = data
+                    .player_acc
+                    .iter()
+                    .map(|(_, acc)| acc.convert())
+                    .collect();
+                acc.extend(
+                    data.missile
+                        .iter()
+                        .map(|missile| -> Vec2 { missile.3.convert() }),
+                );
+                acc
+            };
+            let direct = solve(GravitySolver::Direct);
+            let tree = solve(GravitySolver::BarnesHut { theta: 0.5 });
+            for (exact, approx) in direct.into_iter().zip(tree) {
+                assert!((exact - approx).len() <= 0.01 * exact.len());
+            }
         }
 
-        /// Get position, velocity and acceleration of all missile objects
-        fn get_missile_info(&self) -> impl Iterator<Item = MissileInfo> {
+        #[test]
+        fn invalid_opening_angle_rejected() {
+            for theta in [-0.5, Real::NAN, Real::INFINITY] {
+                assert!(matches!(
+                    PhysicsConfig::new().with_solver(GravitySolver::BarnesHut { theta }),
+                    Err(Error::InvalidValue(_))
+                ));
+            }
+        }
+
+        #[test]
+        fn fixed_point_gravity_matches_float() {
+            let data = || MockData {
+                stars: vec![
+                    StarData::new([0.0, 0.0], 100.0, 5.0),
+                    StarData::new([50.0, 20.0], 30.0, 2.0),
+                ],
+                player_pos: vec![(1, [20.0, 0.0]), (2, [-30.0, 10.0])],
+                player_acc: vec![(1, [0.0, 1.0]), (2, [0.0, 0.0])],
+                missile: vec![(1, 0, [25.0, 5.0], [0.0, 0.0])],
+            };
+            let arena = Arena::new([-60.0, -60.0], [60.0, 60.0], BoundaryMode::Wrap).unwrap();
+            let solve = |config: PhysicsConfig| {
+                let repo = setup_gravity_test(data());
+                let config = config
+                    .with_gravity_constant(1.5)
+                    .with_player_mass(2.0)
+                    .with_missile_mass(0.5)
+                    .with_softening(1.0)
+                    .with_max_acceleration(0.3)
+                    .with_arena(arena);
+                Gravity::new(config, repo.clone()).execute().unwrap();
+                let data = &repo.borrow().data;
+                let mut acc: Vec<Vec2> = data
+                    .player_acc
+                    .iter()
+                    .map(|(_, acc)| acc.convert())
+                    .collect();
+                acc.extend(
+                    data.missile
+                        .iter()
+                        .map(|missile| -> Vec2 { missile.3.convert() }),
+                );
+                acc
+            };
+            let float = solve(PhysicsConfig::new());
+            let fixed = solve(PhysicsConfig::new().with_fixed_point());
+            for (float, fixed) in float.into_iter().zip(fixed) {
+                assert!((float - fixed).len() <= 1e-5 * float.len());
+            }
+        }
+
+        #[test]
+        fn mutual_gravitation_matches_direct_sum() {
+            let bodies: Vec<(Vec2, Real)> = (0..300)
+                .map(|i| {
+                    let angle = i as Real * 2.39996;
+                    let radius = 5.0 + i as Real;
+                    let mass = if i % 3 == 0 {
+                        0.0
+                    } else {
+                        1.0 + (i % 7) as Real
+                    };
+                    (Vec2::new(radius * angle.cos(), radius * angle.sin()), mass)
+                })
+                .collect();
+            let acc = mutual_gravitation(&bodies, &PhysicsConfig::new());
+            for (i, &(pos, _)) in bodies.iter().enumerate() {
+                let expected: Vec2 = bodies
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, &(_, mass))| j != i && mass > 0.0)
+                    .map(|(_, &(other, mass))| gravity(other, mass, pos))
+                    .sum();
+                let error = (acc[i] - expected).len();
+                assert!(error <= 1e-4 * expected.len(), "{i}: {:?}", acc[i]);
+            }
+        }
+    }
+}
+
+mod barnes_hut {
+    use super::PhysicsConfig;
+    use crate::entities::{Real, Vec2};
+
+    /// Maximum depth of the tree. Bodies closer than the size of the deepest cells share a leaf.
+    const MAX_DEPTH: usize = 24;
+
+    /// Cell of a [`QuadTree`]
+    #[derive(Clone, Copy, Debug)]
+    struct Node {
+        /// Center of the square covered by the cell
+        center: Vec2,
+        /// Half of the edge length of the square
+        half_size: Real,
+        /// Total mass of all bodies within the cell
+        mass: Real,
+        /// Center of mass of all bodies within the cell
+        mass_center: Vec2,
+        /// Index of the first of four consecutive child cells, `None` for leaves
+        children: Option<usize>,
+    }
+
+    impl Node {
+        fn new(center: Vec2, half_size: Real) -> Self {
+            Node {
+                center,
+                half_size,
+                mass: 0.0,
+                mass_center: Vec2::zero(),
+                children: None,
+            }
+        }
+
+        /// Check if `pos` lies within the square covered by the cell
+        ///
+        /// Matches [`Self::quadrant`], positions on the center lines belong to the upper cells.
+        fn contains(&self, pos: Vec2) -> bool {
+            let offset = pos - self.center;
+            let within = |x: Real| x >= -self.half_size && x < self.half_size;
+            within(offset.get_x()) && within(offset.get_y())
+        }
+
+        /// Index of the quadrant, relative to the first child, that contains `pos`
+        fn quadrant(&self, pos: Vec2) -> usize {
+            usize::from(pos.get_x() >= self.center.get_x())
+                + 2 * usize::from(pos.get_y() >= self.center.get_y())
+        }
+    }
+
+    /// Barnes-Hut quadtree over attracting bodies
+    ///
+    /// Distant groups of bodies are approximated by a single body at their center of mass. A
+    /// cell counts as distant if its edge length is smaller than `theta` times the distance to its
+    /// center of mass. An opening angle of zero yields the exact sum.
+    pub(super) struct QuadTree {
+        nodes: Vec<Node>,
+        theta: Real,
+        config: PhysicsConfig,
+    }
+
+    impl QuadTree {
+        /// Build the tree from `(position, mass)` of all attracting bodies
+        ///
+        /// Massless bodies are ignored. Gravitational constant, softening and periodic images
+        /// are taken from `config`.
+        pub(super) fn new(bodies: &[(Vec2, Real)], theta: Real, config: PhysicsConfig) -> Self {
+            let mut tree = QuadTree {
+                nodes: Vec::with_capacity(2 * bodies.len() + 1),
+                theta,
+                config,
+            };
+            let mut massive = bodies.iter().filter(|(_, mass)| *mass > 0.0).peekable();
+            let Some(&&(first, _)) = massive.peek() else {
+                return tree;
+            };
+            let (min, max) = massive
+                .clone()
+                .fold((first, first), |(min, max), &(pos, _)| {
+                    (
+                        Vec2::new(min.get_x().min(pos.get_x()), min.get_y().min(pos.get_y())),
+                        Vec2::new(max.get_x().max(pos.get_x()), max.get_y().max(pos.get_y())),
+                    )
+                });
+            let extent = max - min;
+            let half_size = 0.5 * extent.get_x().max(extent.get_y()) * 1.0001 + Real::EPSILON;
+            tree.nodes.push(Node::new(0.5 * (min + max), half_size));
+            for &(pos, mass) in massive {
+                tree.insert(pos, mass);
+            }
+            tree
+        }
+
+        /// Add a body to the tree
+        fn insert(&mut self, pos: Vec2, mass: Real) {
+            let mut index = 0;
+            for depth in 0.. {
+                let node = self.nodes[index];
+                match node.children {
+                    Some(first_child) => {
+                        self.add_mass(index, pos, mass);
+                        index = first_child + node.quadrant(pos);
+                    }
+                    None if node.mass == 0.0 || depth >= MAX_DEPTH => {
+                        self.add_mass(index, pos, mass);
+                        return;
+                    }
+                    None => {
+                        // Move the body of the occupied leaf one level down and retry
+                        let first_child = self.nodes.len();
+                        let half_size = 0.5 * node.half_size;
+                        for quadrant in 0..4 {
+                            let offset = Vec2::new(
+                                if quadrant & 1 == 1 {
+                                    half_size
+                                } else {
+                                    -half_size
+                                },
+                                if quadrant & 2 == 2 {
+                                    half_size
+                                } else {
+                                    -half_size
+                                },
+                            );
+                            self.nodes.push(Node::new(node.center + offset, half_size));
+                        }
+                        let child = &mut self.nodes[first_child + node.quadrant(node.mass_center)];
+                        child.mass = node.mass;
+                        child.mass_center = node.mass_center;
+                        self.nodes[index].children = Some(first_child);
+                        self.add_mass(index, pos, mass);
+                        index = first_child + node.quadrant(pos);
+                    }
+                }
+            }
+        }
+
+        /// Add a body to the total mass and the center of mass of a cell
+        ///
+        /// The center of mass of a cell with a single body is exactly its position.
+        fn add_mass(&mut self, index: usize, pos: Vec2, mass: Real) {
+            let node = &mut self.nodes[index];
+            node.mass += mass;
+            node.mass_center += (pos - node.mass_center) * (mass / node.mass);
+        }
+
+        /// Gravitational acceleration of a body with `mass` at `pos`
+        ///
+        /// If the body is part of the tree, its own mass is removed from every accepted cell that
+        /// contains it, such that the body does not attract itself. Pass a mass of zero for
+        /// positions that are not part of the tree.
+        pub(super) fn acceleration(&self, pos: Vec2, mass: Real) -> Vec2 {
+            let mut acc = Vec2::zero();
+            if self.nodes.is_empty() {
+                return acc;
+            }
+            let theta2 = self.theta * self.theta;
+            let softening = self.config.get_softening();
+            let mut stack = vec![0];
+            while let Some(index) = stack.pop() {
+                let node = &self.nodes[index];
+                let (cell_mass, mass_center) = if node.contains(pos) && mass > 0.0 {
+                    let cell_mass = node.mass - mass;
+                    let mass_center = (node.mass * node.mass_center - mass * pos) / cell_mass;
+                    (cell_mass, mass_center)
+                } else {
+                    (node.mass, node.mass_center)
+                };
+                if cell_mass <= 0.0 {
+                    continue;
+                }
+                let r = self.config.separation(pos, mass_center);
+                let r2 = r.len2();
+                let size = 2.0 * node.half_size;
+                match node.children {
+                    Some(first_child) if size * size >= theta2 * r2 => {
+                        stack.extend(first_child..first_child + 4);
+                    }
+                    _ if r2 > 0.0 => {
+                        let d2 = r2 + softening * softening;
+                        let gravity_constant = self.config.get_gravity_constant();
+                        acc += r * (gravity_constant * cell_mass / (d2 * d2.sqrt()));
+                    }
+                    _ => {}
+                }
+            }
+            acc
+        }
+    }
+
+    #[cfg(test)]
+    mod test_barnes_hut {
+        use super::{super::gravity::mutual_gravitation, PhysicsConfig, QuadTree};
+        use crate::entities::{gravity, Real, Vec2};
+
+        /// Pseudo random bodies from a linear congruential generator
+        fn random_bodies(count: usize) -> Vec<(Vec2, Real)> {
+            let mut seed: u32 = 12345;
+            let mut next = move || {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as Real / (1 << 24) as Real
+            };
+            (0..count)
+                .map(|_| {
+                    let pos = Vec2::new(1000.0 * next() - 500.0, 1000.0 * next() - 500.0);
+                    (pos, 1.0 + 99.0 * next())
+                })
+                .collect()
+        }
+
+        /// Exact acceleration and sum of absolute contributions at `pos`
+        fn direct_sum(bodies: &[(Vec2, Real)], pos: Vec2) -> (Vec2, Real) {
+            bodies
+                .iter()
+                .filter(|(other, _)| *other != pos)
+                .map(|&(other, mass)| gravity(other, mass, pos))
+                .fold((Vec2::zero(), 0.0), |(acc, total), contribution| {
+                    (acc + contribution, total + contribution.len())
+                })
+        }
+
+        #[test]
+        fn empty_tree_does_not_attract() {
+            let tree = QuadTree::new(&[(Vec2::new(1.0, 0.0), 0.0)], 0.5, PhysicsConfig::new());
+            assert_eq!(tree.acceleration(Vec2::zero(), 0.0), Vec2::zero());
+        }
+
+        #[test]
+        fn zero_opening_angle_yields_exact_sum() {
+            let bodies = random_bodies(200);
+            let tree = QuadTree::new(&bodies, 0.0, PhysicsConfig::new());
+            for &(pos, mass) in &bodies {
+                let (exact, total) = direct_sum(&bodies, pos);
+                assert!((tree.acceleration(pos, mass) - exact).len() <= 1e-4 * total);
+            }
+        }
+
+        #[test]
+        fn approximation_within_tolerance() {
+            let bodies = random_bodies(1000);
+            let tree = QuadTree::new(&bodies, 0.5, PhysicsConfig::new());
+            let probes = random_bodies(100);
+            for &(pos, mass) in bodies.iter().take(100).chain(probes.iter()) {
+                let (exact, total) = direct_sum(&bodies, pos);
+                let error = (tree.acceleration(pos, mass) - exact).len();
+                assert!(
+                    error <= 0.01 * total,
+                    "{error} exceeds tolerance at {pos:?}"
+                );
+            }
+        }
+
+        #[test]
+        fn bodies_do_not_attract_themselves() {
+            // At an opening angle of one, the light body accepts the root cell, which also
+            // contains the light body itself
+            let bodies = [
+                (Vec2::new(0.0, 0.0), 1.0),
+                (Vec2::new(49.0, 49.0), 100.0),
+                (Vec2::new(0.0, 49.0), 0.0),
+            ];
+            let config = PhysicsConfig::new();
+            let tree = QuadTree::new(&bodies, 1.0, config);
+            let exact = mutual_gravitation(&bodies, &config);
+            for (&(pos, mass), exact) in bodies.iter().zip(exact) {
+                let acc = tree.acceleration(pos, mass);
+                assert!(
+                    (acc - exact).len() <= 1e-4 * exact.len(),
+                    "{acc:?} at {pos:?}"
+                );
+            }
+        }
+
+        #[test]
+        fn coincident_bodies_share_leaf() {
+            let pos = Vec2::new(3.0, 4.0);
+            let tree = QuadTree::new(
+                &[(pos, 1.0), (pos, 2.0), (Vec2::new(-3.0, 4.0), 1.0)],
+                0.5,
+                PhysicsConfig::new(),
+            );
+            let acc = tree.acceleration(Vec2::zero(), 0.0);
+            let exact =
+                gravity(pos, 3.0, Vec2::zero()) + gravity(Vec2::new(-3.0, 4.0), 1.0, Vec2::zero());
+            assert!((acc - exact).len() < 1e-6);
+        }
+    }
+}
+
+mod integrate {
+
+    use super::{
+        gravity::{fixed_star_field, fixed_stars, star_field, Star},
+        PhysicsConfig,
+    };
+    use crate::{
+        entities::{
+            fixed::{Fixed, FixedVec2},
+            Real, Vec2,
+        },
+        repo_interfaces::{
+            FixedVec2Data, Marshalling, MissileId, MissileIdData, PlayerId, PlayerIdData, StarData,
+            Vec2Data,
+        },
+        Error, Result,
+    };
+    use std::{
+        fmt::Debug,
+        ops::{Add, Div, Mul, Sub},
+        rc::Rc,
+    };
+
+    type ObjInfo<T> = (T, Vec2, Vec2, Vec2);
+    type PlayerInfo = ObjInfo<PlayerId>;
+    type MissileInfo = ObjInfo<(PlayerId, MissileId)>;
+    type PlayerInfoData = (PlayerIdData, Vec2Data, Vec2Data, Vec2Data);
+    type MissileInfoData = (PlayerIdData, MissileIdData, Vec2Data, Vec2Data, Vec2Data);
+    type FixedMotionData = (FixedVec2Data, FixedVec2Data, FixedVec2Data);
+    type FixedPlayerInfoData = (PlayerIdData, FixedVec2Data, FixedVec2Data, FixedVec2Data);
+    type FixedMissileInfoData = (
+        PlayerIdData,
+        MissileIdData,
+        FixedVec2Data,
+        FixedVec2Data,
+        FixedVec2Data,
+    );
+
+    /// Numerical scheme used to integrate position and velocity in time
+    ///
+    /// The acceleration of an object is the sum of the gravitational acceleration of the stars
+    /// and all other accelerations, e.g. thrust. Schemes that need the acceleration at
+    /// intermediate positions re-evaluate the gravitational part and keep the rest constant
+    /// during a time step.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Integrator {
+        /// Explicit Euler method. Orbits gain energy and spiral outwards.
+        ExplicitEuler,
+        /// Semi-implicit (symplectic) Euler method. Updates velocity before position.
+        SemiImplicitEuler,
+        /// Leapfrog integration in velocity Verlet (kick-drift-kick) form
+        VelocityVerlet,
+        /// Classical fourth order Runge-Kutta method
+        RungeKutta4,
+    }
+    impl Integrator {
+        /// Advance position and velocity by one time step
+        ///
+        /// `acc` is the total acceleration at `pos` and `field` returns the gravitational
+        /// acceleration at an arbitrary position. Works with floating and fixed-point numbers.
+        fn step<V, S>(
+            self,
+            (pos, vel, acc): (V, V, V),
+            delta_time: S,
+            field: impl Fn(V) -> V,
+        ) -> (V, V)
+        where
+            V: Copy + Add<Output = V> + Sub<Output = V> + Mul<S, Output = V>,
+            S: Copy + From<Real> + Mul<Output = S> + Div<Output = S>,
+        {
+            let half_dt = S::from(0.5) * delta_time;
+            match self {
+                Integrator::ExplicitEuler => (pos + vel * delta_time, vel + acc * delta_time),
+                Integrator::SemiImplicitEuler => {
+                    let new_vel = vel + acc * delta_time;
+                    (pos + new_vel * delta_time, new_vel)
+                }
+                Integrator::VelocityVerlet => {
+                    let half_step_vel = vel + acc * half_dt;
+                    let new_pos = pos + half_step_vel * delta_time;
+                    let new_acc = acc + field(new_pos) - field(pos);
+                    (new_pos, half_step_vel + new_acc * half_dt)
+                }
+                Integrator::RungeKutta4 => {
+                    let field_at_pos = field(pos);
+                    let acc_at = |x: V| acc + field(x) - field_at_pos;
+                    let (k1_pos, k1_vel) = (vel, acc);
+                    let (k2_pos, k2_vel) = (vel + k1_vel * half_dt, acc_at(pos + k1_pos * half_dt));
+                    let (k3_pos, k3_vel) = (vel + k2_vel * half_dt, acc_at(pos + k2_pos * half_dt));
+                    let (k4_pos, k4_vel) =
+                        (vel + k3_vel * delta_time, acc_at(pos + k3_pos * delta_time));
+                    let (two, sixth_dt) = (S::from(2.0), delta_time / S::from(6.0));
+                    (
+                        pos + (k1_pos + k2_pos * two + k3_pos * two + k4_pos) * sixth_dt,
+                        vel + (k1_vel + k2_vel * two + k3_vel * two + k4_vel) * sixth_dt,
+                    )
+                }
+            }
+        }
+    }
+
+    /// Integrate use-case
+    ///
+    /// Integrates position and velocity of all objects. This will also set the acceleration to zero
+    /// and age all missiles by the time step. A time step is split into the configured number of
+    /// substeps, or into shorter substeps close to stars if adaptive substeps are configured. The
+    /// gravitational acceleration of the stars is re-evaluated for each substep, all other
+    /// accelerations are kept constant.
+    pub struct Integrate {
+        config: PhysicsConfig,
+        repo: Rc<dyn IntegrateDataGateway>,
+    }
+    impl Integrate {
+        /// Create a new integration use case using the numerical scheme of the configuration
+        pub fn new(config: PhysicsConfig, repo: Rc<dyn IntegrateDataGateway>) -> Self {
+            Self { config, repo }
+        }
+
+        /// Configuration of the use case
+        pub fn get_config(&self) -> PhysicsConfig {
+            self.config
+        }
+
+        /// Run the use case
+        ///
+        /// Fails without changing the objects of a kind if the new position or velocity of one
+        /// of them is not finite.
+        pub fn execute(&self, delta_time: impl Into<Real>) -> Result<()> {
+            let dt = delta_time.into();
+            let stars = self.get_stars()?;
+            if self.config.is_fixed_point() {
+                self.integrate_fixed(dt, &stars)?;
+            } else {
+                self.integrate_player(dt, &stars)?;
+                self.integrate_missiles(dt, &stars)?;
+            }
+            self.repo.age_missiles(dt)
+        }
+
+        /// Integrate position and velocity of all objects with fixed-point arithmetic
+        ///
+        /// Position, velocity and acceleration are exchanged with the data repository in
+        /// fixed-point representation, such that no rounding to floating point numbers takes
+        /// place.
+        fn integrate_fixed(&self, delta_time: Real, stars: &[Star]) -> Result<()> {
+            let stars = fixed_stars(&self.config, stars);
+            let advance = |(pos, vel, acc): FixedMotionData| -> FixedMotionData {
+                let motion = (pos.convert(), vel.convert(), acc.convert());
+                let (new_pos, new_vel) = advance_fixed(&self.config, &stars, motion, delta_time);
+                (
+                    new_pos.convert(),
+                    new_vel.convert(),
+                    FixedVec2::zero().convert(),
+                )
+            };
+            let players = self
+                .repo
+                .get_fixed_player_info()?
+                .into_iter()
+                .map(|(id, pos, vel, acc)| {
+                    let (pos, vel, acc) = advance((pos, vel, acc));
+                    (id, pos, vel, acc)
+                })
+                .collect();
+            self.repo.set_fixed_player_info(players)?;
+            let missiles = self
+                .repo
+                .get_fixed_missile_info()?
+                .into_iter()
+                .map(|(p_id, m_id, pos, vel, acc)| {
+                    let (pos, vel, acc) = advance((pos, vel, acc));
+                    (p_id, m_id, pos, vel, acc)
+                })
+                .collect();
+            self.repo.set_fixed_missile_info(missiles)
+        }
+
+        /// Get all attractants
+        fn get_stars(&self) -> Result<Vec<Star>> {
+            Ok(self.repo.get_star_info()?.convert())
+        }
+
+        /// Integrate position and velocity and set acceleration to zero
+        fn integrate_pos_vel_and_acc<T: Debug>(
+            &self,
+            (id, pos, vel, acc): (T, Vec2, Vec2, Vec2),
+            delta_time: Real,
+            stars: &[Star],
+            kind: &str,
+        ) -> Result<ObjInfo<T>> {
+            let (new_pos, new_vel) = advance(&self.config, stars, (pos, vel, acc), delta_time);
+            if !(new_pos.is_finite() && new_vel.is_finite()) {
+                return Err(Error::NonFinite(format!("motion of {kind} {id:?}")));
+            }
+            Ok((id, new_pos, new_vel, Vec2::zero()))
+        }
+
+        /// Integrate position and velocity of all player objects. Also set acceleration to zero.
+        fn integrate_player(&self, delta_time: Real, stars: &[Star]) -> Result<()> {
+            let updated_info = self
+                .get_player_info()?
+                .map(|data| self.integrate_pos_vel_and_acc(data, delta_time, stars, "player"))
+                .collect::<Result<Vec<_>>>()?;
+            self.set_player_info(updated_info.into_iter())
+        }
+
+        /// Iterator of position, velocity and acceleration of all player objects
+        fn get_player_info(&self) -> Result<impl Iterator<Item = PlayerInfo>> {
+            Ok(self
+                .repo
+                .get_player_info()?
+                .into_iter()
+                .map(|item| item.convert()))
+        }
+        /// Update position, velocity and acceleration for all player objects
+        fn set_player_info(&self, data: impl Iterator<Item = PlayerInfo>) -> Result<()> {
             self.repo
-                .get_missile_info()
+                .set_player_info(data.map(|item| item.convert()).collect())
+        }
+
+        /// Integrate position and velocity of all missile objects. Also set acceleration to zero.
+        fn integrate_missiles(&self, delta_time: Real, stars: &[Star]) -> Result<()> {
+            let updated_info = self
+                .get_missile_info()?
+                .map(|data| self.integrate_pos_vel_and_acc(data, delta_time, stars, "missile"))
+                .collect::<Result<Vec<_>>>()?;
+            self.set_missile_info(updated_info.into_iter())
+        }
+
+        /// Get position, velocity and acceleration of all missile objects
+        fn get_missile_info(&self) -> Result<impl Iterator<Item = MissileInfo>> {
+            Ok(self
+                .repo
+                .get_missile_info()?
                 .into_iter()
                 .map(|item| item.convert())
-                .map(|(p_id, m_id, pos, vel, acc)| ((p_id, m_id), pos, vel, acc))
+                .map(|(p_id, m_id, pos, vel, acc)| ((p_id, m_id), pos, vel, acc)))
         }
         /// Update position, velocity and acceleration for all missile objects
-        fn set_missile_info(&self, data: impl Iterator<Item = MissileInfo>) {
+        fn set_missile_info(&self, data: impl Iterator<Item = MissileInfo>) -> Result<()> {
             self.repo.set_missile_info(
                 data.map(|((p_id, m_id), pos, vel, acc)| (p_id, m_id, pos, vel, acc))
                     .map(|item| item.convert())
                     .collect(),
-            );
+            )
+        }
+    }
+
+    /// Advance position and velocity of an object by one time step
+    ///
+    /// `acc` is the total acceleration at `pos`. The time step is split into substeps and the
+    /// clamped gravitational acceleration of the stars is re-evaluated for each of them. Substeps
+    /// are of equal length, unless adaptive substeps shorten them close to stars.
+    pub(super) fn advance(
+        config: &PhysicsConfig,
+        stars: &[Star],
+        (pos, vel, acc): (Vec2, Vec2, Vec2),
+        delta_time: Real,
+    ) -> (Vec2, Vec2) {
+        if config.is_fixed_point() {
+            let stars = fixed_stars(config, stars);
+            let motion = (pos.into(), vel.into(), acc.into());
+            let (new_pos, new_vel) = advance_fixed(config, &stars, motion, delta_time);
+            return (new_pos.into(), new_vel.into());
+        }
+        let field = |x| config.limit_acceleration(star_field(config, stars, x));
+        let max_substeps = config.get_max_substeps();
+        let fixed_dt = delta_time / config.get_substeps() as Real;
+        let min_dt = delta_time / max_substeps as Real;
+        let (mut new_pos, mut new_vel) = (pos, vel);
+        let mut remaining = delta_time;
+        for substep in 0..max_substeps {
+            let sub_acc = if substep == 0 {
+                acc
+            } else {
+                acc + field(new_pos) - field(pos)
+            };
+            let sub_dt = match config.get_step_accuracy() {
+                Some(_) if substep + 1 == max_substeps => remaining,
+                Some(accuracy) => (accuracy * dynamical_time(config, stars, new_pos))
+                    .max(min_dt)
+                    .min(fixed_dt)
+                    .min(remaining),
+                None => fixed_dt,
+            };
+            (new_pos, new_vel) =
+                config
+                    .get_integrator()
+                    .step((new_pos, new_vel, sub_acc), sub_dt, field);
+            new_vel = config.limit_speed(new_vel);
+            remaining -= sub_dt;
+            if config.get_step_accuracy().is_some() && remaining <= 0.0 {
+                break;
+            }
+        }
+        (new_pos, new_vel)
+    }
+
+    /// Fixed-point variant of [`advance`] with substeps of equal length for stars given by
+    /// [`fixed_stars`]
+    fn advance_fixed(
+        config: &PhysicsConfig,
+        stars: &[(FixedVec2, Fixed)],
+        (pos, vel, acc): (FixedVec2, FixedVec2, FixedVec2),
+        delta_time: Real,
+    ) -> (FixedVec2, FixedVec2) {
+        let field = |x| config.fixed_limit_acceleration(fixed_star_field(config, stars, x));
+        let substeps = config.get_substeps();
+        let sub_dt = Fixed::from(delta_time) / Fixed::from(substeps as Real);
+        let (mut new_pos, mut new_vel) = (pos, vel);
+        for substep in 0..substeps {
+            let sub_acc = if substep == 0 {
+                acc
+            } else {
+                acc + field(new_pos) - field(pos)
+            };
+            (new_pos, new_vel) =
+                config
+                    .get_integrator()
+                    .step((new_pos, new_vel, sub_acc), sub_dt, field);
+            new_vel = config.fixed_limit_speed(new_vel);
+        }
+        (new_pos, new_vel)
+    }
+
+    /// Local dynamical time `sqrt(d / a)` at `pos`, with the distance `d` to the nearest star and
+    /// the gravitational acceleration `a`. Infinite without acceleration.
+    fn dynamical_time(config: &PhysicsConfig, stars: &[Star], pos: Vec2) -> Real {
+        let acc = config
+            .limit_acceleration(star_field(config, stars, pos))
+            .len();
+        if acc == 0.0 {
+            return Real::INFINITY;
         }
+        let dist = stars
+            .iter()
+            .map(|star| config.separation(star.pos, pos).len())
+            .fold(Real::INFINITY, Real::min);
+        (dist / acc).sqrt()
     }
 
     pub trait IntegrateDataGateway {
+        /// Get all [`Star`] objects
+        fn get_star_info(&self) -> Result<Vec<StarData>>;
         /// Return `(id, position, velocity, acceleration)` for all player
-        fn get_player_info(&self) -> Vec<PlayerInfoData>;
+        fn get_player_info(&self) -> Result<Vec<PlayerInfoData>>;
         /// Return `(player_id, missile_id, position, velocity, acceleration)` for all missiles
-        fn get_missile_info(&self) -> Vec<MissileInfoData>;
+        fn get_missile_info(&self) -> Result<Vec<MissileInfoData>>;
         /// Update position, velocity and acceleration for all player
-        fn set_player_info(&self, data: Vec<PlayerInfoData>);
+        fn set_player_info(&self, data: Vec<PlayerInfoData>) -> Result<()>;
         /// Update position, velocity and acceleration for all missiles
-        fn set_missile_info(&self, data: Vec<MissileInfoData>);
+        fn set_missile_info(&self, data: Vec<MissileInfoData>) -> Result<()>;
+        /// Fixed-point variant of [`Self::get_player_info`]
+        ///
+        /// Rounds the floating point values by default, which loses the exact state between
+        /// time steps unless overridden.
+        fn get_fixed_player_info(&self) -> Result<Vec<FixedPlayerInfoData>> {
+            Ok(self.get_player_info()?.convert())
+        }
+        /// Fixed-point variant of [`Self::get_missile_info`]
+        fn get_fixed_missile_info(&self) -> Result<Vec<FixedMissileInfoData>> {
+            Ok(self.get_missile_info()?.convert())
+        }
+        /// Fixed-point variant of [`Self::set_player_info`]
+        fn set_fixed_player_info(&self, data: Vec<FixedPlayerInfoData>) -> Result<()> {
+            self.set_player_info(data.convert())
+        }
+        /// Fixed-point variant of [`Self::set_missile_info`]
+        fn set_fixed_missile_info(&self, data: Vec<FixedMissileInfoData>) -> Result<()> {
+            self.set_missile_info(data.convert())
+        }
+        /// Increase the time since launch of all missiles by `delta_time`
+        fn age_missiles(&self, delta_time: Real) -> Result<()>;
     }
 
     #[cfg(test)]
     mod test_integrate {
         use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-        use crate::repo_interfaces::{MissileIdData, PlayerIdData, Vec2Data};
+        use crate::{
+            entities::{gravity, Real, Vec2},
+            repo_interfaces::{Marshalling, MissileIdData, PlayerIdData, StarData, Vec2Data},
+            Error, Result,
+        };
 
-        use super::Integrate;
+        use super::{
+            advance, dynamical_time, star_field, Integrate, Integrator, PhysicsConfig, Star,
+        };
 
         #[derive(Default)]
         struct MockData {
+            stars: Vec<StarData>,
             player_info: HashMap<PlayerIdData, (Vec2Data, Vec2Data, Vec2Data)>,
             missile_info: HashMap<(PlayerIdData, MissileIdData), (Vec2Data, Vec2Data, Vec2Data)>,
+            missile_age: Real,
         }
 
         struct MockDataGateway {
             data: MockData,
         }
         impl super::IntegrateDataGateway for RefCell<MockDataGateway> {
-            fn get_player_info(&self) -> Vec<super::PlayerInfoData> {
-                self.borrow()
+            fn get_star_info(&self) -> Result<Vec<StarData>> {
+                Ok(self.borrow().data.stars.clone())
+            }
+
+            fn get_player_info(&self) -> Result<Vec<super::PlayerInfoData>> {
+                Ok(self
+                    .borrow()
                     .data
                     .player_info
                     .iter()
                     .map(|(&id, &data)| (id, data.0, data.1, data.2))
-                    .collect()
+                    .collect())
             }
 
-            fn set_player_info(&self, data: Vec<super::PlayerInfoData>) {
+            fn set_player_info(&self, data: Vec<super::PlayerInfoData>) -> Result<()> {
                 let mut repo = self.borrow_mut();
-                data.iter().for_each(|&(id, new_pos, new_vel, new_acc)| {
-                    repo.data.player_info.entry(id).and_modify(|value| {
+                data.iter()
+                    .try_for_each(|&(id, new_pos, new_vel, new_acc)| {
+                        let value = repo
+                            .data
+                            .player_info
+                            .get_mut(&id)
+                            .ok_or(Error::UnknownPlayer(id))?;
                         *value = (new_pos, new_vel, new_acc);
-                    });
-                });
+                        Ok(())
+                    })
             }
 
-            fn get_missile_info(&self) -> Vec<super::MissileInfoData> {
-                self.borrow()
+            fn get_missile_info(&self) -> Result<Vec<super::MissileInfoData>> {
+                Ok(self
+                    .borrow()
                     .data
                     .missile_info
                     .iter()
                     .map(|(&id, &data)| (id.0, id.1, data.0, data.1, data.2))
-                    .collect()
+                    .collect())
             }
 
-            fn set_missile_info(&self, data: Vec<super::MissileInfoData>) {
+            fn set_missile_info(&self, data: Vec<super::MissileInfoData>) -> Result<()> {
                 let mut repo = self.borrow_mut();
                 data.iter()
-                    .for_each(|&(pid, mid, new_pos, new_vel, new_acc)| {
-                        repo.data
+                    .try_for_each(|&(pid, mid, new_pos, new_vel, new_acc)| {
+                        let value = repo
+                            .data
                             .missile_info
-                            .entry((pid, mid))
-                            .and_modify(|value| *value = (new_pos, new_vel, new_acc));
-                    });
+                            .get_mut(&(pid, mid))
+                            .ok_or(Error::UnknownMissile(pid, mid))?;
+                        *value = (new_pos, new_vel, new_acc);
+                        Ok(())
+                    })
+            }
+
+            fn age_missiles(&self, delta_time: Real) -> Result<()> {
+                self.borrow_mut().data.missile_age += delta_time;
+                Ok(())
             }
         }
 
@@ -461,8 +1898,11 @@ mod integrate {
         #[test]
         fn integrate_works_if_no_players_are_present() {
             let gateway = setup_integrate_test(MockData::default());
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(1.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(1.0).unwrap();
             assert!(gateway.borrow().data.player_info.is_empty())
         }
 
@@ -477,8 +1917,11 @@ mod integrate {
                 ..MockData::default()
             };
             let gateway = setup_integrate_test(data);
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(2.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(2.0).unwrap();
             let repo = gateway.borrow();
             assert_eq!(repo.data.player_info.clone().get(&0).unwrap().0, [3.0, 2.0]);
             assert_eq!(repo.data.player_info.clone().get(&1).unwrap().0, [1.0, 2.0]);
@@ -495,8 +1938,11 @@ mod integrate {
                 ..MockData::default()
             };
             let gateway = setup_integrate_test(data);
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(2.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(2.0).unwrap();
             let repo = gateway.borrow();
             assert_eq!(repo.data.player_info.clone().get(&0).unwrap().1, [3.0, 1.0]);
             assert_eq!(repo.data.player_info.clone().get(&1).unwrap().1, [2.0, 3.0]);
@@ -513,18 +1959,180 @@ mod integrate {
                 ..MockData::default()
             };
             let gateway = setup_integrate_test(data);
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(2.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(2.0).unwrap();
             let repo = gateway.borrow();
             assert_eq!(repo.data.player_info.clone().get(&0).unwrap().2, [0.0, 0.0]);
             assert_eq!(repo.data.player_info.clone().get(&1).unwrap().2, [0.0, 0.0]);
         }
 
+        #[test]
+        fn integrate_limits_speed() {
+            let data = MockData {
+                player_info: [(0, ([0.0, 0.0], [3.0, 0.0], [0.0, 4.0]))].into(),
+                ..MockData::default()
+            };
+            let gateway = setup_integrate_test(data);
+            let config = PhysicsConfig::new().with_max_speed(2.5);
+            Integrate::new(config, gateway.clone())
+                .execute(1.0)
+                .unwrap();
+            let repo = gateway.borrow();
+            assert_eq!(repo.data.player_info.get(&0).unwrap().1, [1.5, 2.0]);
+        }
+
+        #[test]
+        fn substeps_follow_gravitational_field() {
+            let star = StarData::new([0.0, 0.0], 1000.0, 1.0);
+            let field = |pos: Vec2Data| gravity(Vec2::zero(), 1000.0, pos.convert());
+            let start = ([100.0, 0.0], [0.0, Real::sqrt(1000.0 / 100.0)]);
+            let radius_after = |substeps| {
+                let data = MockData {
+                    stars: vec![star],
+                    player_info: [(0, (start.0, start.1, field(start.0).convert()))].into(),
+                    ..MockData::default()
+                };
+                let gateway = setup_integrate_test(data);
+                let config = PhysicsConfig::new().with_substeps(substeps);
+                let integrate = Integrate::new(config, gateway.clone());
+                for _ in 0..50 {
+                    let (pos, vel, _) = *gateway.borrow().data.player_info.get(&0).unwrap();
+                    gateway
+                        .borrow_mut()
+                        .data
+                        .player_info
+                        .insert(0, (pos, vel, field(pos).convert()));
+                    integrate.execute(10.0).unwrap();
+                }
+                let pos: Vec2 = gateway
+                    .borrow()
+                    .data
+                    .player_info
+                    .get(&0)
+                    .unwrap()
+                    .0
+                    .convert();
+                pos.len()
+            };
+            // A circular orbit keeps its radius better with more substeps
+            let error = |substeps| (radius_after(substeps) - 100.0).abs();
+            assert!(error(8) < error(1));
+        }
+
+        #[test]
+        fn adaptive_substeps_bounded() {
+            let stars: Vec<Star> = vec![StarData::new([0.0, 0.0], 1000.0, 1.0)].convert();
+            let config = PhysicsConfig::new();
+            assert_eq!(dynamical_time(&config, &stars, Vec2::new(10.0, 0.0)), 1.0);
+            assert_eq!(dynamical_time(&config, &[], Vec2::zero()), Real::INFINITY);
+
+            // Close to the star, the upper limit of substeps determines the motion
+            let start = (
+                Vec2::new(1.0, 0.0),
+                Vec2::new(0.0, 10.0),
+                Vec2::new(-1000.0, 0.0),
+            );
+            let single = advance(&config, &stars, start, 0.1);
+            let fine = config.with_substeps(16);
+            assert_eq!(
+                advance(&config.with_adaptive_substeps(0.1, 1), &stars, start, 0.1),
+                single
+            );
+            let (pos, vel) = advance(&config.with_adaptive_substeps(0.01, 16), &stars, start, 0.1);
+            let (fine_pos, fine_vel) = advance(&fine, &stars, start, 0.1);
+            assert!((pos - fine_pos).len() < 1e-5 && (vel - fine_vel).len() < 1e-4);
+            // Far away, substeps are not shortened
+            let far = (
+                Vec2::new(1000.0, 0.0),
+                Vec2::new(0.0, 1.0),
+                Vec2::new(-1e-3, 0.0),
+            );
+            assert_eq!(
+                advance(&config.with_adaptive_substeps(0.1, 16), &stars, far, 0.1),
+                advance(&config, &stars, far, 0.1)
+            );
+        }
+
+        #[test]
+        fn adaptive_substeps_resolve_close_pass() {
+            let stars: Vec<Star> = vec![StarData::new([0.0, 0.0], 1000.0, 1.0)].convert();
+            let energy = |(pos, vel): (Vec2, Vec2)| 0.5 * vel.len2() - 1000.0 / pos.len();
+            // Eccentric orbit from apoapsis at 100 to periapsis at about 5 and back
+            let start = (Vec2::new(100.0, 0.0), Vec2::new(0.0, 1.0));
+            let energy_error = |config: PhysicsConfig| {
+                let mut state = start;
+                for _ in 0..76 {
+                    let acc = star_field(&config, &stars, state.0);
+                    state = advance(&config, &stars, (state.0, state.1, acc), 1.0);
+                }
+                ((energy(state) - energy(start)) / energy(start)).abs()
+            };
+            let fixed = PhysicsConfig::new()
+                .with_integrator(Integrator::VelocityVerlet)
+                .with_substeps(4);
+            let adaptive = fixed.with_adaptive_substeps(0.05, 256);
+            assert!(energy_error(adaptive) < 0.01);
+            assert!(energy_error(adaptive) < energy_error(fixed));
+        }
+
+        #[test]
+        fn max_acceleration_bounds_close_pass() {
+            let stars: Vec<Star> = vec![StarData::new([0.0, 0.0], 1000.0, 1.0)].convert();
+            // Without softening, the first substep ends 1e-3 away from the star
+            let start = (Vec2::new(-1.5, 1e-3), Vec2::new(1.0, 0.0));
+            for fixed_point in [false, true] {
+                for substeps in [1, 3] {
+                    let mut config = PhysicsConfig::new()
+                        .with_integrator(Integrator::VelocityVerlet)
+                        .with_softening(0.0)
+                        .with_max_acceleration(1.0)
+                        .with_substeps(substeps);
+                    if fixed_point {
+                        config = config.with_fixed_point();
+                    }
+                    let acc = config.limit_acceleration(star_field(&config, &stars, start.0));
+                    let (pos, vel) = advance(&config, &stars, (start.0, start.1, acc), 1.0);
+                    assert!(pos.is_finite() && vel.is_finite());
+                    // Sum of three clamped fields, instead of about 1e9 without clamping
+                    assert!((vel - start.1).len() <= 3.0 + 1e-3);
+                }
+            }
+        }
+
+        #[test]
+        fn integrate_rejects_non_finite_motion() {
+            let data = MockData {
+                player_info: [
+                    (0, ([0.0, 0.0], [1.0, 1.0], [0.0, 0.0])),
+                    (1, ([0.0, 0.0], [0.0, 1.0], [Real::INFINITY, 0.0])),
+                ]
+                .into(),
+                ..MockData::default()
+            };
+            let gateway = setup_integrate_test(data);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            assert_eq!(
+                integrate.execute(1.0),
+                Err(Error::NonFinite("motion of player 1".into()))
+            );
+            let repo = gateway.borrow();
+            assert_eq!(repo.data.player_info.get(&0).unwrap().0, [0.0, 0.0]);
+        }
+
         #[test]
         fn integrate_works_if_no_missiles_are_present() {
             let gateway = setup_integrate_test(MockData::default());
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(1.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(1.0).unwrap();
             assert!(gateway.borrow().data.missile_info.is_empty())
         }
 
@@ -540,8 +2148,11 @@ mod integrate {
                 ..MockData::default()
             };
             let gateway = setup_integrate_test(data);
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(2.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(2.0).unwrap();
             let repo = gateway.borrow();
             assert_eq!(
                 repo.data.missile_info.clone().get(&(0, 0)).unwrap().0,
@@ -557,6 +2168,15 @@ mod integrate {
             );
         }
 
+        #[test]
+        fn integrate_ages_missiles() {
+            let gateway = setup_integrate_test(MockData::default());
+            let integrate = Integrate::new(PhysicsConfig::new(), gateway.clone());
+            integrate.execute(0.5).unwrap();
+            integrate.execute(0.25).unwrap();
+            assert_eq!(gateway.borrow().data.missile_age, 0.75);
+        }
+
         #[test]
         fn integrate_updates_missile_vel_correctly() {
             let data = MockData {
@@ -569,8 +2189,11 @@ mod integrate {
                 ..MockData::default()
             };
             let gateway = setup_integrate_test(data);
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(2.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(2.0).unwrap();
             let repo = gateway.borrow();
             assert_eq!(
                 repo.data.missile_info.clone().get(&(0, 0)).unwrap().1,
@@ -598,8 +2221,11 @@ mod integrate {
                 ..MockData::default()
             };
             let gateway = setup_integrate_test(data);
-            let integrate = Integrate::new(gateway.clone());
-            integrate.execute(2.0);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::ExplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(2.0).unwrap();
             let repo = gateway.borrow();
             assert_eq!(
                 repo.data.missile_info.clone().get(&(0, 0)).unwrap().2,
@@ -614,5 +2240,1731 @@ mod integrate {
                 [0.0; 2]
             );
         }
+
+        #[test]
+        fn semi_implicit_euler_updates_position_with_new_velocity() {
+            let data = MockData {
+                player_info: [(0, ([0.0, 0.0], [1.0, 1.0], [1.0, 0.0]))].into(),
+                ..MockData::default()
+            };
+            let gateway = setup_integrate_test(data);
+            let integrate = Integrate::new(
+                PhysicsConfig::new().with_integrator(Integrator::SemiImplicitEuler),
+                gateway.clone(),
+            );
+            integrate.execute(2.0).unwrap();
+            let repo = gateway.borrow();
+            let (pos, vel, acc) = *repo.data.player_info.get(&0).unwrap();
+            assert_eq!((pos, vel, acc), ([6.0, 2.0], [3.0, 1.0], [0.0, 0.0]));
+        }
+
+        /// Relative change of the specific orbital energy after integrating a circular orbit
+        /// around a single star for 100 revolutions
+        fn energy_drift_of_circular_orbit(integrator: Integrator) -> Real {
+            let (star_pos, star_mass, radius) = (Vec2::zero(), 1000.0, 100.0);
+            let field = |x: Vec2| gravity(star_pos, star_mass, x);
+            let energy = |pos: Vec2, vel: Vec2| 0.5 * vel.len2() - star_mass / pos.len();
+
+            let mut pos = Vec2::new(radius, 0.0);
+            let mut vel = Vec2::new(0.0, (star_mass / radius).sqrt());
+            let initial_energy = energy(pos, vel);
+            let mut max_drift: Real = 0.0;
+            for _ in 0..20_000 {
+                (pos, vel) = integrator.step((pos, vel, field(pos)), 1.0, field);
+                max_drift =
+                    max_drift.max(((energy(pos, vel) - initial_energy) / initial_energy).abs());
+            }
+            max_drift
+        }
+
+        #[test]
+        fn explicit_euler_does_not_conserve_orbital_energy() {
+            assert!(energy_drift_of_circular_orbit(Integrator::ExplicitEuler) > 0.5);
+        }
+
+        #[test]
+        fn semi_implicit_euler_keeps_orbital_energy_bounded() {
+            assert!(energy_drift_of_circular_orbit(Integrator::SemiImplicitEuler) < 0.01);
+        }
+
+        #[test]
+        fn velocity_verlet_keeps_orbital_energy_bounded() {
+            assert!(energy_drift_of_circular_orbit(Integrator::VelocityVerlet) < 0.001);
+        }
+
+        #[test]
+        fn runge_kutta_keeps_orbital_energy_bounded() {
+            assert!(energy_drift_of_circular_orbit(Integrator::RungeKutta4) < 0.001);
+        }
+    }
+}
+
+mod collision {
+    use super::{gravity::Star, Arena, PhysicsConfig};
+    use crate::{
+        entities::{Real, Vec2},
+        repo_interfaces::{
+            HazardId, Marshalling, MissileId, MissileIdData, PlayerId, PlayerIdData, StarData,
+            StarId, Vec2Data,
+        },
+        Result,
+    };
+    use std::rc::Rc;
+
+    /// Configuration object for collision detection
+    #[derive(Clone, Copy, Debug)]
+    pub struct CollisionConfig {
+        /// Radius of the hit circle of player objects
+        player_radius: Real,
+        /// Radius of the hit circle of missile objects
+        missile_radius: Real,
+        /// Bounds of the arena, unbounded if `None`
+        arena: Option<Arena>,
+        /// Time after launch during which a missile can't hit its owner
+        owner_grace: Real,
+    }
+
+    impl CollisionConfig {
+        /// Create a new configuration object for collision detection
+        ///
+        /// Without a grace period, missiles launched closer than `player_radius + missile_radius`
+        /// hit their owner right away.
+        pub fn new(player_radius: impl Into<Real>, missile_radius: impl Into<Real>) -> Self {
+            CollisionConfig {
+                player_radius: player_radius.into(),
+                missile_radius: missile_radius.into(),
+                arena: None,
+                owner_grace: 0.0,
+            }
+        }
+
+        /// Let missiles pass their owner during the first `owner_grace` time units after launch
+        pub fn with_owner_grace(mut self, owner_grace: impl Into<Real>) -> Self {
+            self.owner_grace = owner_grace.into();
+            self
+        }
+
+        /// Measure distances within the arena of the physics configuration, if any. In wrap
+        /// mode, objects hit the nearest periodic image of each other.
+        pub fn with_arena_of(mut self, physics: &PhysicsConfig) -> Self {
+            self.arena = physics.get_arena();
+            self
+        }
+
+        /// Radius of the hit circle of player objects
+        pub fn get_player_radius(&self) -> Real {
+            self.player_radius
+        }
+
+        /// Radius of the hit circle of missile objects
+        pub fn get_missile_radius(&self) -> Real {
+            self.missile_radius
+        }
+
+        /// Bounds of the arena, if any
+        pub fn get_arena(&self) -> Option<Arena> {
+            self.arena
+        }
+
+        /// Time after launch during which a missile can't hit its owner
+        pub fn get_owner_grace(&self) -> Real {
+            self.owner_grace
+        }
+    }
+
+    /// Hit event reported by the collision use-case
+    ///
+    /// Stars and hazards are identified by their index in the lists returned by
+    /// [`CollisionDataGateway::get_stars_position_and_radius`] and
+    /// [`CollisionDataGateway::get_hazards`].
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum CollisionEvent {
+        /// A missile hit a player object
+        MissileHitPlayer {
+            missile: (PlayerId, MissileId),
+            player: PlayerId,
+        },
+        /// A player object hit a star
+        PlayerHitStar { player: PlayerId, star: StarId },
+        /// A missile hit a star
+        MissileHitStar {
+            missile: (PlayerId, MissileId),
+            star: StarId,
+        },
+        /// Two player objects hit each other. The smaller player id comes first.
+        PlayerHitPlayer { players: (PlayerId, PlayerId) },
+        /// A player object is within a hazard
+        PlayerHitHazard { player: PlayerId, hazard: HazardId },
+    }
+
+    /// Missile id folded with the id of its owner
+    type MissileKey = (PlayerId, MissileId);
+
+    /// Position and radius of a collidable object
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Body<T> {
+        id: T,
+        pos: Vec2,
+        radius: Real,
+    }
+    impl<T> Body<T> {
+        /// Check if the hit circles of two bodies overlap within `arena`
+        fn hits<S>(&self, other: &Body<S>, arena: Option<&Arena>) -> bool {
+            let min_distance = self.radius + other.radius;
+            let separation = match arena {
+                Some(arena) => arena.separation(self.pos, other.pos),
+                None => other.pos - self.pos,
+            };
+            separation.len2() <= min_distance * min_distance
+        }
+    }
+
+    /// Collision use-case
+    ///
+    /// This use case detects overlapping player, missile, star and hazard objects and reports them
+    /// as [`CollisionEvent`]s. It does not alter the game state.
+    pub struct Collision {
+        config: CollisionConfig,
+        repo: CollisionDG,
+    }
+    impl Collision {
+        /// Create use case object
+        pub fn new(config: CollisionConfig, repo: CollisionDG) -> Self {
+            Self { config, repo }
+        }
+
+        /// Return all hit events of the current game state
+        pub fn execute(&self) -> Result<Vec<CollisionEvent>> {
+            let stars = self.get_stars()?;
+            let player = self.get_player()?;
+            let (missiles, launching) = self.get_missiles()?;
+            let hazards = self.get_hazards()?;
+
+            let arena = self.config.arena.as_ref();
+            let mut events = Vec::new();
+            events.extend(Self::missiles_hitting_player(
+                &missiles, &launching, &player, arena,
+            ));
+            events.extend(Self::player_hitting_stars(&player, &stars, arena));
+            events.extend(Self::missiles_hitting_stars(&missiles, &stars, arena));
+            events.extend(Self::player_hitting_player(&player, arena));
+            events.extend(Self::player_hitting_hazards(&player, &hazards, arena));
+            Ok(events)
+        }
+
+        /// Get all stars
+        fn get_stars(&self) -> Result<Vec<Body<StarId>>> {
+            let stars: Vec<Star> = self.repo.get_stars_position_and_radius()?.convert();
+            Ok(stars
+                .into_iter()
+                .enumerate()
+                .map(|(id, star)| Body {
+                    id,
+                    pos: star.pos,
+                    radius: star.radius,
+                })
+                .collect())
+        }
+
+        /// Get all player objects
+        fn get_player(&self) -> Result<Vec<Body<PlayerId>>> {
+            Ok(self
+                .repo
+                .get_player_positions()?
+                .into_iter()
+                .map(|(id, pos)| Body {
+                    id: id.convert(),
+                    pos: pos.convert(),
+                    radius: self.config.player_radius,
+                })
+                .collect())
+        }
+
+        /// Get all hazards
+        fn get_hazards(&self) -> Result<Vec<Body<HazardId>>> {
+            Ok(self
+                .repo
+                .get_hazards()?
+                .into_iter()
+                .enumerate()
+                .map(|(id, (pos, radius))| Body {
+                    id,
+                    pos: pos.convert(),
+                    radius,
+                })
+                .collect())
+        }
+
+        /// Get all missile objects and the IDs of missiles within the owner grace period. IDs are
+        /// folded into single composit key.
+        fn get_missiles(&self) -> Result<(Vec<Body<MissileKey>>, Vec<MissileKey>)> {
+            let mut launching = Vec::new();
+            let missiles = self
+                .repo
+                .get_missile_positions_and_ages()?
+                .into_iter()
+                .map(|(p_id, m_id, pos, age)| {
+                    let id = (p_id, m_id).convert();
+                    if age < self.config.owner_grace {
+                        launching.push(id);
+                    }
+                    Body {
+                        id,
+                        pos: pos.convert(),
+                        radius: self.config.missile_radius,
+                    }
+                })
+                .collect();
+            Ok((missiles, launching))
+        }
+
+        /// Missiles in `launching` don't hit their owner
+        fn missiles_hitting_player<'a>(
+            missiles: &'a [Body<(PlayerId, MissileId)>],
+            launching: &'a [(PlayerId, MissileId)],
+            player: &'a [Body<PlayerId>],
+            arena: Option<&'a Arena>,
+        ) -> impl Iterator<Item = CollisionEvent> + 'a {
+            missiles.iter().flat_map(move |missile| {
+                let spares_owner = launching.contains(&missile.id);
+                player
+                    .iter()
+                    .filter(move |player| !(spares_owner && player.id == missile.id.0))
+                    .filter(move |player| missile.hits(*player, arena))
+                    .map(|player| CollisionEvent::MissileHitPlayer {
+                        missile: missile.id,
+                        player: player.id,
+                    })
+            })
+        }
+
+        fn player_hitting_stars<'a>(
+            player: &'a [Body<PlayerId>],
+            stars: &'a [Body<StarId>],
+            arena: Option<&'a Arena>,
+        ) -> impl Iterator<Item = CollisionEvent> + 'a {
+            player.iter().flat_map(move |player| {
+                stars
+                    .iter()
+                    .filter(move |star| player.hits(*star, arena))
+                    .map(|star| CollisionEvent::PlayerHitStar {
+                        player: player.id,
+                        star: star.id,
+                    })
+            })
+        }
+
+        fn missiles_hitting_stars<'a>(
+            missiles: &'a [Body<(PlayerId, MissileId)>],
+            stars: &'a [Body<StarId>],
+            arena: Option<&'a Arena>,
+        ) -> impl Iterator<Item = CollisionEvent> + 'a {
+            missiles.iter().flat_map(move |missile| {
+                stars
+                    .iter()
+                    .filter(move |star| missile.hits(*star, arena))
+                    .map(|star| CollisionEvent::MissileHitStar {
+                        missile: missile.id,
+                        star: star.id,
+                    })
+            })
+        }
+
+        /// Every pair of player objects is only checked and reported once
+        fn player_hitting_player<'a>(
+            player: &'a [Body<PlayerId>],
+            arena: Option<&'a Arena>,
+        ) -> impl Iterator<Item = CollisionEvent> + 'a {
+            player.iter().enumerate().flat_map(move |(index, first)| {
+                player[index + 1..]
+                    .iter()
+                    .filter(move |second| first.hits(*second, arena))
+                    .map(|second| CollisionEvent::PlayerHitPlayer {
+                        players: (first.id.min(second.id), first.id.max(second.id)),
+                    })
+            })
+        }
+
+        fn player_hitting_hazards<'a>(
+            player: &'a [Body<PlayerId>],
+            hazards: &'a [Body<HazardId>],
+            arena: Option<&'a Arena>,
+        ) -> impl Iterator<Item = CollisionEvent> + 'a {
+            player.iter().flat_map(move |player| {
+                hazards
+                    .iter()
+                    .filter(move |hazard| player.hits(*hazard, arena))
+                    .map(|hazard| CollisionEvent::PlayerHitHazard {
+                        player: player.id,
+                        hazard: hazard.id,
+                    })
+            })
+        }
+    }
+
+    /// Data repository interface for collision use case.
+    pub trait CollisionDataGateway {
+        /// Get all [`Star`] objects
+        fn get_stars_position_and_radius(&self) -> Result<Vec<StarData>>;
+        /// Get position of all player objects
+        fn get_player_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>>;
+        /// Get `(owner id, missile id, position, age)` of all missile objects
+        fn get_missile_positions_and_ages(
+            &self,
+        ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Real)>>;
+        /// Get `(position, radius)` of all hazards
+        fn get_hazards(&self) -> Result<Vec<(Vec2Data, Real)>>;
+    }
+    type CollisionDG = Rc<dyn CollisionDataGateway>;
+
+    #[cfg(test)]
+    mod test_collision {
+        use super::{
+            Arena, Collision, CollisionConfig, CollisionDataGateway, CollisionEvent, MissileIdData,
+            PhysicsConfig, PlayerIdData, Real, StarData, Vec2Data,
+        };
+        use crate::{physics::BoundaryMode, Result};
+        use std::{cell::RefCell, rc::Rc};
+
+        #[derive(Default)]
+        struct MockData {
+            stars: Vec<StarData>,
+            player: Vec<(PlayerIdData, Vec2Data)>,
+            missiles: Vec<(PlayerIdData, MissileIdData, Vec2Data, Real)>,
+            hazards: Vec<(Vec2Data, Real)>,
+        }
+
+        struct MockDataGateway {
+            data: MockData,
+        }
+        impl CollisionDataGateway for RefCell<MockDataGateway> {
+            fn get_stars_position_and_radius(&self) -> Result<Vec<StarData>> {
+                Ok(self.borrow().data.stars.clone())
+            }
+
+            fn get_player_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+                Ok(self.borrow().data.player.clone())
+            }
+
+            fn get_missile_positions_and_ages(
+                &self,
+            ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Real)>> {
+                Ok(self.borrow().data.missiles.clone())
+            }
+
+            fn get_hazards(&self) -> Result<Vec<(Vec2Data, Real)>> {
+                Ok(self.borrow().data.hazards.clone())
+            }
+        }
+
+        fn setup_collision_test(data: MockData) -> Collision {
+            let config = CollisionConfig::new(1.0, 0.5);
+            let repo = Rc::new(RefCell::new(MockDataGateway { data }));
+            Collision::new(config, repo)
+        }
+
+        #[test]
+        fn collisions_detected_across_wrapped_bounds() {
+            let data = || MockData {
+                stars: vec![StarData::new([-9.5, 0.0], 1.0, 1.0)],
+                player: vec![(1, [9.5, 0.0]), (2, [0.0, 9.8])],
+                missiles: vec![(1, 0, [0.0, -9.8], 0.0)],
+                ..MockData::default()
+            };
+            let collision = |mode| {
+                let arena = Arena::new([-10.0, -10.0], [10.0, 10.0], mode).unwrap();
+                let physics = PhysicsConfig::new().with_arena(arena);
+                let config = CollisionConfig::new(1.0, 0.5).with_arena_of(&physics);
+                let repo = Rc::new(RefCell::new(MockDataGateway { data: data() }));
+                Collision::new(config, repo).execute().unwrap()
+            };
+            assert_eq!(
+                collision(BoundaryMode::Wrap),
+                vec![
+                    CollisionEvent::MissileHitPlayer {
+                        missile: (1, 0),
+                        player: 2
+                    },
+                    CollisionEvent::PlayerHitStar { player: 1, star: 0 }
+                ]
+            );
+            assert!(collision(BoundaryMode::Reflect).is_empty());
+        }
+
+        #[test]
+        fn collision_not_failing_when_repo_is_empty() {
+            let collision = setup_collision_test(MockData::default());
+            assert!(collision.execute().unwrap().is_empty());
+        }
+
+        #[test]
+        fn missile_hits_player() {
+            let collision = setup_collision_test(MockData {
+                player: vec![(1, [0.0, 0.0]), (2, [10.0, 0.0])],
+                missiles: vec![(2, 0, [1.2, 0.0], 0.0), (2, 1, [5.0, 0.0], 0.0)],
+                ..MockData::default()
+            });
+            assert_eq!(
+                collision.execute().unwrap(),
+                vec![CollisionEvent::MissileHitPlayer {
+                    missile: (2, 0),
+                    player: 1
+                }]
+            );
+        }
+
+        #[test]
+        fn launching_missile_spares_owner() {
+            let data = MockData {
+                player: vec![(1, [0.0, 0.0]), (2, [0.0, 1.5])],
+                missiles: vec![(1, 0, [0.0, 1.0], 0.05), (1, 1, [0.0, -1.0], 0.5)],
+                ..MockData::default()
+            };
+            let config = CollisionConfig::new(1.0, 0.5).with_owner_grace(0.1);
+            let repo = Rc::new(RefCell::new(MockDataGateway { data }));
+            assert_eq!(
+                Collision::new(config, repo).execute().unwrap(),
+                vec![
+                    CollisionEvent::MissileHitPlayer {
+                        missile: (1, 0),
+                        player: 2
+                    },
+                    CollisionEvent::MissileHitPlayer {
+                        missile: (1, 1),
+                        player: 1
+                    },
+                    CollisionEvent::PlayerHitPlayer { players: (1, 2) }
+                ]
+            );
+        }
+
+        #[test]
+        fn player_hits_star() {
+            let collision = setup_collision_test(MockData {
+                stars: vec![
+                    StarData::new([0.0, 0.0], 1.0, 2.0),
+                    StarData::new([20.0, 0.0], 1.0, 2.0),
+                ],
+                player: vec![(1, [17.5, 0.0]), (2, [10.0, 0.0])],
+                ..MockData::default()
+            });
+            assert_eq!(
+                collision.execute().unwrap(),
+                vec![CollisionEvent::PlayerHitStar { player: 1, star: 1 }]
+            );
+        }
+
+        #[test]
+        fn missile_hits_star() {
+            let collision = setup_collision_test(MockData {
+                stars: vec![StarData::new([0.0, 0.0], 1.0, 2.0)],
+                missiles: vec![(1, 0, [0.0, 2.4], 0.0), (1, 1, [0.0, 2.6], 0.0)],
+                ..MockData::default()
+            });
+            assert_eq!(
+                collision.execute().unwrap(),
+                vec![CollisionEvent::MissileHitStar {
+                    missile: (1, 0),
+                    star: 0
+                }]
+            );
+        }
+
+        #[test]
+        fn player_hits_player_is_reported_once() {
+            let collision = setup_collision_test(MockData {
+                player: vec![(2, [0.0, 0.0]), (1, [0.0, 1.5]), (3, [0.0, 5.0])],
+                ..MockData::default()
+            });
+            assert_eq!(
+                collision.execute().unwrap(),
+                vec![CollisionEvent::PlayerHitPlayer { players: (1, 2) }]
+            );
+        }
+
+        #[test]
+        fn player_hits_hazard() {
+            let collision = setup_collision_test(MockData {
+                player: vec![(1, [0.0, 0.0]), (2, [0.0, 12.5])],
+                missiles: vec![(1, 0, [20.0, 10.0], 0.0)],
+                hazards: vec![([20.0, 10.0], 5.0), ([0.0, 2.5], 2.0)],
+                ..MockData::default()
+            });
+            assert_eq!(
+                collision.execute().unwrap(),
+                vec![CollisionEvent::PlayerHitHazard {
+                    player: 1,
+                    hazard: 1
+                }]
+            );
+        }
+    }
+}
+
+mod expiry {
+    use crate::{
+        entities::{Real, Vec2},
+        repo_interfaces::{
+            Marshalling, MissileId, MissileIdData, PlayerId, PlayerIdData, Vec2Data,
+        },
+        user_input::MissileConfig,
+        Result,
+    };
+    use std::rc::Rc;
+
+    type MissileInfo = ((PlayerId, MissileId), Real, Vec2, Vec2);
+    type MissileInfoData = (PlayerIdData, MissileIdData, Real, Vec2Data, Vec2Data);
+    type MissileUpdateData = (PlayerIdData, MissileIdData, Vec2Data);
+
+    /// Expiry use-case
+    ///
+    /// Accelerates missiles that have fuel left and removes missiles that exceed their lifetime
+    /// within the time step. Missiles are aged by the integrate use-case, so expiry runs before
+    /// it in a time step.
+    pub struct Expiry {
+        config: MissileConfig,
+        repo: ExpiryDG,
+    }
+    impl Expiry {
+        /// Create use case object
+        pub fn new(config: MissileConfig, repo: ExpiryDG) -> Self {
+            Self { config, repo }
+        }
+
+        /// Run the use case
+        pub fn execute(&self, delta_time: impl Into<Real>) -> Result<()> {
+            let dt = delta_time.into();
+            let (expired, alive): (Vec<_>, Vec<_>) = self
+                .get_missile_info()?
+                .map(|(id, age, vel, acc)| (id, age + dt, self.apply_thrust(age, vel, acc, dt)))
+                .partition(|&(_, age, _)| self.is_expired(age));
+
+            self.repo.remove_missiles(
+                expired
+                    .into_iter()
+                    .map(|((p_id, m_id), _, _)| (p_id, m_id).convert())
+                    .collect(),
+            )?;
+            self.repo.set_missile_acc(
+                alive
+                    .into_iter()
+                    .map(|((p_id, m_id), _, acc)| (p_id, m_id, acc).convert())
+                    .collect(),
+            )
+        }
+
+        /// Check if a missile of a given age exceeded its lifetime
+        fn is_expired(&self, age: Real) -> bool {
+            self.config
+                .get_lifetime()
+                .is_some_and(|lifetime| age >= lifetime)
+        }
+
+        /// Add thrust in direction of velocity for the part of the time step with fuel left
+        fn apply_thrust(&self, age: Real, vel: Vec2, acc: Vec2, delta_time: Real) -> Vec2 {
+            let burn_time = match self.config.get_fuel() {
+                Some(fuel) => (fuel - age).clamp(0.0, delta_time),
+                None => 0.0,
+            };
+            if burn_time <= 0.0 || vel.len2() == 0.0 {
+                return acc;
+            }
+            acc + vel.norm() * self.config.get_thrust() * (burn_time / delta_time)
+        }
+
+        /// Get age, velocity and acceleration of all missiles. IDs are folded into single composit key.
+        fn get_missile_info(&self) -> Result<impl Iterator<Item = MissileInfo>> {
+            Ok(self.repo.get_missile_age_vel_and_acc()?.into_iter().map(
+                |(p_id, m_id, age, vel, acc)| {
+                    ((p_id, m_id).convert(), age, vel.convert(), acc.convert())
+                },
+            ))
+        }
+    }
+
+    /// Data repository interface for expiry use case.
+    pub trait ExpiryDataGateway {
+        /// Return `(player_id, missile_id, age, velocity, acceleration)` for all missiles
+        fn get_missile_age_vel_and_acc(&self) -> Result<Vec<MissileInfoData>>;
+        /// Update acceleration of missiles
+        fn set_missile_acc(&self, updates: Vec<MissileUpdateData>) -> Result<()>;
+        /// Remove missiles
+        fn remove_missiles(&self, ids: Vec<(PlayerIdData, MissileIdData)>) -> Result<()>;
+    }
+    type ExpiryDG = Rc<dyn ExpiryDataGateway>;
+
+    #[cfg(test)]
+    mod test_expiry {
+        use super::{
+            Expiry, ExpiryDataGateway, MissileIdData, MissileInfoData, MissileUpdateData,
+            PlayerIdData, Vec2Data,
+        };
+        use crate::{entities::Real, user_input::MissileConfig, Error, Result};
+        use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+        #[derive(Default)]
+        struct MockData {
+            missiles: HashMap<(PlayerIdData, MissileIdData), (Real, Vec2Data, Vec2Data)>,
+        }
+
+        struct MockDataGateway {
+            data: MockData,
+        }
+        impl ExpiryDataGateway for RefCell<MockDataGateway> {
+            fn get_missile_age_vel_and_acc(&self) -> Result<Vec<MissileInfoData>> {
+                Ok(self
+                    .borrow()
+                    .data
+                    .missiles
+                    .iter()
+                    .map(|(&(p_id, m_id), &(age, vel, acc))| (p_id, m_id, age, vel, acc))
+                    .collect())
+            }
+
+            fn set_missile_acc(&self, updates: Vec<MissileUpdateData>) -> Result<()> {
+                let mut repo = self.borrow_mut();
+                for (p_id, m_id, acc) in updates {
+                    let value = repo
+                        .data
+                        .missiles
+                        .get_mut(&(p_id, m_id))
+                        .ok_or(Error::UnknownMissile(p_id, m_id))?;
+                    value.2 = acc;
+                }
+                Ok(())
+            }
+
+            fn remove_missiles(&self, ids: Vec<(PlayerIdData, MissileIdData)>) -> Result<()> {
+                let mut repo = self.borrow_mut();
+                for (p_id, m_id) in ids {
+                    repo.data
+                        .missiles
+                        .remove(&(p_id, m_id))
+                        .ok_or(Error::UnknownMissile(p_id, m_id))?;
+                }
+                Ok(())
+            }
+        }
+
+        fn setup_expiry_test(
+            config: MissileConfig,
+            data: MockData,
+        ) -> (Expiry, Rc<RefCell<MockDataGateway>>) {
+            let repo = Rc::new(RefCell::new(MockDataGateway { data }));
+            (Expiry::new(config, repo.clone()), repo)
+        }
+
+        #[test]
+        fn missiles_are_not_aged() {
+            let data = MockData {
+                missiles: [((1, 0), (1.0, [1.0, 0.0], [0.0, 0.0]))].into(),
+            };
+            let (expiry, repo) = setup_expiry_test(MissileConfig::new(3, 1.0, 1.0), data);
+            expiry.execute(0.5).unwrap();
+            assert_eq!(repo.borrow().data.missiles[&(1, 0)].0, 1.0);
+        }
+
+        #[test]
+        fn missiles_without_lifetime_never_expire() {
+            let data = MockData {
+                missiles: [((1, 0), (1e6, [1.0, 0.0], [0.0, 0.0]))].into(),
+            };
+            let (expiry, repo) = setup_expiry_test(MissileConfig::new(3, 1.0, 1.0), data);
+            expiry.execute(1.0).unwrap();
+            assert_eq!(repo.borrow().data.missiles.len(), 1);
+        }
+
+        #[test]
+        fn expired_missiles_are_removed() {
+            let data = MockData {
+                missiles: [
+                    ((1, 0), (9.5, [1.0, 0.0], [0.0, 0.0])),
+                    ((1, 1), (2.0, [1.0, 0.0], [0.0, 0.0])),
+                    ((2, 4), (10.0, [1.0, 0.0], [0.0, 0.0])),
+                ]
+                .into(),
+            };
+            let config = MissileConfig::new(3, 1.0, 1.0).with_lifetime(10.0);
+            let (expiry, repo) = setup_expiry_test(config, data);
+            expiry.execute(0.5).unwrap();
+            let repo = repo.borrow();
+            assert_eq!(repo.data.missiles.len(), 1);
+            assert!(repo.data.missiles.contains_key(&(1, 1)));
+        }
+
+        #[test]
+        fn thrust_applied_while_fuel_is_left() {
+            let data = MockData {
+                missiles: [
+                    ((1, 0), (0.0, [0.0, 3.0], [1.0, 0.0])),
+                    ((1, 1), (5.0, [0.0, 3.0], [1.0, 0.0])),
+                ]
+                .into(),
+            };
+            let config = MissileConfig::new(3, 1.0, 1.0).with_fuel(2.0, 4.0);
+            let (expiry, repo) = setup_expiry_test(config, data);
+            expiry.execute(1.0).unwrap();
+            let repo = repo.borrow();
+            assert_eq!(repo.data.missiles[&(1, 0)].2, [1.0, 4.0]);
+            assert_eq!(repo.data.missiles[&(1, 1)].2, [1.0, 0.0]);
+        }
+
+        #[test]
+        fn thrust_scaled_when_fuel_runs_out_during_time_step() {
+            let data = MockData {
+                missiles: [((1, 0), (1.5, [3.0, 0.0], [0.0, 0.0]))].into(),
+            };
+            let config = MissileConfig::new(3, 1.0, 1.0).with_fuel(2.0, 4.0);
+            let (expiry, repo) = setup_expiry_test(config, data);
+            expiry.execute(1.0).unwrap();
+            assert_eq!(repo.borrow().data.missiles[&(1, 0)].2, [2.0, 0.0]);
+        }
+    }
+}
+
+mod star_motion {
+    use super::PhysicsConfig;
+    use crate::{
+        entities::{
+            fixed::{Fixed, FixedVec2},
+            Real, Vec2, PI, TWO_PI,
+        },
+        repo_interfaces::{Marshalling, StarId, Vec2Data},
+        Error, Result,
+    };
+    use std::rc::Rc;
+
+    /// Prescribed motion of a star
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum StarMotion {
+        /// Keplerian orbit with one focus at `center`
+        ///
+        /// If `parent` is set, `center` is relative to the current position of the parent star.
+        /// Angles are in radians, `phase` is the mean anomaly at time zero.
+        Kepler {
+            center: Vec2Data,
+            parent: Option<StarId>,
+            semi_major_axis: Real,
+            eccentricity: Real,
+            period: Real,
+            periapsis_angle: Real,
+            phase: Real,
+        },
+        /// Closed path through `waypoints` with constant time per segment, traversed once per
+        /// `period`
+        Path {
+            waypoints: Vec<Vec2Data>,
+            period: Real,
+        },
+    }
+
+    impl StarMotion {
+        /// Circular orbit of radius `radius` around `center`
+        pub fn circular(center: Vec2Data, radius: Real, period: Real, phase: Real) -> Self {
+            StarMotion::Kepler {
+                center,
+                parent: None,
+                semi_major_axis: radius,
+                eccentricity: 0.0,
+                period,
+                periapsis_angle: 0.0,
+                phase,
+            }
+        }
+
+        /// Star the motion is relative to, if any
+        fn get_parent(&self) -> Option<StarId> {
+            match self {
+                StarMotion::Kepler { parent, .. } => *parent,
+                StarMotion::Path { .. } => None,
+            }
+        }
+
+        /// Check that the motion is well defined
+        pub(crate) fn validate(&self) -> Result<()> {
+            let valid = match self {
+                StarMotion::Kepler {
+                    semi_major_axis,
+                    eccentricity,
+                    period,
+                    ..
+                } => *semi_major_axis >= 0.0 && (0.0..1.0).contains(eccentricity) && *period > 0.0,
+                StarMotion::Path { waypoints, period } => !waypoints.is_empty() && *period > 0.0,
+            };
+            if valid {
+                Ok(())
+            } else {
+                Err(Error::InvalidValue(format!("invalid star motion {self:?}")))
+            }
+        }
+
+        /// Position at `time` given the position of the parent star
+        ///
+        /// In fixed-point mode, Kepler orbits are computed with deterministic fixed-point
+        /// arithmetic.
+        fn position(&self, config: &PhysicsConfig, time: Real, parent_pos: Vec2) -> Vec2 {
+            match self {
+                StarMotion::Kepler {
+                    center,
+                    parent: _,
+                    semi_major_axis,
+                    eccentricity,
+                    period,
+                    periapsis_angle,
+                    phase,
+                } if config.is_fixed_point() => {
+                    let (a, e) = (Fixed::from(*semi_major_axis), Fixed::from(*eccentricity));
+                    let mean_anomaly = Fixed::from(*phase)
+                        + Fixed::TWO_PI
+                            * (Fixed::from(time) / Fixed::from(*period)).rem_euclid(Fixed::ONE);
+                    let (sin, cos) = fixed_eccentric_anomaly(mean_anomaly, e).sin_cos();
+                    let offset =
+                        FixedVec2::new(a * (cos - e), a * (Fixed::ONE - e * e).sqrt() * sin)
+                            .rotate(Fixed::from(*periapsis_angle));
+                    parent_pos + center.convert() + offset.into()
+                }
+                StarMotion::Kepler {
+                    center,
+                    parent: _,
+                    semi_major_axis,
+                    eccentricity,
+                    period,
+                    periapsis_angle,
+                    phase,
+                } => {
+                    let mean_anomaly = phase + TWO_PI * (time / period).rem_euclid(1.0);
+                    let e = *eccentricity;
+                    let anomaly = eccentric_anomaly(mean_anomaly, e);
+                    let offset = Vec2::new(
+                        semi_major_axis * (anomaly.cos() - e),
+                        semi_major_axis * (1.0 - e * e).sqrt() * anomaly.sin(),
+                    )
+                    .rotate(*periapsis_angle);
+                    parent_pos + center.convert() + offset
+                }
+                StarMotion::Path { waypoints, period } => {
+                    let progress = (time / period).rem_euclid(1.0) * waypoints.len() as Real;
+                    let index = (progress as usize).min(waypoints.len() - 1);
+                    let from: Vec2 = waypoints[index].convert();
+                    let to: Vec2 = waypoints[(index + 1) % waypoints.len()].convert();
+                    from + (to - from) * (progress - index as Real)
+                }
+            }
+        }
+    }
+
+    /// Solve Kepler's equation `E - e sin(E) = M` for the eccentric anomaly `E`
+    fn eccentric_anomaly(mean_anomaly: Real, eccentricity: Real) -> Real {
+        let mut anomaly = if eccentricity > 0.8 { PI } else { mean_anomaly };
+        for _ in 0..16 {
+            let delta = (anomaly - eccentricity * anomaly.sin() - mean_anomaly)
+                / (1.0 - eccentricity * anomaly.cos());
+            anomaly -= delta;
+            if delta.abs() < 1e-6 {
+                break;
+            }
+        }
+        anomaly
+    }
+
+    /// Fixed-point variant of [`eccentric_anomaly`]
+    fn fixed_eccentric_anomaly(mean_anomaly: Fixed, eccentricity: Fixed) -> Fixed {
+        let mut anomaly = if eccentricity > Fixed::from(0.8) {
+            Fixed::PI
+        } else {
+            mean_anomaly
+        };
+        let tolerance = Fixed::from(1e-6);
+        for _ in 0..16 {
+            let (sin, cos) = anomaly.sin_cos();
+            let delta =
+                (anomaly - eccentricity * sin - mean_anomaly) / (Fixed::ONE - eccentricity * cos);
+            anomaly = anomaly - delta;
+            if delta.abs() < tolerance {
+                break;
+            }
+        }
+        anomaly
+    }
+
+    /// Star movement use-case
+    ///
+    /// Moves all stars with a prescribed motion to their position at a given time. Positions are
+    /// computed from the time directly, so they do not accumulate integration errors.
+    pub struct StarMovement {
+        config: PhysicsConfig,
+        repo: StarMotionDG,
+    }
+    impl StarMovement {
+        /// Create use case object, only the fixed-point mode of the configuration is used
+        pub fn new(config: PhysicsConfig, repo: StarMotionDG) -> Self {
+            Self { config, repo }
+        }
+
+        /// Configuration of the use case
+        pub fn get_config(&self) -> PhysicsConfig {
+            self.config
+        }
+
+        /// Move stars to their position at `time`
+        ///
+        /// Fails if a motion is invalid, refers to an unknown parent or parents form a cycle.
+        pub fn execute(&self, time: impl Into<Real>) -> Result<()> {
+            let time = time.into();
+            let motions = self.repo.get_star_motions()?;
+            let mut positions: Vec<(StarId, Vec2)> = self.repo.get_star_positions()?.convert();
+            // Static stars are at their final position already
+            let mut resolved = vec![true; positions.len()];
+            for (id, motion) in &motions {
+                motion.validate()?;
+                resolved[star_index(&positions, *id)?] = false;
+            }
+
+            // Resolve parents before children; each pass moves at least one star
+            let mut pending: Vec<&(StarId, StarMotion)> = motions.iter().collect();
+            while !pending.is_empty() {
+                let before = pending.len();
+                let mut index = 0;
+                while index < pending.len() {
+                    let (id, motion) = pending[index];
+                    let parent_pos = match motion.get_parent() {
+                        None => Some(Vec2::zero()),
+                        Some(parent) => {
+                            let parent_index = star_index(&positions, parent)?;
+                            resolved[parent_index].then_some(positions[parent_index].1)
+                        }
+                    };
+                    match parent_pos {
+                        Some(parent_pos) => {
+                            let star = star_index(&positions, *id)?;
+                            positions[star].1 = motion.position(&self.config, time, parent_pos);
+                            resolved[star] = true;
+                            pending.swap_remove(index);
+                        }
+                        None => index += 1,
+                    }
+                }
+                if pending.len() == before {
+                    return Err(Error::InvalidValue("star motions form a cycle".to_string()));
+                }
+            }
+
+            self.repo.set_star_positions(
+                motions
+                    .iter()
+                    .map(|(id, _)| {
+                        let index = star_index(&positions, *id)?;
+                        Ok((*id, positions[index].1.convert()))
+                    })
+                    .collect::<Result<_>>()?,
+            )
+        }
+    }
+
+    /// Index of a star in a list of `(id, position)`
+    fn star_index(positions: &[(StarId, Vec2)], id: StarId) -> Result<usize> {
+        positions
+            .iter()
+            .position(|(star_id, _)| *star_id == id)
+            .ok_or(Error::UnknownStar(id))
+    }
+
+    /// Data repository interface for star movement use case.
+    pub trait StarMotionDataGateway {
+        /// Return `(id, position)` of all stars
+        fn get_star_positions(&self) -> Result<Vec<(StarId, Vec2Data)>>;
+        /// Return `(id, motion)` of all moving stars
+        fn get_star_motions(&self) -> Result<Vec<(StarId, StarMotion)>>;
+        /// Update positions of stars
+        fn set_star_positions(&self, updates: Vec<(StarId, Vec2Data)>) -> Result<()>;
+    }
+    type StarMotionDG = Rc<dyn StarMotionDataGateway>;
+
+    #[cfg(test)]
+    mod test_star_motion {
+        use super::{StarId, StarMotion, StarMotionDataGateway, StarMovement, Vec2Data};
+        use crate::entities::PI;
+        use crate::{entities::Real, physics::PhysicsConfig};
+        use crate::{Error, Result};
+        use std::{cell::RefCell, rc::Rc};
+
+        #[derive(Default)]
+        struct MockData {
+            positions: Vec<Vec2Data>,
+            motions: Vec<(StarId, StarMotion)>,
+        }
+
+        struct MockDataGateway {
+            data: MockData,
+        }
+        impl StarMotionDataGateway for RefCell<MockDataGateway> {
+            fn get_star_positions(&self) -> Result<Vec<(StarId, Vec2Data)>> {
+                Ok(self
+                    .borrow()
+                    .data
+                    .positions
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .collect())
+            }
+
+            fn get_star_motions(&self) -> Result<Vec<(StarId, StarMotion)>> {
+                Ok(self.borrow().data.motions.clone())
+            }
+
+            fn set_star_positions(&self, updates: Vec<(StarId, Vec2Data)>) -> Result<()> {
+                let mut repo = self.borrow_mut();
+                for (id, pos) in updates {
+                    repo.data.positions[id] = pos;
+                }
+                Ok(())
+            }
+        }
+
+        fn setup_star_motion_test(data: MockData) -> (StarMovement, Rc<RefCell<MockDataGateway>>) {
+            let repo = Rc::new(RefCell::new(MockDataGateway { data }));
+            (StarMovement::new(PhysicsConfig::new(), repo.clone()), repo)
+        }
+
+        fn assert_close(actual: Vec2Data, expected: Vec2Data) {
+            assert!(
+                (actual[0] - expected[0]).abs() < 1e-3 && (actual[1] - expected[1]).abs() < 1e-3,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+
+        #[test]
+        fn binary_stars_orbit_common_center() {
+            let (movement, repo) = setup_star_motion_test(MockData {
+                positions: vec![[0.0, 0.0]; 2],
+                motions: vec![
+                    (0, StarMotion::circular([0.0, 0.0], 10.0, 4.0, 0.0)),
+                    (1, StarMotion::circular([0.0, 0.0], 10.0, 4.0, PI)),
+                ],
+            });
+            movement.execute(1.0).unwrap();
+            let positions = repo.borrow().data.positions.clone();
+            assert_close(positions[0], [0.0, 10.0]);
+            assert_close(positions[1], [0.0, -10.0]);
+        }
+
+        #[test]
+        fn eccentric_orbit_reaches_apoapsis_after_half_period() {
+            let orbit = StarMotion::Kepler {
+                center: [0.0, 0.0],
+                parent: None,
+                semi_major_axis: 10.0,
+                eccentricity: 0.5,
+                period: 2.0,
+                periapsis_angle: PI / 2.0,
+                phase: 0.0,
+            };
+            let (movement, repo) = setup_star_motion_test(MockData {
+                positions: vec![[0.0, 0.0]],
+                motions: vec![(0, orbit)],
+            });
+            movement.execute(0.0).unwrap();
+            assert_close(repo.borrow().data.positions[0], [0.0, 5.0]);
+            movement.execute(1.0).unwrap();
+            assert_close(repo.borrow().data.positions[0], [0.0, -15.0]);
+        }
+
+        #[test]
+        fn fixed_point_orbit_matches_float() {
+            let orbit = StarMotion::Kepler {
+                center: [5.0, -3.0],
+                parent: None,
+                semi_major_axis: 40.0,
+                eccentricity: 0.9,
+                period: 7.0,
+                periapsis_angle: 1.0,
+                phase: 0.5,
+            };
+            let data = || MockData {
+                positions: vec![[0.0, 0.0]],
+                motions: vec![(0, orbit.clone())],
+            };
+            let (float, float_repo) = setup_star_motion_test(data());
+            let (_, fixed_repo) = setup_star_motion_test(data());
+            let fixed =
+                StarMovement::new(PhysicsConfig::new().with_fixed_point(), fixed_repo.clone());
+            for step in 0..20 {
+                let time = 0.4 * step as Real;
+                float.execute(time).unwrap();
+                fixed.execute(time).unwrap();
+                assert_close(
+                    fixed_repo.borrow().data.positions[0],
+                    float_repo.borrow().data.positions[0],
+                );
+            }
+        }
+
+        #[test]
+        fn planet_follows_its_sun() {
+            let planet = StarMotion::Kepler {
+                center: [0.0, 0.0],
+                parent: Some(0),
+                semi_major_axis: 2.0,
+                eccentricity: 0.0,
+                period: 1.0,
+                periapsis_angle: 0.0,
+                phase: 0.0,
+            };
+            let (movement, repo) = setup_star_motion_test(MockData {
+                positions: vec![[0.0, 0.0], [0.0, 0.0], [3.0, 3.0]],
+                // Child listed before its parent
+                motions: vec![
+                    (1, planet),
+                    (0, StarMotion::circular([0.0, 0.0], 10.0, 4.0, 0.0)),
+                ],
+            });
+            movement.execute(1.0).unwrap();
+            let positions = repo.borrow().data.positions.clone();
+            assert_close(positions[0], [0.0, 10.0]);
+            assert_close(positions[1], [2.0, 10.0]);
+            assert_eq!(positions[2], [3.0, 3.0]);
+        }
+
+        #[test]
+        fn scripted_path_interpolated() {
+            let (movement, repo) = setup_star_motion_test(MockData {
+                positions: vec![[0.0, 0.0]],
+                motions: vec![(
+                    0,
+                    StarMotion::Path {
+                        waypoints: vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]],
+                        period: 3.0,
+                    },
+                )],
+            });
+            movement.execute(1.5).unwrap();
+            assert_close(repo.borrow().data.positions[0], [10.0, 5.0]);
+            movement.execute(5.5).unwrap();
+            assert_close(repo.borrow().data.positions[0], [5.0, 5.0]);
+        }
+
+        #[test]
+        fn cyclic_parents_rejected() {
+            let orbit = |parent| StarMotion::Kepler {
+                center: [0.0, 0.0],
+                parent: Some(parent),
+                semi_major_axis: 1.0,
+                eccentricity: 0.0,
+                period: 1.0,
+                periapsis_angle: 0.0,
+                phase: 0.0,
+            };
+            let (movement, _) = setup_star_motion_test(MockData {
+                positions: vec![[0.0, 0.0]; 2],
+                motions: vec![(0, orbit(1)), (1, orbit(0))],
+            });
+            assert!(matches!(movement.execute(1.0), Err(Error::InvalidValue(_))));
+        }
+    }
+}
+
+mod boundary {
+    use super::PhysicsConfig;
+    use crate::{
+        entities::{
+            fixed::{Fixed, FixedVec2},
+            Real, Vec2,
+        },
+        repo_interfaces::{
+            Marshalling, MissileId, MissileIdData, PlayerId, PlayerIdData, Vec2Data,
+        },
+        Error, Result,
+    };
+    use std::rc::Rc;
+
+    /// Behaviour of objects that reach the bounds of the arena
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum BoundaryMode {
+        /// Objects leaving the arena re-enter on the opposite side. Distances are measured to
+        /// the nearest periodic image.
+        Wrap,
+        /// Objects bounce off the walls elastically
+        Reflect,
+        /// Ships leaving the arena are destroyed and missiles are removed
+        KillZone,
+    }
+
+    /// Rectangular arena
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Arena {
+        /// Lower left corner
+        min: Vec2,
+        /// Upper right corner
+        max: Vec2,
+        /// Behaviour at the bounds
+        mode: BoundaryMode,
+    }
+
+    impl Arena {
+        /// Create an arena spanned by its lower left and upper right corner
+        ///
+        /// Fails with [`Error::InvalidValue`] unless both corners are finite and the arena has a
+        /// positive extent along both axes.
+        pub fn new(min: Vec2Data, max: Vec2Data, mode: BoundaryMode) -> Result<Self> {
+            let finite = min.iter().chain(&max).all(|value| value.is_finite());
+            if !finite || min[0] >= max[0] || min[1] >= max[1] {
+                return Err(Error::InvalidValue(format!(
+                    "arena from {min:?} to {max:?} has no finite, positive extent"
+                )));
+            }
+            Ok(Arena {
+                min: min.convert(),
+                max: max.convert(),
+                mode,
+            })
+        }
+
+        /// Lower left corner
+        pub fn get_min(&self) -> Vec2Data {
+            self.min.convert()
+        }
+
+        /// Upper right corner
+        pub fn get_max(&self) -> Vec2Data {
+            self.max.convert()
+        }
+
+        /// Behaviour at the bounds
+        pub fn get_mode(&self) -> BoundaryMode {
+            self.mode
+        }
+
+        /// Check if a position lies within the arena
+        pub(crate) fn contains(&self, pos: Vec2) -> bool {
+            (self.min.get_x()..=self.max.get_x()).contains(&pos.get_x())
+                && (self.min.get_y()..=self.max.get_y()).contains(&pos.get_y())
+        }
+
+        /// Vector from `from` to `to`, to the nearest periodic image of `to` in wrap mode
+        pub(crate) fn separation(&self, from: Vec2, to: Vec2) -> Vec2 {
+            let delta = to - from;
+            if self.mode != BoundaryMode::Wrap {
+                return delta;
+            }
+            let size = self.max - self.min;
+            let nearest = |delta: Real, size: Real| delta - size * (delta / size).round();
+            Vec2::new(
+                nearest(delta.get_x(), size.get_x()),
+                nearest(delta.get_y(), size.get_y()),
+            )
+        }
+
+        /// Fixed-point variant of [`Self::separation`]
+        pub(crate) fn fixed_separation(&self, from: FixedVec2, to: FixedVec2) -> FixedVec2 {
+            let delta = to - from;
+            if self.mode != BoundaryMode::Wrap {
+                return delta;
+            }
+            let size = FixedVec2::from(self.max) - FixedVec2::from(self.min);
+            let nearest = |delta: Fixed, size: Fixed| delta - size * (delta / size).round();
+            FixedVec2::new(
+                nearest(delta.get_x(), size.get_x()),
+                nearest(delta.get_y(), size.get_y()),
+            )
+        }
+
+        /// Periodic image of `pos` nearest to `reference`, `pos` itself unless in wrap mode
+        pub(crate) fn nearest_image(&self, pos: Vec2, reference: Vec2) -> Vec2 {
+            reference + self.separation(reference, pos)
+        }
+
+        /// Move an object back into the arena
+        ///
+        /// Returns `None` if the object left the kill zone.
+        pub(crate) fn confine(&self, (pos, vel): (Vec2, Vec2)) -> Option<(Vec2, Vec2)> {
+            let confine_axis = |pos: Real, vel: Real, min: Real, max: Real| match self.mode {
+                BoundaryMode::Wrap => (min + (pos - min).rem_euclid(max - min), vel),
+                BoundaryMode::Reflect if pos < min => ((2.0 * min - pos).min(max), vel.abs()),
+                BoundaryMode::Reflect if pos > max => ((2.0 * max - pos).max(min), -vel.abs()),
+                BoundaryMode::Reflect | BoundaryMode::KillZone => (pos, vel),
+            };
+            if self.mode == BoundaryMode::KillZone && !self.contains(pos) {
+                return None;
+            }
+            let (x, vel_x) =
+                confine_axis(pos.get_x(), vel.get_x(), self.min.get_x(), self.max.get_x());
+            let (y, vel_y) =
+                confine_axis(pos.get_y(), vel.get_y(), self.min.get_y(), self.max.get_y());
+            Some((Vec2::new(x, y), Vec2::new(vel_x, vel_y)))
+        }
+    }
+
+    /// Boundary use-case
+    ///
+    /// Applies the arena bounds to all player and missile objects after they have been moved.
+    pub struct Boundary {
+        arena: Arena,
+        repo: BoundaryDG,
+    }
+    impl Boundary {
+        /// Create use case object for the arena of the physics configuration
+        ///
+        /// Fails if no arena is configured.
+        pub fn new(config: PhysicsConfig, repo: BoundaryDG) -> Result<Self> {
+            let arena = config.get_arena().ok_or_else(|| {
+                Error::InvalidValue("boundary requires a physics configuration with arena".into())
+            })?;
+            Ok(Self { arena, repo })
+        }
+
+        /// Bounds of the arena
+        pub fn get_arena(&self) -> Arena {
+            self.arena
+        }
+
+        /// Run the use case
+        ///
+        /// Returns the ids of all player whose ships were destroyed for leaving the arena.
+        pub fn execute(&self) -> Result<Vec<PlayerId>> {
+            let mut updates = Vec::new();
+            let mut destroyed = Vec::new();
+            for (id, pos, vel) in self.repo.get_player_pos_and_vel()? {
+                match self.arena.confine((pos.convert(), vel.convert())) {
+                    Some((pos, vel)) => updates.push((id, pos.convert(), vel.convert())),
+                    None => destroyed.push(id.convert()),
+                }
+            }
+            self.repo.set_player_pos_and_vel(updates)?;
+            self.repo.destroy_players_out_of_bounds(
+                destroyed.iter().map(|id: &PlayerId| id.convert()).collect(),
+            )?;
+
+            let mut updates = Vec::new();
+            let mut removed: Vec<(PlayerId, MissileId)> = Vec::new();
+            for (p_id, m_id, pos, vel) in self.repo.get_missile_pos_and_vel()? {
+                match self.arena.confine((pos.convert(), vel.convert())) {
+                    Some((pos, vel)) => updates.push((p_id, m_id, pos.convert(), vel.convert())),
+                    None => removed.push((p_id, m_id).convert()),
+                }
+            }
+            self.repo.set_missile_pos_and_vel(updates)?;
+            self.repo.remove_missiles_out_of_bounds(
+                removed.into_iter().map(|id| id.convert()).collect(),
+            )?;
+            Ok(destroyed)
+        }
+    }
+
+    /// Data repository interface for boundary use case.
+    pub trait BoundaryDataGateway {
+        /// Return `(id, position, velocity)` of all player whose ships are not destroyed
+        fn get_player_pos_and_vel(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Vec2Data)>>;
+        /// Return `(player_id, missile_id, position, velocity)` of all missiles
+        fn get_missile_pos_and_vel(
+            &self,
+        ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>>;
+        /// Update position and velocity of player objects
+        fn set_player_pos_and_vel(
+            &self,
+            updates: Vec<(PlayerIdData, Vec2Data, Vec2Data)>,
+        ) -> Result<()>;
+        /// Update position and velocity of missile objects
+        fn set_missile_pos_and_vel(
+            &self,
+            updates: Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>,
+        ) -> Result<()>;
+        /// Mark ships that left the arena as destroyed
+        fn destroy_players_out_of_bounds(&self, ids: Vec<PlayerIdData>) -> Result<()>;
+        /// Remove missiles that left the arena
+        fn remove_missiles_out_of_bounds(
+            &self,
+            ids: Vec<(PlayerIdData, MissileIdData)>,
+        ) -> Result<()>;
+    }
+    type BoundaryDG = Rc<dyn BoundaryDataGateway>;
+
+    #[cfg(test)]
+    mod test_boundary {
+        use std::{cell::RefCell, rc::Rc};
+
+        use super::{Arena, Boundary, BoundaryDataGateway, BoundaryMode};
+        use crate::{
+            entities::{Real, Vec2},
+            physics::PhysicsConfig,
+            repo_interfaces::{MissileIdData, PlayerIdData, Vec2Data},
+            Error, Result,
+        };
+
+        #[derive(Default)]
+        struct MockDataGateway {
+            player: Vec<(PlayerIdData, Vec2Data, Vec2Data)>,
+            missiles: Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>,
+            destroyed: Vec<PlayerIdData>,
+            removed: Vec<(PlayerIdData, MissileIdData)>,
+        }
+
+        impl BoundaryDataGateway for RefCell<MockDataGateway> {
+            fn get_player_pos_and_vel(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Vec2Data)>> {
+                Ok(self.borrow().player.clone())
+            }
+            fn get_missile_pos_and_vel(
+                &self,
+            ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>> {
+                Ok(self.borrow().missiles.clone())
+            }
+            fn set_player_pos_and_vel(
+                &self,
+                updates: Vec<(PlayerIdData, Vec2Data, Vec2Data)>,
+            ) -> Result<()> {
+                self.borrow_mut().player = updates;
+                Ok(())
+            }
+            fn set_missile_pos_and_vel(
+                &self,
+                updates: Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>,
+            ) -> Result<()> {
+                self.borrow_mut().missiles = updates;
+                Ok(())
+            }
+            fn destroy_players_out_of_bounds(&self, ids: Vec<PlayerIdData>) -> Result<()> {
+                self.borrow_mut().destroyed.extend(ids);
+                Ok(())
+            }
+            fn remove_missiles_out_of_bounds(
+                &self,
+                ids: Vec<(PlayerIdData, MissileIdData)>,
+            ) -> Result<()> {
+                self.borrow_mut().removed.extend(ids);
+                Ok(())
+            }
+        }
+
+        fn setup_boundary_test(
+            mode: BoundaryMode,
+            data: MockDataGateway,
+        ) -> (Boundary, Rc<RefCell<MockDataGateway>>) {
+            let arena = Arena::new([-10.0, -10.0], [10.0, 10.0], mode).unwrap();
+            let config = PhysicsConfig::new().with_arena(arena);
+            let repo = Rc::new(RefCell::new(data));
+            (Boundary::new(config, repo.clone()).unwrap(), repo)
+        }
+
+        #[test]
+        fn objects_wrap_around() {
+            let (boundary, repo) = setup_boundary_test(
+                BoundaryMode::Wrap,
+                MockDataGateway {
+                    player: vec![(1, [12.0, -3.0], [1.0, 0.0]), (2, [0.0, 0.0], [1.0, 1.0])],
+                    missiles: vec![(1, 0, [-11.0, -31.0], [0.0, -1.0])],
+                    ..Default::default()
+                },
+            );
+            assert_eq!(boundary.execute(), Ok(vec![]));
+            let repo = repo.borrow();
+            assert_eq!(
+                repo.player,
+                vec![(1, [-8.0, -3.0], [1.0, 0.0]), (2, [0.0, 0.0], [1.0, 1.0])]
+            );
+            assert_eq!(repo.missiles, vec![(1, 0, [9.0, 9.0], [0.0, -1.0])]);
+        }
+
+        #[test]
+        fn objects_reflected_by_walls() {
+            let (boundary, repo) = setup_boundary_test(
+                BoundaryMode::Reflect,
+                MockDataGateway {
+                    player: vec![(1, [12.0, -11.0], [2.0, -1.0])],
+                    missiles: vec![(1, 0, [5.0, 50.0], [0.0, 30.0])],
+                    ..Default::default()
+                },
+            );
+            boundary.execute().unwrap();
+            let repo = repo.borrow();
+            assert_eq!(repo.player, vec![(1, [8.0, -9.0], [-2.0, 1.0])]);
+            // Far outside objects are clamped to the wall
+            assert_eq!(repo.missiles, vec![(1, 0, [5.0, -10.0], [0.0, -30.0])]);
+        }
+
+        #[test]
+        fn objects_leaving_kill_zone_removed() {
+            let (boundary, repo) = setup_boundary_test(
+                BoundaryMode::KillZone,
+                MockDataGateway {
+                    player: vec![(1, [12.0, 0.0], [1.0, 0.0]), (2, [10.0, 0.0], [1.0, 0.0])],
+                    missiles: vec![
+                        (2, 0, [0.0, -11.0], [0.0, -1.0]),
+                        (2, 1, [0.0, 0.0], [0.0, 0.0]),
+                    ],
+                    ..Default::default()
+                },
+            );
+            assert_eq!(boundary.execute(), Ok(vec![1]));
+            let repo = repo.borrow();
+            assert_eq!(repo.destroyed, vec![1]);
+            assert_eq!(repo.player, vec![(2, [10.0, 0.0], [1.0, 0.0])]);
+            assert_eq!(repo.removed, vec![(2, 0)]);
+            assert_eq!(repo.missiles, vec![(2, 1, [0.0, 0.0], [0.0, 0.0])]);
+        }
+
+        #[test]
+        fn separation_uses_minimum_image_when_wrapping() {
+            let wrap = Arena::new([0.0, 0.0], [100.0, 50.0], BoundaryMode::Wrap).unwrap();
+            let (a, b) = (Vec2::new(5.0, 45.0), Vec2::new(95.0, 5.0));
+            assert_eq!(wrap.separation(a, b), Vec2::new(-10.0, 10.0));
+            assert_eq!(wrap.nearest_image(b, a), Vec2::new(-5.0, 55.0));
+            let reflect = Arena::new([0.0, 0.0], [100.0, 50.0], BoundaryMode::Reflect).unwrap();
+            assert_eq!(reflect.separation(a, b), Vec2::new(90.0, -40.0));
+        }
+
+        #[test]
+        fn arena_without_extent_rejected() {
+            let corners = [
+                ([0.0, 0.0], [0.0, 10.0]),
+                ([0.0, 10.0], [10.0, 0.0]),
+                ([0.0, 0.0], [Real::INFINITY, 10.0]),
+                ([Real::NAN, 0.0], [10.0, 10.0]),
+            ];
+            for (min, max) in corners {
+                assert!(matches!(
+                    Arena::new(min, max, BoundaryMode::Wrap),
+                    Err(Error::InvalidValue(_))
+                ));
+            }
+        }
+    }
+}
+
+mod prediction {
+    use super::{
+        gravity::{limited_star_field, Star},
+        integrate::advance,
+        PhysicsConfig,
+    };
+    use crate::{
+        entities::{Real, Vec2},
+        repo_interfaces::{Marshalling, PlayerPosAndVelocityData, StarData, StarId, Vec2Data},
+    };
+
+    /// Predicted path of an object
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct Trajectory {
+        /// Position after each time step
+        pub positions: Vec<Vec2Data>,
+        /// Star hit by the object, if any. The last position lies within this star.
+        pub impact: Option<StarId>,
+    }
+
+    /// Predict the path of an unpowered ship or missile
+    ///
+    /// The object starts at `start`, e.g. the launch state of a missile, and is moved for at most
+    /// `steps` time steps of length `delta_time` in the gravitational field of `stars`. Stars stay
+    /// at rest and ships and missiles do not attract each other. Otherwise, the object moves
+    /// exactly as it would by the gravity and integrate use-cases with the same configuration.
+    ///
+    /// The prediction stops early if the object hits a star or leaves the kill zone.
+    pub fn predict_trajectory(
+        config: &PhysicsConfig,
+        start: PlayerPosAndVelocityData,
+        stars: &[StarData],
+        delta_time: Real,
+        steps: usize,
+    ) -> Trajectory {
+        let stars: Vec<Star> = stars.iter().map(|star| star.convert()).collect();
+        let (mut pos, mut vel): (Vec2, Vec2) = (start.pos.convert(), start.velocity.convert());
+        let mut trajectory = Trajectory {
+            positions: Vec::with_capacity(steps),
+            impact: None,
+        };
+        for _ in 0..steps {
+            let acc = limited_star_field(config, &stars, pos);
+            (pos, vel) = advance(config, &stars, (pos, vel, acc), delta_time);
+            if let Some(arena) = config.get_arena() {
+                match arena.confine((pos, vel)) {
+                    Some(confined) => (pos, vel) = confined,
+                    None => break,
+                }
+            }
+            if !pos.is_finite() {
+                break;
+            }
+            trajectory.positions.push(pos.convert());
+            trajectory.impact = stars.iter().position(|star| {
+                config.separation(pos, star.pos).len2() <= star.radius * star.radius
+            });
+            if trajectory.impact.is_some() {
+                break;
+            }
+        }
+        trajectory
+    }
+
+    #[cfg(test)]
+    mod test_prediction {
+        use std::{cell::RefCell, rc::Rc};
+
+        use super::predict_trajectory;
+        use crate::{
+            entities::Real,
+            physics::{Arena, BoundaryMode, Gravity, Integrate, Integrator, PhysicsConfig},
+            repo::GameState,
+            repo_interfaces::{IntegrateDataGateway, PlayerPosAndVelocityData, StarData},
+        };
+
+        fn launch(pos: [Real; 2], velocity: [Real; 2]) -> PlayerPosAndVelocityData {
+            PlayerPosAndVelocityData {
+                pos,
+                angle: 0.0,
+                velocity,
+            }
+        }
+
+        #[test]
+        fn prediction_matches_simulation() {
+            let stars = vec![StarData::new([0.0, 0.0], 1000.0, 5.0)];
+            let config = PhysicsConfig::new()
+                .with_integrator(Integrator::VelocityVerlet)
+                .with_substeps(2);
+            let start = launch([100.0, 0.0], [0.0, 2.5]);
+            let trajectory = predict_trajectory(&config, start, &stars, 0.5, 100);
+            assert_eq!(trajectory.positions.len(), 100);
+            assert_eq!(trajectory.impact, None);
+
+            let mut state = GameState::with_players(&[1]);
+            stars.iter().for_each(|&star| state.add_star(star));
+            let state = Rc::new(RefCell::new(state));
+            state
+                .set_player_info(vec![(1, start.pos, start.velocity, [0.0, 0.0])])
+                .unwrap();
+            let gravity = Gravity::new(config, state.clone());
+            let integrate = Integrate::new(config, state.clone());
+            for expected in trajectory.positions {
+                gravity.execute().unwrap();
+                integrate.execute(0.5).unwrap();
+                assert_eq!(state.get_player_info().unwrap()[0].1, expected);
+            }
+        }
+
+        #[test]
+        fn prediction_stops_at_star_impact() {
+            let stars = vec![
+                StarData::new([200.0, 0.0], 1.0, 1.0),
+                StarData::new([0.0, 0.0], 1000.0, 5.0),
+            ];
+            let start = launch([50.0, 0.0], [-1.0, 0.0]);
+            let trajectory = predict_trajectory(&PhysicsConfig::new(), start, &stars, 0.1, 10_000);
+            assert_eq!(trajectory.impact, Some(1));
+            assert!(trajectory.positions.len() < 10_000);
+            let [x, y] = *trajectory.positions.last().unwrap();
+            assert!(x.hypot(y) <= 5.0);
+        }
+
+        #[test]
+        fn prediction_stops_when_leaving_kill_zone() {
+            let arena = Arena::new([-10.0, -10.0], [10.0, 10.0], BoundaryMode::KillZone).unwrap();
+            let config = PhysicsConfig::new().with_arena(arena);
+            let trajectory =
+                predict_trajectory(&config, launch([0.0, 0.0], [3.0, 0.0]), &[], 1.0, 10);
+            assert_eq!(
+                trajectory.positions,
+                vec![[3.0, 0.0], [6.0, 0.0], [9.0, 0.0]]
+            );
+            assert_eq!(trajectory.impact, None);
+        }
     }
 }
@@ -1,187 +1,601 @@
 //! Data repository implementations for in-memory and persistent storage.
 
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::Entry, BTreeMap, HashMap},
+};
 
 use super::repo_interfaces::*;
+use crate::{entities::Real, Error, Result};
+
+mod map;
+mod snapshot;
+pub use map::Map;
+pub use snapshot::{Snapshot, SNAPSHOT_VERSION};
+
+/// Exact state of a moving object in fixed-point simulations
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct FixedMotion {
+    position: FixedVec2Data,
+    angle: FixedData,
+    velocity: FixedVec2Data,
+    acceleration: FixedVec2Data,
+}
+impl From<&MovingObject> for FixedMotion {
+    fn from(object: &MovingObject) -> Self {
+        Self {
+            position: object.position.convert(),
+            angle: object.angle.convert(),
+            velocity: object.velocity.convert(),
+            acceleration: object.acceleration.convert(),
+        }
+    }
+}
 
-const MISSILE_CAPACITY: usize = 5;
-const PLAYER_CAPACITY: usize = 2;
-
-type MissileState = MovingObject;
-
+/// State of a moving object
+///
+/// Once fixed-point values are written, the exact fixed-point state is kept next to the
+/// floating point values, which then only mirror it. Writing a floating point value replaces
+/// the fixed-point value only if it differs from the mirrored one, so reading and writing back
+/// unchanged values does not round the exact state.
 #[derive(Clone, Debug, Default, PartialEq)]
 struct MovingObject {
     position: Vec2Data,
-    angle: f32,
+    angle: Real,
     velocity: Vec2Data,
     acceleration: Vec2Data,
+    fixed: Option<FixedMotion>,
 }
 impl MovingObject {
     fn set_acceleration(&mut self, acceleration: Vec2Data) -> &mut Self {
+        if let Some(fixed) = self
+            .fixed
+            .as_mut()
+            .filter(|_| self.acceleration != acceleration)
+        {
+            fixed.acceleration = acceleration.convert();
+        }
         self.acceleration = acceleration;
         self
     }
     fn set_velocity(&mut self, velocity: Vec2Data) -> &mut Self {
+        if let Some(fixed) = self.fixed.as_mut().filter(|_| self.velocity != velocity) {
+            fixed.velocity = velocity.convert();
+        }
         self.velocity = velocity;
         self
     }
     fn set_position(&mut self, position: Vec2Data) -> &mut Self {
+        if let Some(fixed) = self.fixed.as_mut().filter(|_| self.position != position) {
+            fixed.position = position.convert();
+        }
         self.position = position;
         self
     }
-    fn set_angle(&mut self, angle: f32) -> &mut Self {
+    fn set_angle(&mut self, angle: Real) -> &mut Self {
+        if let Some(fixed) = self.fixed.as_mut().filter(|_| self.angle != angle) {
+            fixed.angle = angle.convert();
+        }
         self.angle = angle;
         self
     }
+    /// Exact fixed-point state, rounded from the floating point values if none was written
+    fn get_fixed(&self) -> FixedMotion {
+        self.fixed.unwrap_or_else(|| self.into())
+    }
+    fn fixed_mut(&mut self) -> &mut FixedMotion {
+        let fixed = self.get_fixed();
+        self.fixed.get_or_insert(fixed)
+    }
+    fn set_fixed_acceleration(&mut self, acceleration: FixedVec2Data) -> &mut Self {
+        self.fixed_mut().acceleration = acceleration;
+        self.acceleration = acceleration.convert();
+        self
+    }
+    fn set_fixed_velocity(&mut self, velocity: FixedVec2Data) -> &mut Self {
+        self.fixed_mut().velocity = velocity;
+        self.velocity = velocity.convert();
+        self
+    }
+    fn set_fixed_position(&mut self, position: FixedVec2Data) -> &mut Self {
+        self.fixed_mut().position = position;
+        self.position = position.convert();
+        self
+    }
+    fn set_fixed_angle(&mut self, angle: FixedData) -> &mut Self {
+        self.fixed_mut().angle = angle;
+        self.angle = angle.convert();
+        self
+    }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-struct PlayerState {
-    player_object: MovingObject,
-    missiles: Vec<MissileState>,
+/// State of a missile
+#[derive(Clone, Debug, Default, PartialEq)]
+struct MissileState {
+    missile_object: MovingObject,
+    /// Time since launch
+    age: Real,
 }
-// TODO: Set capacity of missile vector according to Missile config
-impl Default for PlayerState {
-    fn default() -> Self {
+impl From<MovingObject> for MissileState {
+    fn from(missile_object: MovingObject) -> Self {
         Self {
-            player_object: MovingObject::default(),
-            missiles: Vec::with_capacity(MISSILE_CAPACITY),
+            missile_object,
+            age: 0.0,
         }
     }
 }
 
+/// State of a player and its missiles
+///
+/// Missile IDs are handed out in increasing order and never reused, so they stay valid when
+/// other missiles are removed.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct PlayerState {
+    player_object: MovingObject,
+    missiles: BTreeMap<MissileIdData, MissileState>,
+    next_missile_id: MissileIdData,
+    /// Hit points lost
+    hull_damage: Real,
+    /// Shield strength lost
+    shield_damage: Real,
+    destroyed: bool,
+    /// Time since the ship was destroyed
+    time_since_destroyed: Real,
+    score: ScoreData,
+}
+
 pub struct GameState {
     stars: Vec<StarData>,
+    /// Prescribed motion of moving stars
+    star_motions: Vec<(StarId, StarMotion)>,
+    /// `(position, radius)` of all hazards
+    hazards: Vec<(Vec2Data, Real)>,
     player: HashMap<PlayerIdData, PlayerState>,
+    phase: MatchPhase,
+}
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 impl GameState {
+    /// Create an empty game without player and stars
     pub fn new() -> Self {
-        let mut state = Self {
-            player: HashMap::with_capacity(PLAYER_CAPACITY),
+        Self {
+            player: HashMap::new(),
             stars: Vec::new(),
-        };
-        state.add_player(1);
-        state.add_player(2);
+            star_motions: Vec::new(),
+            hazards: Vec::new(),
+            phase: MatchPhase::Lobby,
+        }
+    }
+
+    /// Create a game with default initialized player
+    #[cfg(test)]
+    pub(crate) fn with_players(ids: &[PlayerIdData]) -> Self {
+        let mut state = Self::new();
+        for id in ids {
+            state.add_player(*id).expect("Player ids must be unique");
+        }
         state
     }
 
-    /// Add new player
-    ///
-    /// Reset player data to defaults, if player id already exists.
-    fn add_player(&mut self, id: PlayerIdData) {
-        self.player.insert(id, PlayerState::default());
+    /// Add new player with default data
+    fn add_player(&mut self, id: PlayerIdData) -> Result<&mut PlayerState> {
+        match self.player.entry(id) {
+            Entry::Occupied(_) => Err(Error::PlayerAlreadyExists(id)),
+            Entry::Vacant(entry) => Ok(entry.insert(PlayerState::default())),
+        }
+    }
+
+    /// Remove a player including all of its missiles
+    fn remove_player(&mut self, id: &PlayerIdData) -> Result<PlayerState> {
+        self.player.remove(id).ok_or(Error::UnknownPlayer(*id))
+    }
+
+    fn get_player(&self, id: &PlayerIdData) -> Result<&PlayerState> {
+        self.player.get(id).ok_or(Error::UnknownPlayer(*id))
     }
 
-    // TODO: Propagate error
-    fn get_player(&self, id: &PlayerIdData) -> &PlayerState {
-        self.player.get(id).expect("Player not found")
+    fn get_player_mut(&mut self, id: &PlayerIdData) -> Result<&mut PlayerState> {
+        self.player.get_mut(id).ok_or(Error::UnknownPlayer(*id))
     }
 
-    // TODO: Propagate error
-    fn get_player_mut(&mut self, id: &PlayerIdData) -> &mut PlayerState {
-        self.player.get_mut(id).expect("Player not found")
+    /// Add a missile to a player and return its id
+    fn add_missile(
+        &mut self,
+        player_id: &PlayerIdData,
+        data: impl Into<MissileState>,
+    ) -> Result<MissileIdData> {
+        let player = self.get_player_mut(player_id)?;
+        let missile_id = player.next_missile_id;
+        player.next_missile_id += 1;
+        player.missiles.insert(missile_id, data.into());
+        Ok(missile_id)
     }
 
-    fn add_missile(&mut self, player_id: &PlayerIdData, data: MissileState) {
-        self.get_player_mut(player_id).missiles.push(data);
+    /// Remove a missile of a player. The ids of all other missiles remain unchanged.
+    fn remove_missile(
+        &mut self,
+        player_id: &PlayerIdData,
+        missile_id: MissileIdData,
+    ) -> Result<MissileState> {
+        self.get_player_mut(player_id)?
+            .missiles
+            .remove(&missile_id)
+            .ok_or(Error::UnknownMissile(*player_id, missile_id))
     }
 
     #[cfg(test)]
-    fn get_missile(&self, player_id: &PlayerIdData, missile_id: MissileIdData) -> &MissileState {
-        &self.get_player(player_id).missiles[missile_id]
+    fn get_missile(
+        &self,
+        player_id: &PlayerIdData,
+        missile_id: MissileIdData,
+    ) -> Result<&MissileState> {
+        self.get_player(player_id)?
+            .missiles
+            .get(&missile_id)
+            .ok_or(Error::UnknownMissile(*player_id, missile_id))
     }
 
     fn get_missile_mut(
         &mut self,
         player_id: &PlayerIdData,
         missile_id: MissileIdData,
-    ) -> &mut MissileState {
-        &mut self.get_player_mut(player_id).missiles[missile_id]
+    ) -> Result<&mut MissileState> {
+        self.get_player_mut(player_id)?
+            .missiles
+            .get_mut(&missile_id)
+            .ok_or(Error::UnknownMissile(*player_id, missile_id))
     }
 
     fn iter_player(&self) -> impl Iterator<Item = (PlayerId, &PlayerState)> {
         self.player.iter().map(|(id, data)| (*id, data))
     }
 
+    /// Iterate over all player whose ships are not destroyed
+    fn iter_active_player(&self) -> impl Iterator<Item = (PlayerId, &PlayerState)> {
+        self.iter_player().filter(|(_, player)| !player.destroyed)
+    }
+
     fn iter_missiles(&self) -> impl Iterator<Item = (PlayerId, MissileId, &MissileState)> {
         self.player.iter().flat_map(|(p_id, player)| {
             player
                 .missiles
                 .iter()
-                .enumerate()
-                .map(|(m_id, missile)| (*p_id, m_id, missile))
+                .map(|(m_id, missile)| (*p_id, *m_id, missile))
         })
     }
 
     /// Add a new Star
     #[cfg(test)]
-    fn add_star(&mut self, star: StarData) {
+    pub(crate) fn add_star(&mut self, star: StarData) {
         self.stars.push(star);
     }
 }
 
+impl PlayerManagementDataGateway for RefCell<GameState> {
+    fn get_players(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_player()
+            .map(|(id, player)| (id, player.player_object.position))
+            .collect())
+    }
+
+    fn add_player(&self, id: &PlayerIdData, position: Vec2Data, angle: Real) -> Result<()> {
+        self.borrow_mut()
+            .add_player(*id)?
+            .player_object
+            .set_position(position)
+            .set_angle(angle);
+        Ok(())
+    }
+
+    fn remove_player(&self, id: &PlayerIdData) -> Result<()> {
+        self.borrow_mut().remove_player(id)?;
+        Ok(())
+    }
+}
+
 impl PlayerMovementDataGateway for RefCell<GameState> {
-    fn get_player_orientation(&self, id: &PlayerIdData) -> f32 {
-        self.borrow().get_player(id).player_object.angle
+    fn get_player_orientation(&self, id: &PlayerIdData) -> Result<Real> {
+        Ok(self.borrow().get_player(id)?.player_object.angle)
     }
 
-    fn set_player_orientation(&self, id: &PlayerIdData, orientation: f32) {
+    fn set_player_orientation(&self, id: &PlayerIdData, orientation: Real) -> Result<()> {
         self.borrow_mut()
-            .get_player_mut(id)
+            .get_player_mut(id)?
             .player_object
             .set_angle(orientation);
+        Ok(())
     }
 
-    fn get_player_acceleration(&self, id: &PlayerIdData) -> Vec2Data {
-        self.borrow().get_player(id).player_object.acceleration
+    fn get_player_acceleration(&self, id: &PlayerIdData) -> Result<Vec2Data> {
+        Ok(self.borrow().get_player(id)?.player_object.acceleration)
     }
 
-    fn set_player_acceleration(&self, id: &PlayerIdData, acceleration: Vec2Data) {
+    fn set_player_acceleration(&self, id: &PlayerIdData, acceleration: Vec2Data) -> Result<()> {
         self.borrow_mut()
-            .get_player_mut(id)
+            .get_player_mut(id)?
             .player_object
             .set_acceleration(acceleration);
+        Ok(())
+    }
+
+    fn get_fixed_player_orientation(&self, id: &PlayerIdData) -> Result<FixedData> {
+        Ok(self
+            .borrow()
+            .get_player(id)?
+            .player_object
+            .get_fixed()
+            .angle)
+    }
+
+    fn set_fixed_player_orientation(
+        &self,
+        id: &PlayerIdData,
+        orientation: FixedData,
+    ) -> Result<()> {
+        self.borrow_mut()
+            .get_player_mut(id)?
+            .player_object
+            .set_fixed_angle(orientation);
+        Ok(())
+    }
+
+    fn get_fixed_player_acceleration(&self, id: &PlayerIdData) -> Result<FixedVec2Data> {
+        Ok(self
+            .borrow()
+            .get_player(id)?
+            .player_object
+            .get_fixed()
+            .acceleration)
+    }
+
+    fn set_fixed_player_acceleration(
+        &self,
+        id: &PlayerIdData,
+        acceleration: FixedVec2Data,
+    ) -> Result<()> {
+        self.borrow_mut()
+            .get_player_mut(id)?
+            .player_object
+            .set_fixed_acceleration(acceleration);
+        Ok(())
     }
 }
 
 impl ShootDataGateway for RefCell<GameState> {
-    fn get_player_pos_and_velocity(&self, id: &PlayerIdData) -> PlayerPosAndVelocityData {
-        // let state = self.borrow();
+    fn is_player_destroyed(&self, id: &PlayerIdData) -> Result<bool> {
+        Ok(self.borrow().get_player(id)?.destroyed)
+    }
+
+    fn get_player_pos_and_velocity(&self, id: &PlayerIdData) -> Result<PlayerPosAndVelocityData> {
         let MovingObject {
             position: pos,
             angle,
             velocity,
-            acceleration: _,
-        } = self.borrow().get_player(id).player_object;
-        PlayerPosAndVelocityData {
+            ..
+        } = self.borrow().get_player(id)?.player_object;
+        Ok(PlayerPosAndVelocityData {
             pos,
             angle,
             velocity,
-        }
+        })
     }
 
-    fn get_player_missile_count(&self, id: &PlayerIdData) -> usize {
-        self.borrow().get_player(id).missiles.len()
+    fn get_player_missile_count(&self, id: &PlayerIdData) -> Result<usize> {
+        Ok(self.borrow().get_player(id)?.missiles.len())
     }
 
-    fn create_missile_for_player(&self, id: &PlayerIdData, missile: MissileLaunchData) {
+    fn create_missile_for_player(
+        &self,
+        id: &PlayerIdData,
+        missile: MissileLaunchData,
+    ) -> Result<()> {
         let mut missile_obj = MovingObject::default();
         missile_obj
             .set_position(missile.pos)
             .set_angle(missile.angle)
             .set_velocity(missile.velocity);
-        self.borrow_mut().add_missile(id, missile_obj);
+        self.borrow_mut().add_missile(id, missile_obj)?;
+        Ok(())
+    }
+
+    fn get_fixed_player_pos_and_velocity(
+        &self,
+        id: &PlayerIdData,
+    ) -> Result<FixedPlayerPosAndVelocityData> {
+        let FixedMotion {
+            position: pos,
+            angle,
+            velocity,
+            ..
+        } = self.borrow().get_player(id)?.player_object.get_fixed();
+        Ok(FixedPlayerPosAndVelocityData {
+            pos,
+            angle,
+            velocity,
+        })
+    }
+
+    fn create_fixed_missile_for_player(
+        &self,
+        id: &PlayerIdData,
+        missile: FixedMissileLaunchData,
+    ) -> Result<()> {
+        let mut missile_obj = MovingObject::default();
+        missile_obj
+            .set_fixed_position(missile.pos)
+            .set_fixed_angle(missile.angle)
+            .set_fixed_velocity(missile.velocity);
+        self.borrow_mut().add_missile(id, missile_obj)?;
+        Ok(())
+    }
+
+    fn remove_missile(&self, id: &PlayerIdData, missile_id: &MissileIdData) -> Result<()> {
+        self.borrow_mut().remove_missile(id, *missile_id)?;
+        Ok(())
     }
 }
 
-impl GravityDataGateway for RefCell<GameState> {
-    fn get_stars_position_and_mass(&self) -> Vec<StarData> {
-        self.borrow().stars.clone()
+impl DamageDataGateway for RefCell<GameState> {
+    fn get_player_damage(&self) -> Result<Vec<(PlayerIdData, Real, Real)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
+            .map(|(id, player)| (id, player.hull_damage, player.shield_damage))
+            .collect())
+    }
+
+    fn set_player_damage(&self, updates: Vec<(PlayerIdData, Real, Real)>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (id, hull_damage, shield_damage) in updates {
+            let player = state.get_player_mut(&id)?;
+            player.hull_damage = hull_damage;
+            player.shield_damage = shield_damage;
+        }
+        Ok(())
+    }
+
+    fn set_player_destroyed(&self, ids: Vec<PlayerIdData>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for id in ids {
+            let player = state.get_player_mut(&id)?;
+            player.destroyed = true;
+            player.time_since_destroyed = 0.0;
+        }
+        Ok(())
+    }
+
+    fn destroy_missiles(&self, ids: Vec<(PlayerIdData, MissileIdData)>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (player_id, missile_id) in ids {
+            state.remove_missile(&player_id, missile_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl MatchDataGateway for RefCell<GameState> {
+    fn get_player_ids(&self) -> Result<Vec<PlayerIdData>> {
+        Ok(self.borrow().iter_player().map(|(id, _)| id).collect())
+    }
+
+    fn get_surviving_player_ids(&self) -> Result<Vec<PlayerIdData>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    fn get_score(&self) -> Result<Vec<(PlayerIdData, ScoreData)>> {
+        Ok(self
+            .borrow()
+            .iter_player()
+            .map(|(id, player)| (id, player.score))
+            .collect())
+    }
+
+    fn set_score(&self, updates: Vec<(PlayerIdData, ScoreData)>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (id, score) in updates {
+            state.get_player_mut(&id)?.score = score;
+        }
+        Ok(())
+    }
+
+    fn reset_score(&self) -> Result<()> {
+        self.borrow_mut()
+            .player
+            .values_mut()
+            .for_each(|player| player.score = ScoreData::default());
+        Ok(())
+    }
+
+    fn reset_round(&self, spawns: Vec<(PlayerIdData, Vec2Data, Real)>) -> Result<()> {
+        for (id, position, angle) in spawns {
+            self.respawn_player(&id, position, angle)?;
+        }
+        self.borrow_mut()
+            .player
+            .values_mut()
+            .for_each(|player| player.missiles.clear());
+        Ok(())
     }
 
-    fn get_player_pos_and_acc(&self) -> Vec<(PlayerIdData, Vec2Data, Vec2Data)> {
-        self.borrow()
+    fn get_phase(&self) -> Result<MatchPhase> {
+        Ok(self.borrow().phase)
+    }
+
+    fn set_phase(&self, phase: MatchPhase) -> Result<()> {
+        self.borrow_mut().phase = phase;
+        Ok(())
+    }
+}
+
+impl RespawnDataGateway for RefCell<GameState> {
+    fn get_destroyed_players(&self) -> Result<Vec<(PlayerIdData, Real)>> {
+        Ok(self
+            .borrow()
             .iter_player()
+            .filter(|(_, player)| player.destroyed)
+            .map(|(id, player)| (id, player.time_since_destroyed))
+            .collect())
+    }
+
+    fn set_time_since_destroyed(&self, updates: Vec<(PlayerIdData, Real)>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (id, time) in updates {
+            state.get_player_mut(&id)?.time_since_destroyed = time;
+        }
+        Ok(())
+    }
+
+    fn get_stars(&self) -> Result<Vec<StarData>> {
+        Ok(self.borrow().stars.clone())
+    }
+
+    fn get_active_player_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
+            .map(|(id, player)| (id, player.player_object.position))
+            .collect())
+    }
+
+    fn get_missile_owners_and_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_missiles()
+            .map(|(p_id, _, missile)| (p_id, missile.missile_object.position))
+            .collect())
+    }
+
+    fn respawn_player(&self, id: &PlayerIdData, position: Vec2Data, angle: Real) -> Result<()> {
+        let mut state = self.borrow_mut();
+        let player = state.get_player_mut(id)?;
+        player.player_object = MovingObject::default();
+        player.player_object.set_position(position).set_angle(angle);
+        player.hull_damage = 0.0;
+        player.shield_damage = 0.0;
+        player.destroyed = false;
+        player.time_since_destroyed = 0.0;
+        Ok(())
+    }
+}
+
+impl GravityDataGateway for RefCell<GameState> {
+    fn get_stars_position_and_mass(&self) -> Result<Vec<StarData>> {
+        Ok(self.borrow().stars.clone())
+    }
+
+    fn get_player_pos_and_acc(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
             .map(|(id, p_state)| {
                 (
                     id,
@@ -189,92 +603,461 @@ impl GravityDataGateway for RefCell<GameState> {
                     p_state.player_object.acceleration,
                 )
             })
-            .collect()
+            .collect())
     }
 
-    fn get_missile_pos_and_acc(&self) -> Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)> {
-        self.borrow()
+    fn get_missile_pos_and_acc(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_missiles()
+            .map(|(p_id, m_id, missile)| {
+                (
+                    p_id,
+                    m_id,
+                    missile.missile_object.position,
+                    missile.missile_object.acceleration,
+                )
+            })
+            .collect())
+    }
+
+    fn set_acceleration_for_player(&self, updates: Vec<(PlayerIdData, Vec2Data)>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        updates.into_iter().try_for_each(|(id, acceleration)| {
+            state
+                .get_player_mut(&id)?
+                .player_object
+                .set_acceleration(acceleration);
+            Ok(())
+        })
+    }
+
+    fn set_acceleration_for_missiles(
+        &self,
+        updates: Vec<(PlayerIdData, MissileIdData, Vec2Data)>,
+    ) -> Result<()> {
+        let mut state = self.borrow_mut();
+        updates
+            .into_iter()
+            .try_for_each(|(player_id, missile_id, acceleration)| {
+                state
+                    .get_missile_mut(&player_id, missile_id)?
+                    .missile_object
+                    .set_acceleration(acceleration);
+                Ok(())
+            })
+    }
+
+    fn get_fixed_player_pos_and_acc(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, FixedVec2Data, FixedVec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
+            .map(|(id, p_state)| {
+                let fixed = p_state.player_object.get_fixed();
+                (id, fixed.position, fixed.acceleration)
+            })
+            .collect())
+    }
+
+    fn get_fixed_missile_pos_and_acc(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, FixedVec2Data, FixedVec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_missiles()
+            .map(|(p_id, m_id, missile)| {
+                let fixed = missile.missile_object.get_fixed();
+                (p_id, m_id, fixed.position, fixed.acceleration)
+            })
+            .collect())
+    }
+
+    fn set_fixed_acceleration_for_player(
+        &self,
+        updates: Vec<(PlayerIdData, FixedVec2Data)>,
+    ) -> Result<()> {
+        let mut state = self.borrow_mut();
+        updates.into_iter().try_for_each(|(id, acceleration)| {
+            state
+                .get_player_mut(&id)?
+                .player_object
+                .set_fixed_acceleration(acceleration);
+            Ok(())
+        })
+    }
+
+    fn set_fixed_acceleration_for_missiles(
+        &self,
+        updates: Vec<(PlayerIdData, MissileIdData, FixedVec2Data)>,
+    ) -> Result<()> {
+        let mut state = self.borrow_mut();
+        updates
+            .into_iter()
+            .try_for_each(|(player_id, missile_id, acceleration)| {
+                state
+                    .get_missile_mut(&player_id, missile_id)?
+                    .missile_object
+                    .set_fixed_acceleration(acceleration);
+                Ok(())
+            })
+    }
+}
+
+impl IntegrateDataGateway for RefCell<GameState> {
+    fn get_star_info(&self) -> Result<Vec<StarData>> {
+        Ok(self.borrow().stars.clone())
+    }
+
+    fn get_player_info(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Vec2Data, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
+            .map(|(id, player)| {
+                (
+                    id,
+                    player.player_object.position,
+                    player.player_object.velocity,
+                    player.player_object.acceleration,
+                )
+            })
+            .collect())
+    }
+
+    fn get_missile_info(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_missiles()
+            .map(|(p_id, m_id, missile)| {
+                (
+                    p_id,
+                    m_id,
+                    missile.missile_object.position,
+                    missile.missile_object.velocity,
+                    missile.missile_object.acceleration,
+                )
+            })
+            .collect())
+    }
+
+    fn set_player_info(
+        &self,
+        data: Vec<(PlayerIdData, Vec2Data, Vec2Data, Vec2Data)>,
+    ) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (p_id, pos, vel, acc) in data {
+            state
+                .get_player_mut(&p_id)?
+                .player_object
+                .set_position(pos)
+                .set_velocity(vel)
+                .set_acceleration(acc);
+        }
+        Ok(())
+    }
+
+    fn set_missile_info(
+        &self,
+        data: Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data, Vec2Data)>,
+    ) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (player_id, missile_id, pos, vel, acc) in data {
+            state
+                .get_missile_mut(&player_id, missile_id)?
+                .missile_object
+                .set_position(pos)
+                .set_velocity(vel)
+                .set_acceleration(acc);
+        }
+        Ok(())
+    }
+
+    fn age_missiles(&self, delta_time: Real) -> Result<()> {
+        self.borrow_mut()
+            .player
+            .values_mut()
+            .flat_map(|player| player.missiles.values_mut())
+            .for_each(|missile| missile.age += delta_time);
+        Ok(())
+    }
+
+    fn get_fixed_player_info(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, FixedVec2Data, FixedVec2Data, FixedVec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
+            .map(|(id, player)| {
+                let fixed = player.player_object.get_fixed();
+                (id, fixed.position, fixed.velocity, fixed.acceleration)
+            })
+            .collect())
+    }
+
+    fn get_fixed_missile_info(
+        &self,
+    ) -> Result<
+        Vec<(
+            PlayerIdData,
+            MissileIdData,
+            FixedVec2Data,
+            FixedVec2Data,
+            FixedVec2Data,
+        )>,
+    > {
+        Ok(self
+            .borrow()
             .iter_missiles()
-            .map(|(p_id, m_id, missile)| (p_id, m_id, missile.position, missile.acceleration))
-            .collect()
+            .map(|(p_id, m_id, missile)| {
+                let fixed = missile.missile_object.get_fixed();
+                (
+                    p_id,
+                    m_id,
+                    fixed.position,
+                    fixed.velocity,
+                    fixed.acceleration,
+                )
+            })
+            .collect())
     }
 
-    fn set_acceleration_for_player(&self, updates: Vec<(PlayerIdData, Vec2Data)>) {
+    fn set_fixed_player_info(
+        &self,
+        data: Vec<(PlayerIdData, FixedVec2Data, FixedVec2Data, FixedVec2Data)>,
+    ) -> Result<()> {
         let mut state = self.borrow_mut();
-        updates.into_iter().for_each(|(id, acceleration)| {
+        for (p_id, pos, vel, acc) in data {
             state
-                .get_player_mut(&id)
+                .get_player_mut(&p_id)?
                 .player_object
+                .set_fixed_position(pos)
+                .set_fixed_velocity(vel)
+                .set_fixed_acceleration(acc);
+        }
+        Ok(())
+    }
+
+    fn set_fixed_missile_info(
+        &self,
+        data: Vec<(
+            PlayerIdData,
+            MissileIdData,
+            FixedVec2Data,
+            FixedVec2Data,
+            FixedVec2Data,
+        )>,
+    ) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (player_id, missile_id, pos, vel, acc) in data {
+            state
+                .get_missile_mut(&player_id, missile_id)?
+                .missile_object
+                .set_fixed_position(pos)
+                .set_fixed_velocity(vel)
+                .set_fixed_acceleration(acc);
+        }
+        Ok(())
+    }
+}
+
+impl CollisionDataGateway for RefCell<GameState> {
+    fn get_stars_position_and_radius(&self) -> Result<Vec<StarData>> {
+        Ok(self.borrow().stars.clone())
+    }
+
+    fn get_player_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
+            .map(|(id, player)| (id, player.player_object.position))
+            .collect())
+    }
+
+    fn get_missile_positions_and_ages(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Real)>> {
+        Ok(self
+            .borrow()
+            .iter_missiles()
+            .map(|(p_id, m_id, missile)| (p_id, m_id, missile.missile_object.position, missile.age))
+            .collect())
+    }
+
+    fn get_hazards(&self) -> Result<Vec<(Vec2Data, Real)>> {
+        Ok(self.borrow().hazards.clone())
+    }
+}
+
+impl ExpiryDataGateway for RefCell<GameState> {
+    fn get_missile_age_vel_and_acc(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, Real, Vec2Data, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_missiles()
+            .map(|(p_id, m_id, missile)| {
+                (
+                    p_id,
+                    m_id,
+                    missile.age,
+                    missile.missile_object.velocity,
+                    missile.missile_object.acceleration,
+                )
+            })
+            .collect())
+    }
+
+    fn set_missile_acc(&self, updates: Vec<(PlayerIdData, MissileIdData, Vec2Data)>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (player_id, missile_id, acceleration) in updates {
+            state
+                .get_missile_mut(&player_id, missile_id)?
+                .missile_object
                 .set_acceleration(acceleration);
-        });
+        }
+        Ok(())
+    }
+
+    fn remove_missiles(&self, ids: Vec<(PlayerIdData, MissileIdData)>) -> Result<()> {
+        let mut state = self.borrow_mut();
+        for (player_id, missile_id) in ids {
+            state.remove_missile(&player_id, missile_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl StarMotionDataGateway for RefCell<GameState> {
+    fn get_star_positions(&self) -> Result<Vec<(StarId, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .stars
+            .iter()
+            .enumerate()
+            .map(|(id, star)| (id, star.get_position()))
+            .collect())
+    }
+
+    fn get_star_motions(&self) -> Result<Vec<(StarId, StarMotion)>> {
+        Ok(self.borrow().star_motions.clone())
     }
 
-    fn set_acceleration_for_missiles(&self, updates: Vec<(PlayerIdData, MissileIdData, Vec2Data)>) {
+    fn set_star_positions(&self, updates: Vec<(StarId, Vec2Data)>) -> Result<()> {
         let mut state = self.borrow_mut();
-        updates
-            .into_iter()
-            .for_each(|(player_id, missile_id, acceleration)| {
-                state
-                    .get_missile_mut(&player_id, missile_id)
-                    .set_acceleration(acceleration);
-            });
+        for (id, position) in updates {
+            let star = state.stars.get_mut(id).ok_or(Error::UnknownStar(id))?;
+            *star = StarData::new(position, star.get_mass(), star.get_radius());
+        }
+        Ok(())
     }
 }
 
-impl IntegrateDataGateway for RefCell<GameState> {
-    fn get_player_info(&self) -> Vec<(PlayerIdData, Vec2Data, Vec2Data, Vec2Data)> {
-        self.borrow()
-            .iter_player()
+impl BoundaryDataGateway for RefCell<GameState> {
+    fn get_player_pos_and_vel(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Vec2Data)>> {
+        Ok(self
+            .borrow()
+            .iter_active_player()
             .map(|(id, player)| {
                 (
                     id,
                     player.player_object.position,
                     player.player_object.velocity,
-                    player.player_object.acceleration,
                 )
             })
-            .collect()
+            .collect())
     }
 
-    fn get_missile_info(&self) -> Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data, Vec2Data)> {
-        self.borrow()
+    fn get_missile_pos_and_vel(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>> {
+        Ok(self
+            .borrow()
             .iter_missiles()
             .map(|(p_id, m_id, missile)| {
                 (
                     p_id,
                     m_id,
-                    missile.position,
-                    missile.velocity,
-                    missile.acceleration,
+                    missile.missile_object.position,
+                    missile.missile_object.velocity,
                 )
             })
-            .collect()
+            .collect())
     }
 
-    fn set_player_info(&self, data: Vec<(PlayerIdData, Vec2Data, Vec2Data, Vec2Data)>) {
+    fn set_player_pos_and_vel(
+        &self,
+        updates: Vec<(PlayerIdData, Vec2Data, Vec2Data)>,
+    ) -> Result<()> {
         let mut state = self.borrow_mut();
-        for (p_id, pos, vel, acc) in data {
+        for (id, pos, vel) in updates {
             state
-                .get_player_mut(&p_id)
+                .get_player_mut(&id)?
                 .player_object
                 .set_position(pos)
-                .set_velocity(vel)
-                .set_acceleration(acc);
+                .set_velocity(vel);
         }
+        Ok(())
     }
 
-    fn set_missile_info(
+    fn set_missile_pos_and_vel(
         &self,
-        data: Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data, Vec2Data)>,
-    ) {
+        updates: Vec<(PlayerIdData, MissileIdData, Vec2Data, Vec2Data)>,
+    ) -> Result<()> {
         let mut state = self.borrow_mut();
-        for (player_id, missile_id, pos, vel, acc) in data {
+        for (player_id, missile_id, pos, vel) in updates {
             state
-                .get_missile_mut(&player_id, missile_id)
+                .get_missile_mut(&player_id, missile_id)?
+                .missile_object
                 .set_position(pos)
-                .set_velocity(vel)
-                .set_acceleration(acc);
+                .set_velocity(vel);
         }
+        Ok(())
+    }
+
+    fn destroy_players_out_of_bounds(&self, ids: Vec<PlayerIdData>) -> Result<()> {
+        self.set_player_destroyed(ids)
+    }
+
+    fn remove_missiles_out_of_bounds(&self, ids: Vec<(PlayerIdData, MissileIdData)>) -> Result<()> {
+        self.destroy_missiles(ids)
+    }
+}
+
+impl SimulationDataGateway for RefCell<GameState> {
+    fn get_player_pos_and_angle(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Real)>> {
+        Ok(self
+            .borrow()
+            .iter_player()
+            .map(|(id, player)| {
+                (
+                    id,
+                    player.player_object.position,
+                    player.player_object.angle,
+                )
+            })
+            .collect())
+    }
+
+    fn get_missile_pos_and_angle(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Real)>> {
+        Ok(self
+            .borrow()
+            .iter_missiles()
+            .map(|(p_id, m_id, missile)| {
+                (
+                    p_id,
+                    m_id,
+                    missile.missile_object.position,
+                    missile.missile_object.angle,
+                )
+            })
+            .collect())
     }
 }
 
@@ -282,20 +1065,94 @@ impl InGameState for RefCell<GameState> {}
 
 #[cfg(test)]
 mod test {
-    use std::cell::RefCell;
+    use std::{cell::RefCell, rc::Rc};
 
     use crate::{
-        physics::{GravityDataGateway, IntegrateDataGateway, StarData},
+        damage::{Damage, DamageConfig, DamageDataGateway},
+        entities::PI,
+        game_match::{Match, MatchConfig, MatchDataGateway, MatchPhase, ScoreData},
+        lobby::PlayerManagementDataGateway,
+        physics::{
+            BoundaryDataGateway, Collision, CollisionConfig, CollisionDataGateway, CollisionEvent,
+            ExpiryDataGateway, GravityDataGateway, Integrate, IntegrateDataGateway, PhysicsConfig,
+            StarData, StarMotionDataGateway,
+        },
+        respawn::RespawnDataGateway,
         user_input::{MissileLaunchData, PlayerMovementDataGateway, ShootDataGateway},
     };
 
     use super::{GameState, MissileState, MovingObject};
+    use crate::Error;
 
     #[test]
     fn new_player_has_no_missiles() {
         let mut state = GameState::new();
-        state.add_player(0);
-        assert_eq!(state.get_player(&0).missiles.len(), 0)
+        state.add_player(0).unwrap();
+        assert_eq!(state.get_player(&0).unwrap().missiles.len(), 0)
+    }
+
+    #[test]
+    fn unknown_player_returns_error() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        assert_eq!(
+            state.get_player_orientation(&3),
+            Err(Error::UnknownPlayer(3))
+        );
+        assert_eq!(
+            state.set_acceleration_for_player(vec![(3, [1.0, 0.0])]),
+            Err(Error::UnknownPlayer(3))
+        );
+    }
+
+    #[test]
+    fn unknown_missile_returns_error() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        assert_eq!(
+            state.remove_missile(&1, &0),
+            Err(Error::UnknownMissile(1, 0))
+        );
+        assert_eq!(
+            state.set_missile_info(vec![(1, 4, [0.0, 0.0], [0.0, 0.0], [0.0, 0.0])]),
+            Err(Error::UnknownMissile(1, 4))
+        );
+    }
+
+    //////////////////////////
+    // PlayerManagementDG impl
+    //////////////////////////
+    #[test]
+    fn player_added_at_position() {
+        let state = RefCell::new(GameState::new());
+        state.add_player(&3, [1.0, 2.0], 0.5).unwrap();
+
+        assert_eq!(state.get_players(), Ok(vec![(3, [1.0, 2.0])]));
+        assert_eq!(state.get_player_orientation(&3), Ok(0.5));
+        assert_eq!(
+            state.add_player(&3, [0.0, 0.0], 0.0),
+            Err(Error::PlayerAlreadyExists(3))
+        );
+    }
+
+    #[test]
+    fn removed_player_leaves_no_missiles() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        state
+            .borrow_mut()
+            .add_missile(&1, MissileState::default())
+            .unwrap();
+        state
+            .borrow_mut()
+            .add_missile(&2, MissileState::default())
+            .unwrap();
+
+        state.remove_player(&1).unwrap();
+
+        assert_eq!(state.get_players(), Ok(vec![(2, [0.0, 0.0])]));
+        assert_eq!(
+            state.get_missile_positions_and_ages(),
+            Ok(vec![(2, 0, [0.0, 0.0], 0.0)])
+        );
+        assert_eq!(state.remove_player(&1), Err(Error::UnknownPlayer(1)));
     }
 
     //////////////////////////
@@ -304,19 +1161,17 @@ mod test {
     #[test]
     fn angle_updated_correctly() {
         let angle = 3.0;
-        let state = RefCell::new(GameState::new());
-        state.borrow_mut().add_player(0);
-        state.set_player_orientation(&0, angle);
-        assert_eq!(state.get_player_orientation(&0), angle);
+        let state = RefCell::new(GameState::with_players(&[0]));
+        state.set_player_orientation(&0, angle).unwrap();
+        assert_eq!(state.get_player_orientation(&0).unwrap(), angle);
     }
 
     #[test]
     fn acceleration_set_correctly() {
         let acc = [3.0, 1000.0];
-        let state = RefCell::new(GameState::new());
-        state.borrow_mut().add_player(0);
-        state.set_player_acceleration(&0, acc);
-        assert_eq!(state.get_player_acceleration(&0), acc);
+        let state = RefCell::new(GameState::with_players(&[0]));
+        state.set_player_acceleration(&0, acc).unwrap();
+        assert_eq!(state.get_player_acceleration(&0).unwrap(), acc);
     }
 
     //////////////////////////
@@ -325,13 +1180,27 @@ mod test {
     #[test]
     fn player_pos_and_vel_retrieved_correctly() {
         let (pos, angle, vel) = ([0.0, 200.0], 4.0, [10.0, 4.0]);
-        let state = RefCell::new(GameState::new());
-        state.borrow_mut().add_player(0);
-        state.borrow_mut().get_player_mut(&0).player_object.position = pos;
-        state.borrow_mut().get_player_mut(&0).player_object.angle = angle;
-        state.borrow_mut().get_player_mut(&0).player_object.velocity = vel;
+        let state = RefCell::new(GameState::with_players(&[0]));
+        state
+            .borrow_mut()
+            .get_player_mut(&0)
+            .unwrap()
+            .player_object
+            .position = pos;
+        state
+            .borrow_mut()
+            .get_player_mut(&0)
+            .unwrap()
+            .player_object
+            .angle = angle;
+        state
+            .borrow_mut()
+            .get_player_mut(&0)
+            .unwrap()
+            .player_object
+            .velocity = vel;
 
-        let res = state.get_player_pos_and_velocity(&0);
+        let res = state.get_player_pos_and_velocity(&0).unwrap();
         assert_eq!(res.pos, pos);
         assert_eq!(res.angle, angle);
         assert_eq!(res.velocity, vel);
@@ -344,28 +1213,227 @@ mod test {
             angle: 2.0,
             velocity: [10.0, 40.0],
         };
-        let state = RefCell::new(GameState::new());
-        state.borrow_mut().add_player(0);
+        let state = RefCell::new(GameState::with_players(&[0]));
 
-        state.create_missile_for_player(&0, data);
+        state.create_missile_for_player(&0, data).unwrap();
 
         let state_ref = state.borrow();
-        assert_eq!(state_ref.get_player(&0).missiles.len(), 1);
-        let created_missile = state_ref.get_missile(&0, 0);
-        assert_eq!(created_missile.position, data.pos);
-        assert_eq!(created_missile.angle, data.angle);
-        assert_eq!(created_missile.velocity, data.velocity);
-        assert_eq!(created_missile.acceleration, [0.0, 0.0]);
+        assert_eq!(state_ref.get_player(&0).unwrap().missiles.len(), 1);
+        let created_missile = state_ref.get_missile(&0, 0).unwrap();
+        assert_eq!(created_missile.missile_object.position, data.pos);
+        assert_eq!(created_missile.missile_object.angle, data.angle);
+        assert_eq!(created_missile.missile_object.velocity, data.velocity);
+        assert_eq!(created_missile.missile_object.acceleration, [0.0, 0.0]);
     }
 
     #[test]
     fn missiles_counted_correctly() {
-        let state = RefCell::new(GameState::new());
-        state.borrow_mut().add_player(0);
+        let state = RefCell::new(GameState::with_players(&[0]));
         for _ in 0..4 {
-            state.borrow_mut().add_missile(&0, MissileState::default())
+            state
+                .borrow_mut()
+                .add_missile(&0, MissileState::default())
+                .unwrap();
+        }
+        assert_eq!(state.get_player_missile_count(&0).unwrap(), 4)
+    }
+
+    #[test]
+    fn missile_ids_stay_valid_after_removal() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        for x in [1.0, 2.0, 3.0] {
+            state
+                .borrow_mut()
+                .add_missile(
+                    &1,
+                    MovingObject::default().set_position([x, 0.0]).to_owned(),
+                )
+                .unwrap();
         }
-        assert_eq!(state.get_player_missile_count(&0), 4)
+
+        state.remove_missile(&1, &1).unwrap();
+
+        let state_ref = state.borrow();
+        assert_eq!(state_ref.get_player(&1).unwrap().missiles.len(), 2);
+        assert_eq!(
+            state_ref
+                .get_missile(&1, 0)
+                .unwrap()
+                .missile_object
+                .position,
+            [1.0, 0.0]
+        );
+        assert_eq!(
+            state_ref
+                .get_missile(&1, 2)
+                .unwrap()
+                .missile_object
+                .position,
+            [3.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn missile_ids_are_not_reused() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        let first = state
+            .borrow_mut()
+            .add_missile(&1, MissileState::default())
+            .unwrap();
+        state.remove_missile(&1, &first).unwrap();
+        let second = state
+            .borrow_mut()
+            .add_missile(&1, MissileState::default())
+            .unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(state.get_player_missile_count(&1).unwrap(), 1);
+    }
+
+    //////////////////////////
+    // DamageDG impl
+    //////////////////////////
+    #[test]
+    fn damage_stored_and_destroyed_player_hidden() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        state
+            .borrow_mut()
+            .add_missile(&2, MissileState::default())
+            .unwrap();
+
+        state.set_player_damage(vec![(1, 5.0, 2.0)]).unwrap();
+        state.set_player_destroyed(vec![2]).unwrap();
+        state.destroy_missiles(vec![(2, 0)]).unwrap();
+
+        assert_eq!(state.get_player_damage(), Ok(vec![(1, 5.0, 2.0)]));
+        assert_eq!(state.is_player_destroyed(&2), Ok(true));
+        assert_eq!(state.get_player_missile_count(&2), Ok(0));
+        assert_eq!(state.get_player_pos_and_acc().unwrap().len(), 1);
+        assert_eq!(
+            <RefCell<GameState> as IntegrateDataGateway>::get_player_info(&state)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(state.get_player_positions(), Ok(vec![(1, [0.0, 0.0])]));
+    }
+
+    //////////////////////////
+    // MatchDG impl
+    //////////////////////////
+    #[test]
+    fn new_round_restores_destroyed_ships() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        let score = ScoreData {
+            rounds_won: 1,
+            kills: 2,
+            deaths: 3,
+        };
+        state.set_score(vec![(2, score)]).unwrap();
+        state.set_player_damage(vec![(1, 5.0, 1.0)]).unwrap();
+        state.set_player_destroyed(vec![2]).unwrap();
+        assert_eq!(state.get_surviving_player_ids(), Ok(vec![1]));
+
+        state
+            .borrow_mut()
+            .add_missile(&1, MovingObject::default())
+            .unwrap();
+        state
+            .set_player_info(vec![(1, [5.0, 5.0], [1.0, 2.0], [3.0, 4.0])])
+            .unwrap();
+
+        state
+            .reset_round(vec![(1, [-10.0, 0.0], 0.0), (2, [10.0, 0.0], 3.0)])
+            .unwrap();
+
+        assert_eq!(state.get_player_missile_count(&1), Ok(0));
+        assert_eq!(
+            state.borrow().get_player(&1).unwrap().player_object,
+            MovingObject {
+                position: [-10.0, 0.0],
+                ..Default::default()
+            }
+        );
+        assert_eq!(state.get_player_orientation(&2), Ok(3.0));
+        let mut survivors = state.get_surviving_player_ids().unwrap();
+        survivors.sort();
+        assert_eq!(survivors, vec![1, 2]);
+        let mut damage = state.get_player_damage().unwrap();
+        damage.sort_by_key(|&(id, _, _)| id);
+        assert_eq!(damage, vec![(1, 0.0, 0.0), (2, 0.0, 0.0)]);
+        let mut scores = state.get_score().unwrap();
+        scores.sort_by_key(|&(id, _)| id);
+        assert_eq!(scores, vec![(1, ScoreData::default()), (2, score)]);
+    }
+
+    #[test]
+    fn ship_destroyed_in_star_survives_next_round() {
+        let mut game_state = GameState::with_players(&[1, 2]);
+        game_state.add_star(StarData::new([0.0, 0.0], 1000.0, 20.0));
+        let state = Rc::new(RefCell::new(game_state));
+        let collision = Collision::new(CollisionConfig::new(2.0, 1.0), state.clone());
+        let damage = Damage::new(DamageConfig::new(100.0, 30.0, 1000.0), state.clone());
+        let config = MatchConfig::new(3)
+            .with_spawn_point([-100.0, 0.0], 0.0)
+            .with_spawn_point([100.0, 0.0], PI);
+        let game = Match::new(config, state.clone());
+        let tick = || {
+            let destroyed = damage.execute(&collision.execute().unwrap(), 0.1).unwrap();
+            game.update(0.1, &destroyed).unwrap();
+        };
+        game.start().unwrap();
+        tick();
+        assert_eq!(game.get_phase(), Ok(MatchPhase::Playing));
+
+        state
+            .set_player_info(vec![(1, [5.0, 0.0], [0.0, 0.0], [0.0, 0.0])])
+            .unwrap();
+        let missile = MovingObject {
+            position: [-100.0, 0.0],
+            ..Default::default()
+        };
+        state.borrow_mut().add_missile(&2, missile).unwrap();
+        tick();
+        assert_eq!(state.is_player_destroyed(&1), Ok(true));
+        tick();
+        assert!(matches!(game.get_phase(), Ok(MatchPhase::Countdown { .. })));
+
+        tick();
+        assert_eq!(game.get_phase(), Ok(MatchPhase::Playing));
+        assert_eq!(state.is_player_destroyed(&1), Ok(false));
+        assert_eq!(state.get_player_positions().unwrap().len(), 2);
+        assert_eq!(state.get_player_missile_count(&2), Ok(0));
+    }
+
+    //////////////////////////
+    // RespawnDG impl
+    //////////////////////////
+    #[test]
+    fn respawned_ship_is_reset() {
+        let state = RefCell::new(GameState::with_players(&[1]));
+        state
+            .set_player_info(vec![(1, [5.0, 5.0], [1.0, 2.0], [3.0, 4.0])])
+            .unwrap();
+        state.set_player_damage(vec![(1, 8.0, 2.0)]).unwrap();
+        state.set_player_destroyed(vec![1]).unwrap();
+        state.set_time_since_destroyed(vec![(1, 1.5)]).unwrap();
+        assert_eq!(state.get_destroyed_players(), Ok(vec![(1, 1.5)]));
+        assert_eq!(state.get_active_player_positions(), Ok(vec![]));
+
+        state.respawn_player(&1, [10.0, 0.0], 1.0).unwrap();
+
+        assert_eq!(state.get_destroyed_players(), Ok(vec![]));
+        assert_eq!(state.get_player_damage(), Ok(vec![(1, 0.0, 0.0)]));
+        assert_eq!(
+            state.borrow().get_player(&1).unwrap().player_object,
+            MovingObject {
+                position: [10.0, 0.0],
+                angle: 1.0,
+                velocity: [0.0, 0.0],
+                acceleration: [0.0, 0.0],
+                fixed: None,
+            }
+        );
     }
 
     //////////////////////////
@@ -373,13 +1441,13 @@ mod test {
     //////////////////////////
     #[test]
     fn stars_correctly_returned() {
-        let state = RefCell::new(GameState::new());
-        let star1 = StarData::new([1.0, 0.0], 1.0);
-        let star2 = StarData::new([0.0, 1.0], 2.0);
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        let star1 = StarData::new([1.0, 0.0], 1.0, 0.5);
+        let star2 = StarData::new([0.0, 1.0], 2.0, 0.5);
         state.borrow_mut().add_star(star1);
         state.borrow_mut().add_star(star2);
 
-        let stars = state.get_stars_position_and_mass();
+        let stars = state.get_stars_position_and_mass().unwrap();
 
         assert_eq!(stars.len(), 2);
         assert_eq!(stars[0], star1);
@@ -388,22 +1456,24 @@ mod test {
 
     #[test]
     fn player_pos_and_acc_corectly_returned() {
-        let state = RefCell::new(GameState::new());
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
         {
             let mut state_ref = state.borrow_mut();
             state_ref
                 .get_player_mut(&1)
+                .unwrap()
                 .player_object
                 .set_position([4.0, 1.0])
                 .set_acceleration([2.0, 1.0]);
             state_ref
                 .get_player_mut(&2)
+                .unwrap()
                 .player_object
                 .set_position([1.0, 4.0])
                 .set_acceleration([1.0, 2.0]);
         }
 
-        let result = state.get_player_pos_and_acc();
+        let result = state.get_player_pos_and_acc().unwrap();
 
         assert_eq!(result.len(), 2);
         for id_pos_acc in result {
@@ -417,22 +1487,25 @@ mod test {
 
     #[test]
     fn missile_pos_and_acc_correctly_returned() {
-        let state = RefCell::new(GameState::new());
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
         for (p_id, pos, acc) in [
             (1, [0.0, 0.0], [0.0, 0.0]),
             (1, [1.0, 0.0], [0.0, 1.0]),
             (2, [0.0, 1.0], [1.0, 0.0]),
         ] {
-            state.borrow_mut().add_missile(
-                &p_id,
-                MovingObject::default()
-                    .set_position(pos)
-                    .set_acceleration(acc)
-                    .to_owned(),
-            );
+            state
+                .borrow_mut()
+                .add_missile(
+                    &p_id,
+                    MovingObject::default()
+                        .set_position(pos)
+                        .set_acceleration(acc)
+                        .to_owned(),
+                )
+                .unwrap();
         }
 
-        let result = state.get_missile_pos_and_acc();
+        let result = state.get_missile_pos_and_acc().unwrap();
 
         assert_eq!(result.len(), 3);
         for item in result {
@@ -453,40 +1526,71 @@ mod test {
 
     #[test]
     fn player_acc_correclty_updated() {
-        let state = RefCell::new(GameState::new());
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
 
-        state.set_acceleration_for_player(vec![(1, [20.0, 10.0]), (2, [10.0, 20.0])]);
-        state.set_acceleration_for_player(vec![(1, [5.0, 2.0])]);
+        state
+            .set_acceleration_for_player(vec![(1, [20.0, 10.0]), (2, [10.0, 20.0])])
+            .unwrap();
+        state
+            .set_acceleration_for_player(vec![(1, [5.0, 2.0])])
+            .unwrap();
 
         let state_ref = state.borrow();
         assert_eq!(
-            state_ref.get_player(&1).player_object.acceleration,
+            state_ref.get_player(&1).unwrap().player_object.acceleration,
             [5.0, 2.0]
         );
         assert_eq!(
-            state_ref.get_player(&2).player_object.acceleration,
+            state_ref.get_player(&2).unwrap().player_object.acceleration,
             [10.0, 20.0]
         );
     }
 
     #[test]
     fn missiles_correctly_updated() {
-        let state = RefCell::new(GameState::new());
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
         for p_id in &[1, 1, 2] {
             state
                 .borrow_mut()
-                .add_missile(p_id, MovingObject::default());
+                .add_missile(p_id, MovingObject::default())
+                .unwrap();
         }
 
-        state.set_acceleration_for_missiles(vec![
-            (1, 0, [20.0, 10.0]),
-            (1, 1, [40.0, 10.0]),
-            (2, 0, [10.0, 20.0]),
-        ]);
+        state
+            .set_acceleration_for_missiles(vec![
+                (1, 0, [20.0, 10.0]),
+                (1, 1, [40.0, 10.0]),
+                (2, 0, [10.0, 20.0]),
+            ])
+            .unwrap();
 
-        assert_eq!(state.borrow().get_missile(&1, 0).acceleration, [20.0, 10.0]);
-        assert_eq!(state.borrow().get_missile(&1, 1).acceleration, [40.0, 10.0]);
-        assert_eq!(state.borrow().get_missile(&2, 0).acceleration, [10.0, 20.0]);
+        assert_eq!(
+            state
+                .borrow()
+                .get_missile(&1, 0)
+                .unwrap()
+                .missile_object
+                .acceleration,
+            [20.0, 10.0]
+        );
+        assert_eq!(
+            state
+                .borrow()
+                .get_missile(&1, 1)
+                .unwrap()
+                .missile_object
+                .acceleration,
+            [40.0, 10.0]
+        );
+        assert_eq!(
+            state
+                .borrow()
+                .get_missile(&2, 0)
+                .unwrap()
+                .missile_object
+                .acceleration,
+            [10.0, 20.0]
+        );
     }
 
     //////////////////////////
@@ -494,10 +1598,11 @@ mod test {
     //////////////////////////
     #[test]
     fn player_pos_vel_and_acc_corectly_returned() {
-        let state = RefCell::new(GameState::new());
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
         state
             .borrow_mut()
             .get_player_mut(&1)
+            .unwrap()
             .player_object
             .set_position([4.0, 1.0])
             .set_velocity([4.0, 1.0])
@@ -505,12 +1610,13 @@ mod test {
         state
             .borrow_mut()
             .get_player_mut(&2)
+            .unwrap()
             .player_object
             .set_position([1.0, 4.0])
             .set_velocity([1.0, 5.0])
             .set_acceleration([1.0, 2.0]);
 
-        let result = <RefCell<GameState> as IntegrateDataGateway>::get_player_info(&state);
+        let result = <RefCell<GameState> as IntegrateDataGateway>::get_player_info(&state).unwrap();
 
         assert_eq!(result.len(), 2);
         for id_pos_acc in result {
@@ -524,17 +1630,20 @@ mod test {
 
     #[test]
     fn missile_pos_vel_and_acc_correctly_returned() {
-        let state = RefCell::new(GameState::new());
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
         {
             let mut state = state.borrow_mut();
             for (pid, y) in [(1, 1.0), (1, 2.0), (2, 3.0)] {
-                state.add_missile(
-                    &pid,
-                    MovingObject::default().set_position([0.0, y]).to_owned(),
-                );
+                state
+                    .add_missile(
+                        &pid,
+                        MovingObject::default().set_position([0.0, y]).to_owned(),
+                    )
+                    .unwrap();
             }
         }
-        let result = <RefCell<GameState> as IntegrateDataGateway>::get_missile_info(&state);
+        let result =
+            <RefCell<GameState> as IntegrateDataGateway>::get_missile_info(&state).unwrap();
         for (p_id, m_id, pos, _, _) in result {
             match (p_id, m_id) {
                 (1, 0) => assert_eq!(pos, [0.0, 1.0]),
@@ -547,27 +1656,33 @@ mod test {
 
     #[test]
     fn missile_pos_vel_and_acc_set_correctly() {
-        let state = RefCell::new(GameState::new());
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
         {
             let mut state = state.borrow_mut();
             for pid in &[1, 1, 2] {
-                state.add_missile(pid, MovingObject::default());
+                state.add_missile(pid, MovingObject::default()).unwrap();
             }
         }
-        state.set_missile_info(vec![
-            (1, 0, [1.0, 2.0], [1.0, 1.0], [2.0, 2.0]),
-            (2, 0, [2.0, 1.0], [2.0, 3.0], [4.0, 5.0]),
-        ]);
+        state
+            .set_missile_info(vec![
+                (1, 0, [1.0, 2.0], [1.0, 1.0], [2.0, 2.0]),
+                (2, 0, [2.0, 1.0], [2.0, 3.0], [4.0, 5.0]),
+            ])
+            .unwrap();
         for p_id in [1, 2] {
             for (
-                m_id,
-                &MovingObject {
-                    position,
-                    velocity,
-                    acceleration,
-                    angle: _,
+                &m_id,
+                &MissileState {
+                    missile_object:
+                        MovingObject {
+                            position,
+                            velocity,
+                            acceleration,
+                            ..
+                        },
+                    age: _,
                 },
-            ) in state.borrow().get_player(&p_id).missiles.iter().enumerate()
+            ) in state.borrow().get_player(&p_id).unwrap().missiles.iter()
             {
                 match (p_id, m_id) {
                     (1, 0) => assert_eq!(
@@ -589,4 +1704,166 @@ mod test {
             }
         }
     }
+
+    //////////////////////////
+    // StarMotionDG impl
+    //////////////////////////
+    #[test]
+    fn star_positions_updated() {
+        let state = RefCell::new(GameState::new());
+        state
+            .borrow_mut()
+            .add_star(StarData::new([1.0, 0.0], 1.0, 0.5));
+        state
+            .borrow_mut()
+            .add_star(StarData::new([0.0, 1.0], 2.0, 0.5));
+
+        state.set_star_positions(vec![(1, [5.0, 5.0])]).unwrap();
+
+        assert_eq!(
+            state.get_stars_position_and_mass(),
+            Ok(vec![
+                StarData::new([1.0, 0.0], 1.0, 0.5),
+                StarData::new([5.0, 5.0], 2.0, 0.5)
+            ])
+        );
+        assert_eq!(
+            state.set_star_positions(vec![(2, [0.0, 0.0])]),
+            Err(Error::UnknownStar(2))
+        );
+    }
+
+    //////////////////////////
+    // BoundaryDG impl
+    //////////////////////////
+    #[test]
+    fn objects_out_of_bounds_removed() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        state
+            .borrow_mut()
+            .add_missile(&2, MissileState::default())
+            .unwrap();
+        state
+            .set_player_pos_and_vel(vec![(1, [3.0, 4.0], [1.0, 0.0])])
+            .unwrap();
+        state
+            .set_missile_pos_and_vel(vec![(2, 0, [5.0, 6.0], [0.0, 1.0])])
+            .unwrap();
+        assert_eq!(
+            state.get_missile_pos_and_vel(),
+            Ok(vec![(2, 0, [5.0, 6.0], [0.0, 1.0])])
+        );
+
+        state.destroy_players_out_of_bounds(vec![2]).unwrap();
+        state.remove_missiles_out_of_bounds(vec![(2, 0)]).unwrap();
+        assert_eq!(
+            state.get_player_pos_and_vel(),
+            Ok(vec![(1, [3.0, 4.0], [1.0, 0.0])])
+        );
+        assert_eq!(state.get_missile_pos_and_vel(), Ok(vec![]));
+    }
+
+    //////////////////////////
+    // ExpiryDG impl
+    //////////////////////////
+    #[test]
+    fn missile_acc_updated_and_expired_missiles_removed() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        for _ in 0..3 {
+            state
+                .borrow_mut()
+                .add_missile(&1, MissileState::default())
+                .unwrap();
+        }
+
+        state.set_missile_acc(vec![(1, 0, [1.0, 0.0])]).unwrap();
+        state.age_missiles(2.0).unwrap();
+        state.remove_missiles(vec![(1, 1)]).unwrap();
+
+        let mut result = state.get_missile_age_vel_and_acc().unwrap();
+        result.sort_by_key(|&(p_id, m_id, _, _, _)| (p_id, m_id));
+        assert_eq!(
+            result,
+            vec![
+                (1, 0, 2.0, [0.0, 0.0], [1.0, 0.0]),
+                (1, 2, 2.0, [0.0, 0.0], [0.0, 0.0])
+            ]
+        );
+    }
+
+    //////////////////////////
+    // CollisionDG impl
+    //////////////////////////
+    #[test]
+    fn player_positions_correctly_returned() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        state
+            .borrow_mut()
+            .get_player_mut(&1)
+            .unwrap()
+            .player_object
+            .set_position([4.0, 1.0]);
+        state
+            .borrow_mut()
+            .get_player_mut(&2)
+            .unwrap()
+            .player_object
+            .set_position([1.0, 4.0]);
+
+        let mut result = state.get_player_positions().unwrap();
+        result.sort_by_key(|&(id, _)| id);
+
+        assert_eq!(result, vec![(1, [4.0, 1.0]), (2, [1.0, 4.0])]);
+    }
+
+    #[test]
+    fn missile_positions_correctly_returned() {
+        let state = RefCell::new(GameState::with_players(&[1, 2]));
+        for (p_id, pos, age) in [
+            (1, [1.0, 0.0], 0.5),
+            (1, [2.0, 0.0], 0.0),
+            (2, [3.0, 0.0], 1.5),
+        ] {
+            let missile = MissileState {
+                missile_object: MovingObject::default().set_position(pos).to_owned(),
+                age,
+            };
+            state.borrow_mut().add_missile(&p_id, missile).unwrap();
+        }
+
+        let mut result = state.get_missile_positions_and_ages().unwrap();
+        result.sort_by_key(|&(p_id, m_id, _, _)| (p_id, m_id));
+
+        assert_eq!(
+            result,
+            vec![
+                (1, 0, [1.0, 0.0], 0.5),
+                (1, 1, [2.0, 0.0], 0.0),
+                (2, 0, [3.0, 0.0], 1.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn owner_grace_ends_without_expiry() {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1])));
+        state
+            .borrow_mut()
+            .add_missile(&1, MovingObject::default())
+            .unwrap();
+        let config = CollisionConfig::new(2.0, 1.0).with_owner_grace(0.5);
+        let collision = Collision::new(config, state.clone());
+        let integrate = Integrate::new(PhysicsConfig::new(), state.clone());
+
+        integrate.execute(0.3).unwrap();
+        assert_eq!(collision.execute(), Ok(vec![]));
+        integrate.execute(0.3).unwrap();
+        assert_eq!(
+            collision.execute(),
+            Ok(vec![CollisionEvent::MissileHitPlayer {
+                missile: (1, 0),
+                player: 1
+            }])
+        );
+    }
 }
@@ -0,0 +1,529 @@
+//! Map file format
+//!
+//! A map is a line based text file. Empty lines and everything after a `#` are ignored. Every
+//! other line declares a single element of the map by a keyword followed by numbers separated by
+//! whitespace:
+//!
+//! ```text
+//! # keyword  parameters
+//! arena      min_x min_y max_x max_y   # at most once
+//! star       x y mass radius
+//! spawn      x y [angle]               # angle in degree, defaults to 0
+//! hazard     x y radius                # damages ships within the radius
+//! ```
+//!
+//! Stars are numbered in order of declaration starting at 0. The following keywords let the most
+//! recently declared star move, angles are in degree:
+//!
+//! ```text
+//! orbit      center_x center_y semi_major_axis eccentricity period [periapsis_angle phase]
+//! satellite  parent semi_major_axis eccentricity period [periapsis_angle phase]
+//! path       period x1 y1 [x2 y2 ...]
+//! ```
+//!
+//! A satellite orbits a previously declared parent star.
+//!
+//! The following keywords tune the physics of the map, each at most once:
+//!
+//! ```text
+//! gravity    constant
+//! softening  length
+//! max_speed  speed
+//! substeps   count
+//! ```
+//!
+//! [`GameState::from_map`] sets up stars and hazards. The arena and the spawn points configure
+//! the use-cases by means of [`Map::make_physics`], [`Map::make_lobby_config`],
+//! [`Map::make_match_config`] and [`Map::make_respawn_config`].
+
+use std::io::Read;
+
+use super::GameState;
+use crate::{
+    entities::Real,
+    game_match::MatchConfig,
+    lobby::LobbyConfig,
+    physics::{Arena, BoundaryMode, PhysicsConfig},
+    repo_interfaces::{StarData, StarId, StarMotion, Vec2Data},
+    respawn::RespawnConfig,
+    Error, Result,
+};
+
+/// Keywords that tune the physics
+const PHYSICS_KEYWORDS: [&str; 4] = ["gravity", "softening", "max_speed", "substeps"];
+
+/// Level description with stars, arena bounds, spawn points and hazards
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Map {
+    arena: Option<(Vec2Data, Vec2Data)>,
+    stars: Vec<StarData>,
+    star_motions: Vec<(StarId, StarMotion)>,
+    spawn_points: Vec<(Vec2Data, Real)>,
+    hazards: Vec<(Vec2Data, Real)>,
+    physics: PhysicsConfig,
+}
+
+impl Map {
+    /// Parse a map from its textual representation
+    ///
+    /// Fails with [`Error::MapParse`] pointing to the first invalid line.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut map = Map::default();
+        let mut spawn_lines = Vec::new();
+        let mut physics_keywords = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let content = line.split('#').next().unwrap_or_default();
+            let mut tokens = content.split_whitespace();
+            let Some(keyword) = tokens.next() else {
+                continue;
+            };
+            let values = tokens
+                .map(|token| {
+                    token
+                        .parse::<Real>()
+                        .ok()
+                        .filter(|value| value.is_finite())
+                        .ok_or_else(|| {
+                            parse_error(line_number, format!("`{token}` is not a finite number"))
+                        })
+                })
+                .collect::<Result<Vec<Real>>>()?;
+            if PHYSICS_KEYWORDS.contains(&keyword) {
+                if physics_keywords.contains(&keyword) {
+                    return Err(parse_error(
+                        line_number,
+                        format!("`{keyword}` declared twice"),
+                    ));
+                }
+                physics_keywords.push(keyword);
+            }
+            map.add_element(line_number, keyword, &values)?;
+            if keyword == "spawn" {
+                spawn_lines.push(line_number);
+            }
+        }
+        map.validate_spawn_points(&spawn_lines)?;
+        Ok(map)
+    }
+
+    /// Read and parse a map
+    pub fn read(reader: &mut dyn Read) -> Result<Self> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|err| Error::Io(err.to_string()))?;
+        Self::parse(&text)
+    }
+
+    /// Lower left and upper right corner of the arena, if the arena is bounded
+    pub fn get_arena(&self) -> Option<(Vec2Data, Vec2Data)> {
+        self.arena
+    }
+
+    /// All stars of the map
+    pub fn get_stars(&self) -> &[StarData] {
+        &self.stars
+    }
+
+    /// `(id, motion)` of all moving stars
+    pub fn get_star_motions(&self) -> &[(StarId, StarMotion)] {
+        &self.star_motions
+    }
+
+    /// `(position, orientation)` of all spawn points, orientation in radians
+    pub fn get_spawn_points(&self) -> &[(Vec2Data, Real)] {
+        &self.spawn_points
+    }
+
+    /// `(position, radius)` of all hazards
+    pub fn get_hazards(&self) -> &[(Vec2Data, Real)] {
+        &self.hazards
+    }
+
+    /// Default physics configuration tuned by the map
+    pub fn get_physics(&self) -> PhysicsConfig {
+        self.physics
+    }
+
+    /// Physics configuration tuned by the map, bounded by its arena with `mode` at the bounds
+    pub fn make_physics(&self, mode: BoundaryMode) -> Result<PhysicsConfig> {
+        match self.arena {
+            Some((min, max)) => Ok(self.physics.with_arena(Arena::new(min, max, mode)?)),
+            None => Ok(self.physics),
+        }
+    }
+
+    /// Lobby configuration with the spawn points of the map
+    pub fn make_lobby_config(&self, max_players: usize) -> LobbyConfig {
+        self.spawn_points
+            .iter()
+            .fold(LobbyConfig::new(max_players), |config, &(pos, angle)| {
+                config.with_spawn_point(pos, angle)
+            })
+    }
+
+    /// Match configuration that starts every round on the spawn points of the map
+    pub fn make_match_config(&self, rounds_to_win: u32) -> MatchConfig {
+        self.spawn_points
+            .iter()
+            .fold(MatchConfig::new(rounds_to_win), |config, &(pos, angle)| {
+                config.with_spawn_point(pos, angle)
+            })
+    }
+
+    /// Respawn configuration with the spawn points and the physics of the map
+    ///
+    /// Use [`RespawnConfig::with_physics`] to rate spawn points within the arena of
+    /// [`Self::make_physics`].
+    pub fn make_respawn_config(
+        &self,
+        delay: impl Into<Real>,
+        safe_distance: impl Into<Real>,
+        prediction_time: impl Into<Real>,
+    ) -> RespawnConfig {
+        self.spawn_points.iter().fold(
+            RespawnConfig::new(delay, safe_distance, prediction_time).with_physics(self.physics),
+            |config, &(pos, _)| config.with_spawn_point(pos),
+        )
+    }
+
+    /// Add a single element declared in line `line_number`
+    fn add_element(&mut self, line_number: usize, keyword: &str, values: &[Real]) -> Result<()> {
+        let expect_count = |counts: &[usize]| {
+            if counts.contains(&values.len()) {
+                Ok(())
+            } else {
+                Err(parse_error(
+                    line_number,
+                    format!(
+                        "`{keyword}` expects {} values, got {}",
+                        counts
+                            .iter()
+                            .map(|count| count.to_string())
+                            .collect::<Vec<_>>()
+                            .join(" or "),
+                        values.len()
+                    ),
+                ))
+            }
+        };
+        match keyword {
+            "arena" => {
+                expect_count(&[4])?;
+                if self.arena.is_some() {
+                    return Err(parse_error(line_number, "arena declared twice"));
+                }
+                let (min, max) = ([values[0], values[1]], [values[2], values[3]]);
+                if min[0] >= max[0] || min[1] >= max[1] {
+                    return Err(parse_error(line_number, "arena has no extent"));
+                }
+                self.arena = Some((min, max));
+            }
+            "star" => {
+                expect_count(&[4])?;
+                let (mass, radius) = (values[2], values[3]);
+                if mass < 0.0 || radius <= 0.0 {
+                    return Err(parse_error(
+                        line_number,
+                        "star needs a non-negative mass and a positive radius",
+                    ));
+                }
+                self.stars
+                    .push(StarData::new([values[0], values[1]], mass, radius));
+            }
+            "orbit" => {
+                expect_count(&[5, 7])?;
+                let motion = kepler_motion([values[0], values[1]], None, &values[2..]);
+                self.add_star_motion(line_number, motion)?;
+            }
+            "satellite" => {
+                expect_count(&[4, 6])?;
+                let parent = values[0] as StarId;
+                if values[0] != parent as Real || parent + 1 >= self.stars.len() {
+                    return Err(parse_error(
+                        line_number,
+                        "parent must be a previously declared star",
+                    ));
+                }
+                let motion = kepler_motion([0.0, 0.0], Some(parent), &values[1..]);
+                self.add_star_motion(line_number, motion)?;
+            }
+            "path" => {
+                if values.len() < 3 || values.len().is_multiple_of(2) {
+                    return Err(parse_error(
+                        line_number,
+                        "`path` expects a period followed by pairs of coordinates",
+                    ));
+                }
+                let motion = StarMotion::Path {
+                    waypoints: values[1..].chunks(2).map(|xy| [xy[0], xy[1]]).collect(),
+                    period: values[0],
+                };
+                self.add_star_motion(line_number, motion)?;
+            }
+            "spawn" => {
+                expect_count(&[2, 3])?;
+                let angle = values.get(2).copied().unwrap_or_default().to_radians();
+                self.spawn_points.push(([values[0], values[1]], angle));
+            }
+            "hazard" => {
+                expect_count(&[3])?;
+                if values[2] <= 0.0 {
+                    return Err(parse_error(line_number, "hazard needs a positive radius"));
+                }
+                self.hazards.push(([values[0], values[1]], values[2]));
+            }
+            "gravity" | "softening" | "max_speed" => {
+                expect_count(&[1])?;
+                let value = values[0];
+                self.physics = match keyword {
+                    "gravity" if value > 0.0 => self.physics.with_gravity_constant(value),
+                    "softening" if value >= 0.0 => self.physics.with_softening(value),
+                    "max_speed" if value > 0.0 => self.physics.with_max_speed(value),
+                    _ => {
+                        return Err(parse_error(
+                            line_number,
+                            format!("`{keyword}` out of range"),
+                        ))
+                    }
+                };
+            }
+            "substeps" => {
+                expect_count(&[1])?;
+                let substeps = values[0] as u32;
+                if values[0] != substeps as Real || substeps == 0 {
+                    return Err(parse_error(
+                        line_number,
+                        "substeps must be a positive integer",
+                    ));
+                }
+                self.physics = self.physics.with_substeps(substeps);
+            }
+            _ => {
+                return Err(parse_error(
+                    line_number,
+                    format!("unknown keyword `{keyword}`"),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Let the most recently declared star move
+    fn add_star_motion(&mut self, line_number: usize, motion: StarMotion) -> Result<()> {
+        let Some(id) = self.stars.len().checked_sub(1) else {
+            return Err(parse_error(line_number, "motion declared before any star"));
+        };
+        if self.star_motions.iter().any(|(star, _)| *star == id) {
+            return Err(parse_error(line_number, "star already has a motion"));
+        }
+        motion.validate().map_err(|_| {
+            parse_error(
+                line_number,
+                "motion needs a non-negative size, an eccentricity in [0, 1) and a positive period",
+            )
+        })?;
+        self.star_motions.push((id, motion));
+        Ok(())
+    }
+
+    /// Ensure that all spawn points lie within the arena
+    ///
+    /// `spawn_lines` holds the line number of each spawn point.
+    fn validate_spawn_points(&self, spawn_lines: &[usize]) -> Result<()> {
+        let Some((min, max)) = self.arena else {
+            return Ok(());
+        };
+        match self.spawn_points.iter().position(|(pos, _)| {
+            pos[0] < min[0] || pos[0] > max[0] || pos[1] < min[1] || pos[1] > max[1]
+        }) {
+            Some(index) => Err(parse_error(
+                spawn_lines[index],
+                "spawn point lies outside of the arena",
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl GameState {
+    /// Create a game with the stars and hazards of a map but without player
+    pub fn from_map(map: &Map) -> Self {
+        let mut state = Self::new();
+        state.stars = map.stars.clone();
+        state.star_motions = map.star_motions.clone();
+        state.hazards = map.hazards.clone();
+        state
+    }
+}
+
+/// Keplerian motion from `[semi_major_axis, eccentricity, period, periapsis_angle, phase]`, the
+/// angles being optional
+fn kepler_motion(center: Vec2Data, parent: Option<StarId>, values: &[Real]) -> StarMotion {
+    StarMotion::Kepler {
+        center,
+        parent,
+        semi_major_axis: values[0],
+        eccentricity: values[1],
+        period: values[2],
+        periapsis_angle: values.get(3).copied().unwrap_or_default().to_radians(),
+        phase: values.get(4).copied().unwrap_or_default().to_radians(),
+    }
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> Error {
+    Error::MapParse {
+        line,
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GameState, Map};
+    use crate::{
+        entities::Real,
+        physics::{Arena, BoundaryMode, PhysicsConfig, StarData, StarMotion},
+        Error,
+    };
+
+    const MAP: &str = "
+        # Binary system
+        arena -100 -100 100 100
+        star -20 0 500 5
+        star  20 0 500 5   # second sun
+        star  60 0 10 1
+        satellite 1 40 0 10
+        star  0 90 1 1
+        path  4 0 90 10 90
+
+        spawn -80 0 90
+        spawn  80 0
+        hazard 0 50 10
+
+        gravity   2.5
+        substeps  4
+    ";
+
+    #[test]
+    fn map_parsed() {
+        let map = Map::parse(MAP).unwrap();
+        assert_eq!(map.get_arena(), Some(([-100.0, -100.0], [100.0, 100.0])));
+        assert_eq!(
+            map.get_stars(),
+            &[
+                StarData::new([-20.0, 0.0], 500.0, 5.0),
+                StarData::new([20.0, 0.0], 500.0, 5.0),
+                StarData::new([60.0, 0.0], 10.0, 1.0),
+                StarData::new([0.0, 90.0], 1.0, 1.0)
+            ]
+        );
+        assert_eq!(
+            map.get_star_motions(),
+            &[
+                (
+                    2,
+                    StarMotion::Kepler {
+                        center: [0.0, 0.0],
+                        parent: Some(1),
+                        semi_major_axis: 40.0,
+                        eccentricity: 0.0,
+                        period: 10.0,
+                        periapsis_angle: 0.0,
+                        phase: 0.0
+                    }
+                ),
+                (
+                    3,
+                    StarMotion::Path {
+                        waypoints: vec![[0.0, 90.0], [10.0, 90.0]],
+                        period: 4.0
+                    }
+                )
+            ]
+        );
+        assert_eq!(
+            map.get_spawn_points(),
+            &[([-80.0, 0.0], Real::to_radians(90.0)), ([80.0, 0.0], 0.0)]
+        );
+        assert_eq!(map.get_hazards(), &[([0.0, 50.0], 10.0)]);
+        assert_eq!(
+            map.get_physics(),
+            PhysicsConfig::new()
+                .with_gravity_constant(2.5)
+                .with_substeps(4)
+        );
+    }
+
+    #[test]
+    fn game_state_built_from_map() {
+        let state = GameState::from_map(&Map::parse(MAP).unwrap());
+        assert_eq!(state.stars.len(), 4);
+        assert_eq!(state.star_motions.len(), 2);
+        assert_eq!(state.hazards, vec![([0.0, 50.0], 10.0)]);
+        assert!(state.player.is_empty());
+    }
+
+    #[test]
+    fn configs_built_from_map() {
+        let map = Map::parse(MAP).unwrap();
+        let physics = map.make_physics(BoundaryMode::Wrap).unwrap();
+        assert_eq!(
+            physics.get_arena(),
+            Some(Arena::new([-100.0, -100.0], [100.0, 100.0], BoundaryMode::Wrap).unwrap())
+        );
+        assert_eq!(physics.get_substeps(), 4);
+        assert_eq!(
+            map.make_lobby_config(4).get_spawn_points(),
+            map.get_spawn_points()
+        );
+        assert_eq!(
+            map.make_match_config(3).get_spawn_points(),
+            map.get_spawn_points()
+        );
+        let respawn = map.make_respawn_config(1.0, 50.0, 2.0);
+        assert_eq!(respawn.get_spawn_points(), vec![[-80.0, 0.0], [80.0, 0.0]]);
+        assert_eq!(respawn.get_physics(), map.get_physics());
+        assert_eq!(
+            Map::parse("star 0 0 1 1")
+                .unwrap()
+                .make_physics(BoundaryMode::Reflect)
+                .unwrap()
+                .get_arena(),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_errors_report_line_number() {
+        let parse_error = |text: &str| match Map::parse(text) {
+            Err(Error::MapParse { line, .. }) => line,
+            result => panic!("Expected parse error, got {result:?}"),
+        };
+        assert_eq!(parse_error("star 0 0 1 1\nplanet 0 0"), 2);
+        assert_eq!(parse_error("\n\nstar 0 0 1"), 3);
+        assert_eq!(parse_error("star 0 0 heavy 1"), 1);
+        assert_eq!(parse_error("star 0 0 1 -1"), 1);
+        assert_eq!(parse_error("arena 0 0 1 1\narena 0 0 2 2"), 2);
+        assert_eq!(parse_error("spawn 0 0 NaN"), 1);
+        assert_eq!(parse_error("orbit 0 0 1 0 1"), 1);
+        assert_eq!(parse_error("star 0 0 1 1\norbit 0 0 1 1.5 1"), 2);
+        assert_eq!(parse_error("star 0 0 1 1\nsatellite 0 1 0 1"), 2);
+        assert_eq!(parse_error("star 0 0 1 1\npath 1 0 0\npath 1 0 0"), 3);
+        assert_eq!(parse_error("gravity 1\ngravity 2"), 2);
+        assert_eq!(parse_error("softening -1"), 1);
+        assert_eq!(parse_error("substeps 1.5"), 1);
+    }
+
+    #[test]
+    fn spawn_points_must_lie_in_arena() {
+        assert_eq!(
+            Map::parse("spawn 5 5\nspawn 20 0\narena 0 0 10 10"),
+            Err(Error::MapParse {
+                line: 2,
+                message: "spawn point lies outside of the arena".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,694 @@
+//! Versioned snapshot format for [`GameState`]
+//!
+//! A snapshot is a line based text file. The first line names the format and its version, the
+//! last line holds a checksum of all preceding lines. Every line in between describes a single
+//! object by its kind followed by `key=value` pairs, e.g.
+//!
+//! ```text
+//! icorb-snapshot version=4
+//! star pos=0,0 mass=1000 radius=10
+//! star pos=0,50 mass=10 radius=1
+//! orbit star=1 center=0,0 parent=0 a=50 e=0.1 period=20 periapsis=0 phase=1.57
+//! path star=2 period=8 waypoints=0,0;10,0;10,10
+//! hazard pos=0,80 radius=5
+//! match phase=round_over remaining=1.5 winner=1
+//! player id=1 pos=100,0 angle=0 vel=0,3 acc=0,0 next_missile=1 hull_damage=0 shield_damage=0 destroyed=false time_since_destroyed=0 rounds_won=0 kills=0 deaths=0
+//! missile player=1 id=0 pos=110,0 angle=1.5 vel=0,20 acc=0,0 age=0.5
+//! checksum fnv1a=<64 bit FNV-1a hash of all preceding bytes in hex>
+//! ```
+//!
+//! The `parent` field of an orbit and the `winner` of a drawn round are omitted. The `match`
+//! record holds the [`MatchPhase`] with the `remaining` time of a countdown or round over phase.
+//!
+//! Version 1 did not know `orbit` and `path` records, version 2 did not know `hazard` records and
+//! version 3 did not know `match` records. Snapshots without a `match` record are restored to the
+//! lobby. Records unknown to the version of a snapshot are rejected.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    io::{Read, Write},
+};
+
+use super::{GameState, MissileState, MovingObject, PlayerState};
+use crate::{
+    entities::Real,
+    repo_interfaces::{
+        MatchPhase, MissileIdData, PlayerIdData, ScoreData, StarData, StarId, StarMotion, Vec2Data,
+    },
+    Error, Result,
+};
+
+/// Identifier in the first line of every snapshot
+const FORMAT_NAME: &str = "icorb-snapshot";
+/// Version written by this implementation. All older versions can be read.
+pub const SNAPSHOT_VERSION: u32 = 4;
+
+/// Save and restore the complete game state
+pub trait Snapshot: Sized {
+    /// Serialize into the current snapshot format
+    fn to_snapshot(&self) -> String;
+
+    /// Restore from a snapshot
+    ///
+    /// Fails with [`Error::UnsupportedSnapshotVersion`] for snapshots of future versions and
+    /// with [`Error::CorruptSnapshot`] if the checksum does not match or a line can't be parsed.
+    fn from_snapshot(snapshot: &str) -> Result<Self>;
+
+    /// Write snapshot to `writer`
+    fn save_snapshot(&self, writer: &mut dyn Write) -> Result<()> {
+        writer
+            .write_all(self.to_snapshot().as_bytes())
+            .map_err(|err| Error::Io(err.to_string()))
+    }
+
+    /// Read snapshot from `reader`
+    fn load_snapshot(reader: &mut dyn Read) -> Result<Self> {
+        let mut snapshot = String::new();
+        reader
+            .read_to_string(&mut snapshot)
+            .map_err(|err| Error::Io(err.to_string()))?;
+        Self::from_snapshot(&snapshot)
+    }
+}
+
+impl Snapshot for GameState {
+    fn to_snapshot(&self) -> String {
+        let mut lines = vec![format!("{FORMAT_NAME} version={SNAPSHOT_VERSION}")];
+        for star in &self.stars {
+            lines.push(format!(
+                "star pos={} mass={} radius={}",
+                format_vec2(star.get_position()),
+                star.get_mass(),
+                star.get_radius()
+            ));
+        }
+        for (id, motion) in &self.star_motions {
+            lines.push(match motion {
+                StarMotion::Kepler {
+                    center,
+                    parent,
+                    semi_major_axis,
+                    eccentricity,
+                    period,
+                    periapsis_angle,
+                    phase,
+                } => format!(
+                    "orbit star={id} center={}{} a={semi_major_axis} e={eccentricity} \
+                     period={period} periapsis={periapsis_angle} phase={phase}",
+                    format_vec2(*center),
+                    parent.map_or(String::new(), |parent| format!(" parent={parent}")),
+                ),
+                StarMotion::Path { waypoints, period } => format!(
+                    "path star={id} period={period} waypoints={}",
+                    waypoints
+                        .iter()
+                        .map(|point| format_vec2(*point))
+                        .collect::<Vec<_>>()
+                        .join(";")
+                ),
+            });
+        }
+        for (pos, radius) in &self.hazards {
+            lines.push(format!("hazard pos={} radius={radius}", format_vec2(*pos)));
+        }
+        lines.push(format!("match {}", format_match_phase(self.phase)));
+        let mut player: Vec<_> = self.player.iter().collect();
+        player.sort_by_key(|(id, _)| **id);
+        for (id, player) in &player {
+            lines.push(format!(
+                "player id={id} {} next_missile={} hull_damage={} shield_damage={} destroyed={} \
+                 time_since_destroyed={} rounds_won={} kills={} deaths={}",
+                format_moving_object(&player.player_object),
+                player.next_missile_id,
+                player.hull_damage,
+                player.shield_damage,
+                player.destroyed,
+                player.time_since_destroyed,
+                player.score.rounds_won,
+                player.score.kills,
+                player.score.deaths,
+            ));
+        }
+        for (p_id, player) in &player {
+            for (m_id, missile) in &player.missiles {
+                lines.push(format!(
+                    "missile player={p_id} id={m_id} {} age={}",
+                    format_moving_object(&missile.missile_object),
+                    missile.age
+                ));
+            }
+        }
+        let mut snapshot = lines.join("\n");
+        snapshot.push('\n');
+        let checksum = fnv1a(snapshot.as_bytes());
+        snapshot.push_str(&format!("checksum fnv1a={checksum:016x}\n"));
+        snapshot
+    }
+
+    fn from_snapshot(snapshot: &str) -> Result<Self> {
+        let mut lines = snapshot
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line));
+
+        let (_, header) = lines
+            .next()
+            .ok_or_else(|| corrupt(1, "snapshot is empty"))?;
+        let version = parse_header(header)?;
+        if !(1..=SNAPSHOT_VERSION).contains(&version) {
+            return Err(Error::UnsupportedSnapshotVersion(version));
+        }
+        verify_checksum(snapshot)?;
+
+        let mut state = GameState::new();
+        let mut phase = None;
+        for (line_number, line) in lines {
+            let mut record = Record::parse(line_number, line)?;
+            if introduced_in(record.kind) > version {
+                return Err(corrupt(
+                    line_number,
+                    format!("`{}` record not allowed in version {version}", record.kind),
+                ));
+            }
+            match record.kind {
+                "star" => state.stars.push(StarData::new(
+                    record.take("pos")?,
+                    record.take("mass")?,
+                    record.take("radius")?,
+                )),
+                "orbit" => {
+                    let id = record.take("star")?;
+                    let motion = StarMotion::Kepler {
+                        center: record.take("center")?,
+                        parent: record.take_optional("parent")?,
+                        semi_major_axis: record.take("a")?,
+                        eccentricity: record.take("e")?,
+                        period: record.take("period")?,
+                        periapsis_angle: record.take("periapsis")?,
+                        phase: record.take("phase")?,
+                    };
+                    state.add_star_motion(line_number, id, motion)?;
+                }
+                "path" => {
+                    let id = record.take("star")?;
+                    let motion = StarMotion::Path {
+                        period: record.take("period")?,
+                        waypoints: record.take_with("waypoints", |value| {
+                            value.split(';').map(Vec2Data::parse_field).collect()
+                        })?,
+                    };
+                    state.add_star_motion(line_number, id, motion)?;
+                }
+                "hazard" => state
+                    .hazards
+                    .push((record.take("pos")?, record.take("radius")?)),
+                "match" => {
+                    if phase.replace(record.take_match_phase()?).is_some() {
+                        return Err(corrupt(line_number, "duplicate match record"));
+                    }
+                }
+                "player" => {
+                    let id: PlayerIdData = record.take("id")?;
+                    let player = PlayerState {
+                        player_object: record.take_moving_object()?,
+                        missiles: BTreeMap::new(),
+                        next_missile_id: record.take("next_missile")?,
+                        hull_damage: record.take("hull_damage")?,
+                        shield_damage: record.take("shield_damage")?,
+                        destroyed: record.take("destroyed")?,
+                        time_since_destroyed: record.take("time_since_destroyed")?,
+                        score: ScoreData {
+                            rounds_won: record.take("rounds_won")?,
+                            kills: record.take("kills")?,
+                            deaths: record.take("deaths")?,
+                        },
+                    };
+                    if state.player.insert(id, player).is_some() {
+                        return Err(corrupt(line_number, format!("duplicate player {id}")));
+                    }
+                }
+                "missile" => {
+                    let p_id: PlayerIdData = record.take("player")?;
+                    let m_id: MissileIdData = record.take("id")?;
+                    let missile = MissileState {
+                        missile_object: record.take_moving_object()?,
+                        age: record.take("age")?,
+                    };
+                    let player = state.player.get_mut(&p_id).ok_or_else(|| {
+                        corrupt(line_number, format!("missile of unknown player {p_id}"))
+                    })?;
+                    if m_id >= player.next_missile_id
+                        || player.missiles.insert(m_id, missile).is_some()
+                    {
+                        return Err(corrupt(line_number, format!("invalid missile id {m_id}")));
+                    }
+                }
+                "checksum" => break,
+                kind => return Err(corrupt(line_number, format!("unknown record `{kind}`"))),
+            }
+            record.finish()?;
+        }
+        state.phase = phase.unwrap_or_default();
+        Ok(state)
+    }
+}
+
+impl GameState {
+    /// Add the motion of a previously restored star
+    fn add_star_motion(
+        &mut self,
+        line_number: usize,
+        id: StarId,
+        motion: StarMotion,
+    ) -> Result<()> {
+        let parent_valid = match motion {
+            StarMotion::Kepler {
+                parent: Some(parent),
+                ..
+            } => parent < self.stars.len() && parent != id,
+            _ => true,
+        };
+        if id >= self.stars.len()
+            || !parent_valid
+            || self.star_motions.iter().any(|(star, _)| *star == id)
+            || motion.validate().is_err()
+        {
+            return Err(corrupt(line_number, format!("invalid motion of star {id}")));
+        }
+        self.star_motions.push((id, motion));
+        Ok(())
+    }
+}
+
+/// Parse the header line and return the snapshot version
+fn parse_header(header: &str) -> Result<u32> {
+    let mut record = Record::parse(1, header)?;
+    if record.kind != FORMAT_NAME {
+        return Err(corrupt(1, "not an icorb snapshot"));
+    }
+    let version = record.take("version")?;
+    record.finish()?;
+    Ok(version)
+}
+
+/// First snapshot version that knows records of `kind`
+fn introduced_in(kind: &str) -> u32 {
+    match kind {
+        "orbit" | "path" => 2,
+        "hazard" => 3,
+        "match" => 4,
+        _ => 1,
+    }
+}
+
+/// Check that the last line holds the checksum of all preceding lines
+fn verify_checksum(snapshot: &str) -> Result<()> {
+    let content = snapshot.trim_end_matches('\n');
+    let (body, last_line) = match content.rfind('\n') {
+        Some(index) => content.split_at(index + 1),
+        None => ("", content),
+    };
+    let line_number = body.lines().count() + 1;
+    let mut record = Record::parse(line_number, last_line)?;
+    if record.kind != "checksum" {
+        return Err(corrupt(line_number, "checksum missing"));
+    }
+    let expected = record.take_with("fnv1a", |value| u64::from_str_radix(value, 16).ok())?;
+    record.finish()?;
+    if fnv1a(body.as_bytes()) != expected {
+        return Err(corrupt(line_number, "checksum mismatch"));
+    }
+    Ok(())
+}
+
+/// A single line of a snapshot
+struct Record<'a> {
+    line_number: usize,
+    kind: &'a str,
+    fields: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Record<'a> {
+    /// Split a line into kind and `key=value` pairs
+    fn parse(line_number: usize, line: &'a str) -> Result<Self> {
+        let mut tokens = line.split_whitespace();
+        let kind = tokens
+            .next()
+            .ok_or_else(|| corrupt(line_number, "empty line"))?;
+        let mut fields = HashMap::new();
+        for token in tokens {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or_else(|| corrupt(line_number, format!("malformed field `{token}`")))?;
+            if fields.insert(key, value).is_some() {
+                return Err(corrupt(line_number, format!("duplicate field `{key}`")));
+            }
+        }
+        Ok(Record {
+            line_number,
+            kind,
+            fields,
+        })
+    }
+
+    /// Remove a field and parse its value
+    fn take<T: ParseField>(&mut self, key: &str) -> Result<T> {
+        self.take_with(key, T::parse_field)
+    }
+
+    /// Remove a field if present and parse its value
+    fn take_optional<T: ParseField>(&mut self, key: &str) -> Result<Option<T>> {
+        if self.fields.contains_key(key) {
+            self.take(key).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Remove a field and parse its value with `parse`
+    fn take_with<T>(&mut self, key: &str, parse: impl Fn(&str) -> Option<T>) -> Result<T> {
+        let value = self
+            .fields
+            .remove(key)
+            .ok_or_else(|| corrupt(self.line_number, format!("missing field `{key}`")))?;
+        parse(value).ok_or_else(|| {
+            corrupt(
+                self.line_number,
+                format!("invalid value `{value}` of field `{key}`"),
+            )
+        })
+    }
+
+    /// Remove the fields of a [`MovingObject`]
+    fn take_moving_object(&mut self) -> Result<MovingObject> {
+        Ok(MovingObject {
+            position: self.take("pos")?,
+            angle: self.take("angle")?,
+            velocity: self.take("vel")?,
+            acceleration: self.take("acc")?,
+            fixed: None,
+        })
+    }
+
+    /// Remove the fields of a [`MatchPhase`]
+    fn take_match_phase(&mut self) -> Result<MatchPhase> {
+        let phase: String = self.take("phase")?;
+        Ok(match phase.as_str() {
+            "lobby" => MatchPhase::Lobby,
+            "countdown" => MatchPhase::Countdown {
+                remaining: self.take("remaining")?,
+            },
+            "playing" => MatchPhase::Playing,
+            "round_over" => MatchPhase::RoundOver {
+                remaining: self.take("remaining")?,
+                winner: self.take_optional("winner")?,
+            },
+            "match_over" => MatchPhase::MatchOver {
+                winner: self.take("winner")?,
+            },
+            _ => {
+                return Err(corrupt(
+                    self.line_number,
+                    format!("unknown match phase `{phase}`"),
+                ))
+            }
+        })
+    }
+
+    /// Ensure that all fields were used
+    fn finish(self) -> Result<()> {
+        match self.fields.keys().min() {
+            Some(key) => Err(corrupt(self.line_number, format!("unknown field `{key}`"))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Values that can be stored in a snapshot field
+///
+/// Implemented for all [`std::str::FromStr`] types used in snapshots.
+trait ParseField: Sized {
+    fn parse_field(value: &str) -> Option<Self>;
+}
+
+/// Implement [`ParseField`] by means of [`str::parse`]
+macro_rules! parse_field_from_str {
+    ($($type:ty),*) => {
+        $(impl ParseField for $type {
+            fn parse_field(value: &str) -> Option<Self> {
+                value.parse().ok()
+            }
+        })*
+    };
+}
+parse_field_from_str!(Real, u32, usize, bool, String);
+
+impl ParseField for Vec2Data {
+    fn parse_field(value: &str) -> Option<Self> {
+        let (x, y) = value.split_once(',')?;
+        Some([x.parse().ok()?, y.parse().ok()?])
+    }
+}
+
+fn format_vec2(vec: Vec2Data) -> String {
+    format!("{},{}", vec[0], vec[1])
+}
+
+fn format_moving_object(object: &MovingObject) -> String {
+    format!(
+        "pos={} angle={} vel={} acc={}",
+        format_vec2(object.position),
+        object.angle,
+        format_vec2(object.velocity),
+        format_vec2(object.acceleration)
+    )
+}
+
+fn format_match_phase(phase: MatchPhase) -> String {
+    match phase {
+        MatchPhase::Lobby => "phase=lobby".into(),
+        MatchPhase::Countdown { remaining } => format!("phase=countdown remaining={remaining}"),
+        MatchPhase::Playing => "phase=playing".into(),
+        MatchPhase::RoundOver { remaining, winner } => format!(
+            "phase=round_over remaining={remaining}{}",
+            winner.map_or(String::new(), |winner| format!(" winner={winner}"))
+        ),
+        MatchPhase::MatchOver { winner } => format!("phase=match_over winner={winner}"),
+    }
+}
+
+fn corrupt(line_number: usize, message: impl Display) -> Error {
+    Error::CorruptSnapshot(format!("line {line_number}: {message}"))
+}
+
+/// 64 bit FNV-1a hash
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use super::{GameState, MissileState, MovingObject, Snapshot, SNAPSHOT_VERSION};
+    use crate::{
+        damage::DamageDataGateway,
+        game_match::{MatchDataGateway, MatchPhase},
+        physics::{StarData, StarMotion},
+        repo_interfaces::ScoreData,
+        Error,
+    };
+
+    fn setup_snapshot_test() -> GameState {
+        let mut state = GameState::with_players(&[2, 1]);
+        state.add_star(StarData::new([0.0, 0.0], 1000.0, 10.0));
+        state.add_star(StarData::new([-50.5, 1e-3], 0.1, 0.25));
+        state.add_star(StarData::new([10.0, 10.0], 1.0, 0.5));
+        state.star_motions = vec![
+            (1, StarMotion::circular([0.0, 0.0], 50.5, 20.0, 0.1)),
+            (
+                2,
+                StarMotion::Kepler {
+                    center: [1.0, -1.0],
+                    parent: Some(1),
+                    semi_major_axis: 3.0,
+                    eccentricity: 0.25,
+                    period: 2.0,
+                    periapsis_angle: 1.0,
+                    phase: 0.5,
+                },
+            ),
+            (
+                0,
+                StarMotion::Path {
+                    waypoints: vec![[0.0, 0.0], [5.0, 0.0]],
+                    period: 3.0,
+                },
+            ),
+        ];
+        state.hazards.push(([0.0, 80.0], 5.0));
+        state.phase = MatchPhase::RoundOver {
+            remaining: 1.5,
+            winner: Some(1),
+        };
+        state.get_player_mut(&1).unwrap().player_object = MovingObject {
+            position: [100.0, 0.0],
+            angle: 0.1,
+            velocity: [0.0, 3.3],
+            acceleration: [-0.1, 1.0 / 3.0],
+            fixed: None,
+        };
+        for age in [0.5, 1.5, 2.5] {
+            let missile = MissileState {
+                missile_object: MovingObject::default().set_position([age, 0.0]).to_owned(),
+                age,
+            };
+            state.add_missile(&1, missile).unwrap();
+        }
+        state.remove_missile(&1, 1).unwrap();
+
+        let state = RefCell::new(state);
+        state.set_player_damage(vec![(2, 12.5, 3.0)]).unwrap();
+        state.set_player_destroyed(vec![2]).unwrap();
+        state
+            .set_score(vec![(
+                1,
+                ScoreData {
+                    rounds_won: 2,
+                    kills: 3,
+                    deaths: 1,
+                },
+            )])
+            .unwrap();
+        state.into_inner()
+    }
+
+    #[test]
+    fn snapshot_restores_game_state() {
+        let state = setup_snapshot_test();
+
+        let snapshot = state.to_snapshot();
+        let restored = GameState::from_snapshot(&snapshot).unwrap();
+
+        assert_eq!(restored.stars, state.stars);
+        assert_eq!(restored.star_motions, state.star_motions);
+        assert_eq!(restored.hazards, state.hazards);
+        assert_eq!(restored.phase, state.phase);
+        assert_eq!(restored.player, state.player);
+        assert_eq!(restored.to_snapshot(), snapshot);
+    }
+
+    #[test]
+    fn snapshot_written_and_read() {
+        let state = setup_snapshot_test();
+        let mut buffer = Vec::new();
+        state.save_snapshot(&mut buffer).unwrap();
+        let restored = GameState::load_snapshot(&mut buffer.as_slice()).unwrap();
+        assert_eq!(restored.player, state.player);
+    }
+
+    #[test]
+    fn modified_snapshot_rejected() {
+        let snapshot = setup_snapshot_test()
+            .to_snapshot()
+            .replace("mass=1000", "mass=1001");
+        assert!(matches!(
+            GameState::from_snapshot(&snapshot),
+            Err(Error::CorruptSnapshot(msg)) if msg.ends_with("checksum mismatch")
+        ));
+    }
+
+    #[test]
+    fn truncated_snapshot_rejected() {
+        let snapshot = setup_snapshot_test().to_snapshot();
+        let truncated = &snapshot[..snapshot.find("\nmissile").unwrap()];
+        assert!(matches!(
+            GameState::from_snapshot(truncated),
+            Err(Error::CorruptSnapshot(msg)) if msg.ends_with("checksum missing")
+        ));
+        assert!(matches!(
+            GameState::from_snapshot(""),
+            Err(Error::CorruptSnapshot(_))
+        ));
+    }
+
+    #[test]
+    fn future_version_rejected() {
+        let snapshot = setup_snapshot_test().to_snapshot().replace(
+            &format!("version={SNAPSHOT_VERSION}"),
+            &format!("version={}", SNAPSHOT_VERSION + 1),
+        );
+        assert_eq!(
+            GameState::from_snapshot(&snapshot).err(),
+            Some(Error::UnsupportedSnapshotVersion(SNAPSHOT_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn version_1_snapshot_read() {
+        let body = "icorb-snapshot version=1\nstar pos=0,0 mass=1 radius=1\n";
+        let snapshot = format!(
+            "{body}checksum fnv1a={:016x}\n",
+            super::fnv1a(body.as_bytes())
+        );
+        let restored = GameState::from_snapshot(&snapshot).unwrap();
+        assert_eq!(restored.stars.len(), 1);
+        assert_eq!(restored.phase, MatchPhase::Lobby);
+    }
+
+    #[test]
+    fn match_phases_restored() {
+        let phases = [
+            MatchPhase::Lobby,
+            MatchPhase::Countdown { remaining: 2.5 },
+            MatchPhase::Playing,
+            MatchPhase::RoundOver {
+                remaining: 0.5,
+                winner: None,
+            },
+            MatchPhase::MatchOver { winner: 2 },
+        ];
+        for phase in phases {
+            let mut state = GameState::with_players(&[1, 2]);
+            state.phase = phase;
+            let restored = GameState::from_snapshot(&state.to_snapshot()).unwrap();
+            assert_eq!(restored.phase, phase);
+        }
+    }
+
+    #[test]
+    fn records_of_later_versions_rejected() {
+        let body = "icorb-snapshot version=1\nstar pos=0,0 mass=1 radius=1\n\
+                    path star=0 period=1 waypoints=0,0;1,0\n";
+        let snapshot = format!(
+            "{body}checksum fnv1a={:016x}\n",
+            super::fnv1a(body.as_bytes())
+        );
+        assert_eq!(
+            GameState::from_snapshot(&snapshot).err(),
+            Some(Error::CorruptSnapshot(
+                "line 3: `path` record not allowed in version 1".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn invalid_record_reported_with_line_number() {
+        let body = "icorb-snapshot version=1\nstar pos=0,0 mass=heavy radius=1\n";
+        let snapshot = format!(
+            "{body}checksum fnv1a={:016x}\n",
+            super::fnv1a(body.as_bytes())
+        );
+        assert_eq!(
+            GameState::from_snapshot(&snapshot).err(),
+            Some(Error::CorruptSnapshot(
+                "line 2: invalid value `heavy` of field `mass`".into()
+            ))
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Respawn of destroyed ships
+//!
+//! Destroyed ships return after a delay at the safest of the configured spawn points. A spawn
+//! point is rated by its clearance to enemy ships and missiles and by the distance a ship at rest
+//! would drift towards the stars within the prediction time. Gravity and distances follow the
+//! physics configuration, including softening and the nearest periodic image in wrap mode.
+
+use std::rc::Rc;
+
+use crate::{
+    entities::{Real, Vec2},
+    physics::{limited_star_field, PhysicsConfig, Star, StarData},
+    repo_interfaces::{Marshalling, PlayerId, PlayerIdData, Vec2Data},
+    Result,
+};
+
+/// Configuration object for respawning ships
+#[derive(Clone, Debug)]
+pub struct RespawnConfig {
+    /// Time between destruction and respawn
+    delay: Real,
+    /// Clearance to enemies beyond which a spawn point counts as safe
+    safe_distance: Real,
+    /// Time span for which the drift of a respawned ship is predicted
+    prediction_time: Real,
+    /// Candidate positions for respawning ships
+    spawn_points: Vec<Vec2>,
+    /// Gravity and arena used to rate spawn points
+    physics: PhysicsConfig,
+}
+
+impl RespawnConfig {
+    /// Create a new respawn configuration without spawn points
+    ///
+    /// Without spawn points, ships respawn at the origin.
+    pub fn new(
+        delay: impl Into<Real>,
+        safe_distance: impl Into<Real>,
+        prediction_time: impl Into<Real>,
+    ) -> Self {
+        RespawnConfig {
+            delay: delay.into(),
+            safe_distance: safe_distance.into(),
+            prediction_time: prediction_time.into(),
+            spawn_points: Vec::new(),
+            physics: PhysicsConfig::new(),
+        }
+    }
+
+    /// Add a candidate position for respawning ships
+    pub fn with_spawn_point(mut self, position: Vec2Data) -> Self {
+        self.spawn_points.push(position.convert());
+        self
+    }
+
+    /// Rate spawn points with the gravity and arena of `physics` instead of the defaults
+    pub fn with_physics(mut self, physics: PhysicsConfig) -> Self {
+        self.physics = physics;
+        self
+    }
+
+    /// Time between destruction and respawn
+    pub fn get_delay(&self) -> Real {
+        self.delay
+    }
+
+    /// Clearance to enemies beyond which a spawn point counts as safe
+    pub fn get_safe_distance(&self) -> Real {
+        self.safe_distance
+    }
+
+    /// Time span for which the drift of a respawned ship is predicted
+    pub fn get_prediction_time(&self) -> Real {
+        self.prediction_time
+    }
+
+    /// Candidate positions for respawning ships
+    pub fn get_spawn_points(&self) -> Vec<Vec2Data> {
+        self.spawn_points.iter().map(|pos| pos.convert()).collect()
+    }
+
+    /// Gravity and arena used to rate spawn points
+    pub fn get_physics(&self) -> PhysicsConfig {
+        self.physics
+    }
+}
+
+/// Respawn use-case
+pub struct Respawn {
+    config: RespawnConfig,
+    repo: RespawnDG,
+}
+
+impl Respawn {
+    /// Create use case object
+    pub fn new(config: RespawnConfig, repo: RespawnDG) -> Self {
+        Self { config, repo }
+    }
+
+    /// Run the use case
+    ///
+    /// Returns the ids of all respawned player.
+    pub fn execute(&self, delta_time: impl Into<Real>) -> Result<Vec<PlayerId>> {
+        let dt = delta_time.into();
+        let (ready, waiting): (Vec<(PlayerId, Real)>, Vec<_>) = self
+            .repo
+            .get_destroyed_players()?
+            .into_iter()
+            .map(|(id, time)| (id.convert(), time + dt))
+            .partition(|&(_, time)| time >= self.config.delay);
+        self.repo.set_time_since_destroyed(
+            waiting
+                .into_iter()
+                .map(|(id, time)| (id.convert(), time))
+                .collect(),
+        )?;
+        if ready.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stars: Vec<Star> = self.repo.get_stars()?.convert();
+        let mut ships: Vec<(PlayerId, Vec2)> = self.repo.get_active_player_positions()?.convert();
+        let missiles: Vec<(PlayerId, Vec2)> =
+            self.repo.get_missile_owners_and_positions()?.convert();
+
+        let mut respawned = Vec::with_capacity(ready.len());
+        for (id, _) in ready {
+            let enemies = ships
+                .iter()
+                .chain(missiles.iter())
+                .filter(|(owner, _)| *owner != id)
+                .map(|&(_, pos)| pos);
+            let pos = self.select_spawn_point(&stars, enemies.collect());
+            let pull = limited_star_field(&self.config.physics, &stars, pos);
+            let angle = if pull.len2() > 0.0 {
+                (Vec2::zero() - pull).angle()
+            } else {
+                0.0
+            };
+            self.repo
+                .respawn_player(&id.convert(), pos.convert(), angle.convert())?;
+            ships.push((id, pos));
+            respawned.push(id);
+        }
+        Ok(respawned)
+    }
+
+    /// Spawn point with the highest rating, the origin if there are no spawn points
+    fn select_spawn_point(&self, stars: &[Star], enemies: Vec<Vec2>) -> Vec2 {
+        self.config
+            .spawn_points
+            .iter()
+            .map(|&pos| (pos, self.rate_spawn_point(pos, stars, &enemies)))
+            .fold(
+                None,
+                |best: Option<(Vec2, Real)>, (pos, rating)| match best {
+                    Some((_, best_rating)) if best_rating >= rating => best,
+                    _ => Some((pos, rating)),
+                },
+            )
+            .map_or(Vec2::zero(), |(pos, _)| pos)
+    }
+
+    /// Rate a spawn point, higher is safer
+    ///
+    /// The rating is the clearance to the closest enemy, capped at the safe distance, minus the
+    /// predicted drift of a ship at rest. Points from which a ship would drift into a star are
+    /// rated below all other points.
+    fn rate_spawn_point(&self, pos: Vec2, stars: &[Star], enemies: &[Vec2]) -> Real {
+        let physics = &self.config.physics;
+        let clearance = enemies
+            .iter()
+            .map(|&enemy| physics.separation(pos, enemy).len())
+            .fold(self.config.safe_distance, Real::min);
+        let pull = limited_star_field(physics, stars, pos);
+        let drift = 0.5 * pull.len() * self.config.prediction_time.powi(2);
+        let captured = stars
+            .iter()
+            .any(|star| physics.separation(pos, star.pos).len() - star.radius <= drift);
+        if captured {
+            -self.config.safe_distance - drift
+        } else {
+            clearance - drift
+        }
+    }
+}
+
+/// Data repository interface for respawn use case.
+pub trait RespawnDataGateway {
+    /// Return `(id, time since destruction)` of all destroyed player
+    fn get_destroyed_players(&self) -> Result<Vec<(PlayerIdData, Real)>>;
+
+    /// Update time since destruction of destroyed player
+    fn set_time_since_destroyed(&self, updates: Vec<(PlayerIdData, Real)>) -> Result<()>;
+
+    /// Return all stars
+    fn get_stars(&self) -> Result<Vec<StarData>>;
+
+    /// Return `(id, position)` of all player whose ships are not destroyed
+    fn get_active_player_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>>;
+
+    /// Return `(owner id, position)` of all missiles
+    fn get_missile_owners_and_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>>;
+
+    /// Restore the ship of a player at rest at `position` with orientation `angle`
+    fn respawn_player(&self, id: &PlayerIdData, position: Vec2Data, angle: Real) -> Result<()>;
+}
+
+type RespawnDG = Rc<dyn RespawnDataGateway>;
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        entities::{Real, PI, TWO_PI},
+        physics::{Arena, BoundaryMode, PhysicsConfig, StarData},
+        repo_interfaces::{PlayerIdData, Vec2Data},
+        Result,
+    };
+
+    use super::{Respawn, RespawnConfig, RespawnDataGateway};
+
+    #[derive(Default)]
+    struct MockDataGateway {
+        destroyed: Vec<(PlayerIdData, Real)>,
+        stars: Vec<StarData>,
+        player: Vec<(PlayerIdData, Vec2Data)>,
+        missiles: Vec<(PlayerIdData, Vec2Data)>,
+        respawned: Vec<(PlayerIdData, Vec2Data, Real)>,
+    }
+
+    impl RespawnDataGateway for RefCell<MockDataGateway> {
+        fn get_destroyed_players(&self) -> Result<Vec<(PlayerIdData, Real)>> {
+            Ok(self.borrow().destroyed.clone())
+        }
+        fn set_time_since_destroyed(&self, updates: Vec<(PlayerIdData, Real)>) -> Result<()> {
+            self.borrow_mut().destroyed = updates;
+            Ok(())
+        }
+        fn get_stars(&self) -> Result<Vec<StarData>> {
+            Ok(self.borrow().stars.clone())
+        }
+        fn get_active_player_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+            Ok(self.borrow().player.clone())
+        }
+        fn get_missile_owners_and_positions(&self) -> Result<Vec<(PlayerIdData, Vec2Data)>> {
+            Ok(self.borrow().missiles.clone())
+        }
+        fn respawn_player(&self, id: &PlayerIdData, position: Vec2Data, angle: Real) -> Result<()> {
+            self.borrow_mut().respawned.push((*id, position, angle));
+            Ok(())
+        }
+    }
+
+    fn setup_respawn_test(data: MockDataGateway) -> (Respawn, Rc<RefCell<MockDataGateway>>) {
+        setup_respawn_test_with_physics(data, PhysicsConfig::new())
+    }
+
+    fn setup_respawn_test_with_physics(
+        data: MockDataGateway,
+        physics: PhysicsConfig,
+    ) -> (Respawn, Rc<RefCell<MockDataGateway>>) {
+        let config = RespawnConfig::new(2.0, 50.0, 1.0)
+            .with_spawn_point([0.0, 0.0])
+            .with_spawn_point([100.0, 0.0])
+            .with_spawn_point([0.0, 100.0])
+            .with_physics(physics);
+        let repo = Rc::new(RefCell::new(data));
+        (Respawn::new(config, repo.clone()), repo)
+    }
+
+    /// Spawn point chosen for player 1 under `physics`
+    fn respawn_position(data: MockDataGateway, physics: PhysicsConfig) -> Vec2Data {
+        let (respawn, repo) = setup_respawn_test_with_physics(data, physics);
+        respawn.execute(0.0).unwrap();
+        let position = repo.borrow().respawned[0].1;
+        position
+    }
+
+    #[test]
+    fn ship_respawns_after_delay() {
+        let (respawn, repo) = setup_respawn_test(MockDataGateway {
+            destroyed: vec![(1, 0.0)],
+            ..Default::default()
+        });
+        assert_eq!(respawn.execute(1.5), Ok(vec![]));
+        assert_eq!(repo.borrow().destroyed, vec![(1, 1.5)]);
+        assert_eq!(respawn.execute(0.5), Ok(vec![1]));
+        assert_eq!(repo.borrow().respawned, vec![(1, [0.0, 0.0], 0.0)]);
+    }
+
+    #[test]
+    fn spawn_point_avoids_enemies() {
+        let (respawn, repo) = setup_respawn_test(MockDataGateway {
+            destroyed: vec![(1, 2.0)],
+            player: vec![(2, [0.0, 10.0])],
+            missiles: vec![(2, [95.0, 0.0]), (1, [0.0, 95.0])],
+            ..Default::default()
+        });
+        respawn.execute(0.0).unwrap();
+        assert_eq!(repo.borrow().respawned[0].1, [0.0, 100.0]);
+    }
+
+    #[test]
+    fn spawn_point_avoids_gravity_well() {
+        let (respawn, repo) = setup_respawn_test(MockDataGateway {
+            destroyed: vec![(1, 2.0)],
+            stars: vec![StarData::new([0.0, 120.0], 10000.0, 10.0)],
+            player: vec![(2, [-20.0, 0.0])],
+            ..Default::default()
+        });
+        respawn.execute(0.0).unwrap();
+        let (_, pos, angle) = repo.borrow().respawned[0];
+        assert_eq!(pos, [100.0, 0.0]);
+        // Facing away from the star
+        assert!(angle > PI && angle < TWO_PI);
+    }
+
+    #[test]
+    fn spawn_point_rated_with_configured_gravity() {
+        let data = || MockDataGateway {
+            destroyed: vec![(1, 2.0)],
+            stars: vec![StarData::new([0.0, -40.0], 1600.0, 5.0)],
+            player: vec![(2, [100.0, 10.0]), (3, [0.0, 105.0])],
+            ..Default::default()
+        };
+        assert_eq!(respawn_position(data(), PhysicsConfig::new()), [0.0, 0.0]);
+        let strong_gravity = PhysicsConfig::new().with_gravity_constant(100.0);
+        assert_eq!(respawn_position(data(), strong_gravity), [100.0, 0.0]);
+    }
+
+    #[test]
+    fn spawn_point_clearance_measured_across_wrapped_bounds() {
+        let data = || MockDataGateway {
+            destroyed: vec![(1, 2.0)],
+            player: vec![(2, [0.0, 180.0])],
+            ..Default::default()
+        };
+        assert_eq!(respawn_position(data(), PhysicsConfig::new()), [0.0, 0.0]);
+        let arena = Arena::new([-50.0, -50.0], [150.0, 150.0], BoundaryMode::Wrap).unwrap();
+        let wrap = PhysicsConfig::new().with_arena(arena);
+        assert_eq!(respawn_position(data(), wrap), [100.0, 0.0]);
+    }
+
+    #[test]
+    fn respawned_ships_avoid_each_other() {
+        let (respawn, repo) = setup_respawn_test(MockDataGateway {
+            destroyed: vec![(1, 2.0), (2, 2.0)],
+            ..Default::default()
+        });
+        respawn.execute(0.0).unwrap();
+        let respawned = &repo.borrow().respawned;
+        assert_ne!(respawned[0].1, respawned[1].1);
+    }
+}
@@ -0,0 +1,768 @@
+//! Fixed time step simulation loop
+//!
+//! The simulation advances the game state in ticks of constant duration, independent of the frame
+//! time of the caller. Left over time is kept in an accumulator and exposed as interpolation
+//! factor between the previous and current state for rendering.
+
+use std::rc::Rc;
+
+use crate::{
+    entities::{trim_angle, Real, Vec2, PI, TWO_PI},
+    physics::{Arena, Boundary, Gravity, GravitySolver, Integrate, PhysicsConfig, StarMovement},
+    repo_interfaces::{Marshalling, MissileIdData, PlayerId, PlayerIdData, Vec2Data},
+    user_input::InputCommand,
+    Error, Result,
+};
+
+/// Configuration object for the simulation loop
+#[derive(Clone, Copy, Debug)]
+pub struct SimulationConfig {
+    /// Duration of a single tick
+    tick_duration: Real,
+    /// Maximum number of ticks computed per update
+    max_ticks_per_update: usize,
+}
+
+impl SimulationConfig {
+    /// Create a new configuration object for the simulation loop
+    ///
+    /// `tick_duration` is the fixed time step of the simulation. `max_ticks_per_update` limits
+    /// the number of ticks computed in a single update. If the caller falls behind, surplus time
+    /// is dropped instead of stalling the caller even further.
+    pub fn new(tick_duration: impl Into<Real>, max_ticks_per_update: usize) -> Self {
+        SimulationConfig {
+            tick_duration: tick_duration.into(),
+            max_ticks_per_update,
+        }
+    }
+
+    /// Fixed time step of the simulation
+    pub fn get_tick_duration(&self) -> Real {
+        self.tick_duration
+    }
+
+    /// Maximum number of ticks computed per update
+    pub fn get_max_ticks_per_update(&self) -> usize {
+        self.max_ticks_per_update
+    }
+}
+
+/// Position and orientation of all objects at the end of a tick
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimulationState {
+    /// Number of the tick
+    pub tick: u64,
+    /// `(id, position, orientation)` of all player
+    pub player: Vec<(PlayerIdData, Vec2Data, Real)>,
+    /// `(player_id, missile_id, position, orientation)` of all missiles
+    pub missiles: Vec<(PlayerIdData, MissileIdData, Vec2Data, Real)>,
+}
+
+/// Simulation loop
+///
+/// Owns the physics use-cases and the input commands issued since the last tick. All of them
+/// have to agree on the arena and on whether to compute with deterministic fixed-point
+/// arithmetic, as a single floating point computation breaks the determinism of a lockstep game.
+pub struct Simulation {
+    config: SimulationConfig,
+    star_movement: Option<StarMovement>,
+    gravity: Gravity,
+    integrate: Integrate,
+    boundary: Option<Boundary>,
+    out_of_bounds: Vec<PlayerId>,
+    commands: Vec<Box<dyn InputCommand>>,
+    accumulator: Real,
+    previous: SimulationState,
+    current: SimulationState,
+    repo: Rc<dyn SimulationDataGateway>,
+}
+
+impl Simulation {
+    /// Create a new simulation loop
+    ///
+    /// Fails if the tick duration is not positive, if no tick may be computed per update or if
+    /// gravity and integration disagree on the fixed-point mode or the arena. In fixed-point
+    /// mode, adaptive substeps and the Barnes-Hut solver are rejected as they are computed with
+    /// floating point numbers.
+    pub fn new(
+        config: SimulationConfig,
+        gravity: Gravity,
+        integrate: Integrate,
+        repo: Rc<dyn SimulationDataGateway>,
+    ) -> Result<Self> {
+        if !(config.tick_duration > 0.0 && config.tick_duration.is_finite()) {
+            return Err(Error::InvalidValue(format!(
+                "tick duration {} is not positive",
+                config.tick_duration
+            )));
+        }
+        if config.max_ticks_per_update == 0 {
+            return Err(Error::InvalidValue(
+                "at least one tick per update required".into(),
+            ));
+        }
+        let (physics, gravity_physics) = (integrate.get_config(), gravity.get_config());
+        check_agreement(
+            physics.is_fixed_point(),
+            gravity_physics.is_fixed_point(),
+            "fixed-point mode of gravity",
+        )?;
+        check_agreement(
+            physics.get_arena(),
+            gravity_physics.get_arena(),
+            "arena of gravity",
+        )?;
+        if physics.is_fixed_point() {
+            check_fixed_point_support(physics)?;
+            check_fixed_point_support(gravity_physics)?;
+        }
+        let mut simulation = Self {
+            config,
+            star_movement: None,
+            gravity,
+            integrate,
+            boundary: None,
+            out_of_bounds: Vec::new(),
+            commands: Vec::new(),
+            accumulator: 0.0,
+            previous: SimulationState::default(),
+            current: SimulationState::default(),
+            repo,
+        };
+        simulation.current = simulation.read_state(0)?;
+        simulation.previous = simulation.current.clone();
+        Ok(simulation)
+    }
+
+    /// Move stars along their prescribed paths at the beginning of every tick
+    ///
+    /// Fails if star movement and integration disagree on the fixed-point mode.
+    pub fn with_star_movement(mut self, star_movement: StarMovement) -> Result<Self> {
+        check_agreement(
+            self.is_fixed_point(),
+            star_movement.get_config().is_fixed_point(),
+            "fixed-point mode of star movement",
+        )?;
+        self.star_movement = Some(star_movement);
+        Ok(self)
+    }
+
+    /// Apply the arena bounds at the end of every tick
+    ///
+    /// Fails if boundary and integration disagree on the arena.
+    pub fn with_boundary(mut self, boundary: Boundary) -> Result<Self> {
+        check_agreement(
+            self.integrate.get_config().get_arena(),
+            Some(boundary.get_arena()),
+            "arena of boundary",
+        )?;
+        self.boundary = Some(boundary);
+        Ok(self)
+    }
+
+    /// Ids of all player destroyed for leaving the arena since the last call
+    pub fn take_players_out_of_bounds(&mut self) -> Vec<PlayerId> {
+        std::mem::take(&mut self.out_of_bounds)
+    }
+
+    /// Queue an input command. Commands are executed at the beginning of the next tick.
+    ///
+    /// Fails if command and integration disagree on the fixed-point mode.
+    pub fn push_command(&mut self, command: Box<dyn InputCommand>) -> Result<()> {
+        check_agreement(
+            self.is_fixed_point(),
+            command.is_fixed_point(),
+            "fixed-point mode of input command",
+        )?;
+        self.commands.push(command);
+        Ok(())
+    }
+
+    /// Whether the simulation computes with deterministic fixed-point arithmetic
+    pub fn is_fixed_point(&self) -> bool {
+        self.integrate.get_config().is_fixed_point()
+    }
+
+    /// Advance the simulation by the elapsed frame time
+    ///
+    /// Returns the number of ticks computed. Stops at the first tick that fails.
+    pub fn update(&mut self, frame_time: impl Into<Real>) -> Result<usize> {
+        let tick_duration = self.config.tick_duration;
+        self.accumulator += frame_time.into();
+        let mut ticks = 0;
+        while self.accumulator >= tick_duration {
+            if ticks == self.config.max_ticks_per_update {
+                self.accumulator %= tick_duration;
+                break;
+            }
+            self.tick()?;
+            self.accumulator -= tick_duration;
+            ticks += 1;
+        }
+        Ok(ticks)
+    }
+
+    /// Compute a single tick
+    ///
+    /// Pending input commands are executed and stars are moved to their position at the beginning
+    /// of the tick before gravity is applied and all other objects are moved. Finally, the arena
+    /// bounds are applied.
+    pub fn tick(&mut self) -> Result<()> {
+        self.commands
+            .drain(..)
+            .try_for_each(|command| command.execute())?;
+        if let Some(star_movement) = &self.star_movement {
+            star_movement.execute(self.current.tick as Real * self.config.tick_duration)?;
+        }
+        self.gravity.execute()?;
+        self.integrate.execute(self.config.tick_duration)?;
+        if let Some(boundary) = &self.boundary {
+            self.out_of_bounds.extend(boundary.execute()?);
+        }
+
+        let next = self.read_state(self.current.tick + 1)?;
+        self.previous = std::mem::replace(&mut self.current, next);
+        Ok(())
+    }
+
+    /// Number of ticks computed so far
+    pub fn get_tick(&self) -> u64 {
+        self.current.tick
+    }
+
+    /// Fraction of a tick that has elapsed since the last computed tick
+    pub fn get_alpha(&self) -> Real {
+        self.accumulator / self.config.tick_duration
+    }
+
+    /// State at the end of the second last tick
+    pub fn get_previous_state(&self) -> &SimulationState {
+        &self.previous
+    }
+
+    /// State at the end of the last tick
+    pub fn get_current_state(&self) -> &SimulationState {
+        &self.current
+    }
+
+    /// State interpolated between previous and current state according to [`Self::get_alpha`]
+    ///
+    /// Objects that did not exist in the previous state are returned at their current position.
+    /// Objects that wrapped around the arena are interpolated towards the nearest image of their
+    /// current position.
+    pub fn get_interpolated_state(&self) -> SimulationState {
+        let alpha = self.get_alpha();
+        let arena = self.boundary.as_ref().map(|boundary| boundary.get_arena());
+        let interpolate = |from, to| interpolate_position(from, to, alpha, arena);
+        let player = self
+            .current
+            .player
+            .iter()
+            .map(
+                |&(id, pos, angle)| match self.previous.player.iter().find(|item| item.0 == id) {
+                    Some(&(_, prev_pos, prev_angle)) => (
+                        id,
+                        interpolate(prev_pos, pos),
+                        interpolate_angle(prev_angle, angle, alpha),
+                    ),
+                    None => (id, pos, angle),
+                },
+            )
+            .collect();
+        let missiles = self
+            .current
+            .missiles
+            .iter()
+            .map(|&(p_id, m_id, pos, angle)| {
+                match self
+                    .previous
+                    .missiles
+                    .iter()
+                    .find(|item| (item.0, item.1) == (p_id, m_id))
+                {
+                    Some(&(_, _, prev_pos, prev_angle)) => (
+                        p_id,
+                        m_id,
+                        interpolate(prev_pos, pos),
+                        interpolate_angle(prev_angle, angle, alpha),
+                    ),
+                    None => (p_id, m_id, pos, angle),
+                }
+            })
+            .collect();
+        SimulationState {
+            tick: self.current.tick,
+            player,
+            missiles,
+        }
+    }
+
+    /// Read position and orientation of all objects from the repository
+    fn read_state(&self, tick: u64) -> Result<SimulationState> {
+        Ok(SimulationState {
+            tick,
+            player: self.repo.get_player_pos_and_angle()?,
+            missiles: self.repo.get_missile_pos_and_angle()?,
+        })
+    }
+}
+
+/// Fail if a setting of a component differs from the one of the simulation
+fn check_agreement<T: PartialEq>(expected: T, actual: T, setting: &str) -> Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(Error::InvalidValue(format!(
+            "{setting} differs from the simulation"
+        )))
+    }
+}
+
+/// Fail for settings of `physics` which are not computed with fixed-point arithmetic
+fn check_fixed_point_support(physics: PhysicsConfig) -> Result<()> {
+    if physics.get_step_accuracy().is_some() {
+        return Err(Error::InvalidValue(
+            "adaptive substeps are not supported in fixed-point mode".into(),
+        ));
+    }
+    if physics.get_solver() != GravitySolver::Direct {
+        return Err(Error::InvalidValue(
+            "only the direct gravity solver is supported in fixed-point mode".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Linear interpolation between two positions within `arena`
+fn interpolate_position(
+    from: Vec2Data,
+    to: Vec2Data,
+    alpha: Real,
+    arena: Option<Arena>,
+) -> Vec2Data {
+    let (from, to): (Vec2, Vec2) = (from.convert(), to.convert());
+    let delta = match arena {
+        Some(arena) => arena.separation(from, to),
+        None => to - from,
+    };
+    (from + delta * alpha).convert()
+}
+
+/// Interpolation between two angles along the shorter arc
+fn interpolate_angle(from: Real, to: Real, alpha: Real) -> Real {
+    let mut delta = trim_angle(to - from);
+    if delta > PI {
+        delta -= TWO_PI;
+    }
+    trim_angle(from + delta * alpha)
+}
+
+/// Data repository interface for the simulation loop
+pub trait SimulationDataGateway {
+    /// Return `(id, position, orientation)` for all player
+    fn get_player_pos_and_angle(&self) -> Result<Vec<(PlayerIdData, Vec2Data, Real)>>;
+    /// Return `(player_id, missile_id, position, orientation)` for all missiles
+    fn get_missile_pos_and_angle(
+        &self,
+    ) -> Result<Vec<(PlayerIdData, MissileIdData, Vec2Data, Real)>>;
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use crate::{
+        entities::Real,
+        physics::{
+            Arena, Boundary, BoundaryMode, Gravity, GravityDataGateway, GravitySolver, Integrate,
+            IntegrateDataGateway, Integrator, PhysicsConfig, StarData, StarMovement,
+        },
+        repo::{GameState, Map},
+        repo_interfaces::PlayerMovementDataGateway,
+        user_input::{
+            InputCommand, MissileConfig, MoveCommandFactory, MoveConfig, MoveInstruction,
+            ShootCommandFactory,
+        },
+        Error, Result,
+    };
+
+    use super::{Simulation, SimulationConfig};
+
+    struct CountingCommand {
+        count: Rc<Cell<usize>>,
+    }
+    impl InputCommand for CountingCommand {
+        fn execute(&self) -> Result<()> {
+            self.count.set(self.count.get() + 1);
+            Ok(())
+        }
+    }
+
+    struct FailingCommand;
+    impl InputCommand for FailingCommand {
+        fn execute(&self) -> Result<()> {
+            Err(Error::UnknownPlayer(7))
+        }
+    }
+
+    fn setup_simulation_test(max_ticks: usize) -> (Simulation, Rc<RefCell<GameState>>) {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1, 2])));
+        state
+            .set_player_info(vec![
+                (1, [0.0, 0.0], [1.0, 0.0], [0.0, 0.0]),
+                (2, [0.0, 10.0], [0.0, 0.0], [0.0, 0.0]),
+            ])
+            .unwrap();
+        let simulation = Simulation::new(
+            SimulationConfig::new(0.5, max_ticks),
+            Gravity::new(PhysicsConfig::new(), state.clone()),
+            Integrate::new(PhysicsConfig::new(), state.clone()),
+            state.clone(),
+        )
+        .unwrap();
+        (simulation, state)
+    }
+
+    #[test]
+    fn no_tick_computed_for_short_frames() {
+        let (mut simulation, _) = setup_simulation_test(5);
+        assert_eq!(simulation.update(0.2), Ok(0));
+        assert_eq!(simulation.get_tick(), 0);
+        assert!((simulation.get_alpha() - 0.4).abs() < Real::EPSILON);
+    }
+
+    #[test]
+    fn fixed_ticks_computed_from_accumulated_time() {
+        let (mut simulation, _) = setup_simulation_test(5);
+        assert_eq!(simulation.update(0.3), Ok(0));
+        assert_eq!(simulation.update(0.8), Ok(2));
+        assert_eq!(simulation.get_tick(), 2);
+        assert!((simulation.get_alpha() - 0.2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ticks_per_update_are_limited() {
+        let (mut simulation, _) = setup_simulation_test(3);
+        assert_eq!(simulation.update(10.25), Ok(3));
+        assert!((simulation.get_alpha() - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn commands_executed_once_on_next_tick() {
+        let (mut simulation, _) = setup_simulation_test(5);
+        let count = Rc::new(Cell::new(0));
+        simulation
+            .push_command(Box::new(CountingCommand {
+                count: count.clone(),
+            }))
+            .unwrap();
+        simulation.update(0.1).unwrap();
+        assert_eq!(count.get(), 0);
+        simulation.update(1.0).unwrap();
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn failing_command_aborts_update() {
+        let (mut simulation, _) = setup_simulation_test(5);
+        simulation.push_command(Box::new(FailingCommand)).unwrap();
+        assert_eq!(simulation.update(1.0), Err(Error::UnknownPlayer(7)));
+        assert_eq!(simulation.get_tick(), 0);
+    }
+
+    #[test]
+    fn previous_and_current_state_tracked() {
+        let (mut simulation, _) = setup_simulation_test(5);
+        simulation.update(1.0).unwrap();
+        let previous = simulation.get_previous_state();
+        let current = simulation.get_current_state();
+        assert_eq!((previous.tick, current.tick), (1, 2));
+        let find_player_1 =
+            |player: &[(usize, [Real; 2], Real)]| player.iter().find(|p| p.0 == 1).unwrap().1;
+        assert_eq!(find_player_1(&previous.player), [0.5, 0.0]);
+        assert_eq!(find_player_1(&current.player), [1.0, 0.0]);
+    }
+
+    #[test]
+    fn state_interpolated_with_alpha() {
+        let (mut simulation, _) = setup_simulation_test(5);
+        simulation.update(0.75).unwrap();
+        let interpolated = simulation.get_interpolated_state();
+        let player_1 = interpolated.player.iter().find(|p| p.0 == 1).unwrap();
+        assert!((player_1.1[0] - 0.25).abs() < 1e-5);
+        assert_eq!(player_1.1[1], 0.0);
+    }
+
+    #[test]
+    fn boundary_applied_every_tick() {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1, 2])));
+        state
+            .set_player_info(vec![
+                (1, [9.0, 0.0], [3.0, 0.0], [0.0, 0.0]),
+                (2, [0.0, 9.0], [0.0, 3.0], [0.0, 0.0]),
+            ])
+            .unwrap();
+        let physics = |mode| {
+            PhysicsConfig::new().with_arena(Arena::new([-10.0, -10.0], [10.0, 10.0], mode).unwrap())
+        };
+        let setup = |mode| {
+            Simulation::new(
+                SimulationConfig::new(1.0, 5),
+                Gravity::new(physics(mode), state.clone()),
+                Integrate::new(physics(mode), state.clone()),
+                state.clone(),
+            )
+            .unwrap()
+            .with_boundary(Boundary::new(physics(mode), state.clone()).unwrap())
+            .unwrap()
+        };
+
+        let mut simulation = setup(BoundaryMode::Wrap);
+        simulation.update(1.5).unwrap();
+        let mut positions = simulation.get_current_state().player.clone();
+        positions.sort_by_key(|&(id, _, _)| id);
+        assert_eq!(positions[0].1, [-8.0, 0.0]);
+        // Interpolated across the seam instead of through the arena
+        let interpolated = simulation.get_interpolated_state();
+        let (_, pos, _) = interpolated.player.iter().find(|item| item.0 == 1).unwrap();
+        assert_eq!(*pos, [10.5, 0.0]);
+
+        let mut simulation = setup(BoundaryMode::KillZone);
+        simulation.update(3.0).unwrap();
+        assert!(simulation.take_players_out_of_bounds().is_empty());
+        simulation.update(4.0).unwrap();
+        let mut out_of_bounds = simulation.take_players_out_of_bounds();
+        out_of_bounds.sort();
+        assert_eq!(out_of_bounds, vec![1, 2]);
+        assert!(simulation.take_players_out_of_bounds().is_empty());
+    }
+
+    #[test]
+    fn stars_moved_every_tick() {
+        let map = Map::parse("star 0 0 1 1\npath 2 0 0 10 0").unwrap();
+        let state = Rc::new(RefCell::new(GameState::from_map(&map)));
+        let mut simulation = Simulation::new(
+            SimulationConfig::new(0.5, 5),
+            Gravity::new(PhysicsConfig::new(), state.clone()),
+            Integrate::new(PhysicsConfig::new(), state.clone()),
+            state.clone(),
+        )
+        .unwrap()
+        .with_star_movement(StarMovement::new(PhysicsConfig::new(), state.clone()))
+        .unwrap();
+
+        simulation.update(1.0).unwrap();
+
+        assert_eq!(
+            state.get_stars_position_and_mass(),
+            Ok(vec![StarData::new([5.0, 0.0], 1.0, 1.0)])
+        );
+    }
+
+    /// FNV-1a hash of position and velocity of all objects and the orientation of all player
+    /// Hash of the raw fixed-point position, velocity and orientation of all objects
+    fn state_hash(state: &RefCell<GameState>) -> u64 {
+        let mut player = state.get_fixed_player_info().unwrap();
+        player.sort_by_key(|&(id, ..)| id);
+        let mut missiles = state.get_fixed_missile_info().unwrap();
+        missiles.sort_by_key(|&(p_id, m_id, ..)| (p_id, m_id));
+        let mut values = Vec::new();
+        for (id, pos, vel, _) in player {
+            values.extend(pos.into_iter().chain(vel));
+            values.push(state.get_fixed_player_orientation(&id).unwrap());
+        }
+        for (_, _, pos, vel, _) in missiles {
+            values.extend(pos.into_iter().chain(vel));
+        }
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .fold(0xcbf29ce484222325, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            })
+    }
+
+    #[test]
+    fn invalid_config_rejected() {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1])));
+        let simulation = |config| {
+            Simulation::new(
+                config,
+                Gravity::new(PhysicsConfig::new(), state.clone()),
+                Integrate::new(PhysicsConfig::new(), state.clone()),
+                state.clone(),
+            )
+        };
+        for tick_duration in [0.0, -0.1, Real::NAN, Real::INFINITY] {
+            assert!(matches!(
+                simulation(SimulationConfig::new(tick_duration, 5)),
+                Err(Error::InvalidValue(_))
+            ));
+        }
+        assert!(matches!(
+            simulation(SimulationConfig::new(0.1, 0)),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn mismatched_arenas_rejected() {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1])));
+        let arena = |mode| Arena::new([-10.0, -10.0], [10.0, 10.0], mode).unwrap();
+        let wrap = PhysicsConfig::new().with_arena(arena(BoundaryMode::Wrap));
+        let reflect = PhysicsConfig::new().with_arena(arena(BoundaryMode::Reflect));
+        let simulation = |gravity: PhysicsConfig, integrate: PhysicsConfig| {
+            Simulation::new(
+                SimulationConfig::new(0.1, 1),
+                Gravity::new(gravity, state.clone()),
+                Integrate::new(integrate, state.clone()),
+                state.clone(),
+            )
+        };
+        assert!(matches!(
+            simulation(PhysicsConfig::new(), wrap),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            Boundary::new(PhysicsConfig::new(), state.clone()),
+            Err(Error::InvalidValue(_))
+        ));
+        let boundary = || Boundary::new(reflect, state.clone()).unwrap();
+        assert!(matches!(
+            simulation(wrap, wrap).unwrap().with_boundary(boundary()),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(simulation(reflect, reflect)
+            .unwrap()
+            .with_boundary(boundary())
+            .is_ok());
+    }
+
+    #[test]
+    fn mixed_fixed_point_modes_rejected() {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1])));
+        let fixed = PhysicsConfig::new().with_fixed_point();
+        let simulation = |gravity: PhysicsConfig, integrate: PhysicsConfig| {
+            Simulation::new(
+                SimulationConfig::new(0.1, 1),
+                Gravity::new(gravity, state.clone()),
+                Integrate::new(integrate, state.clone()),
+                state.clone(),
+            )
+        };
+        assert!(matches!(
+            simulation(PhysicsConfig::new(), fixed),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            simulation(fixed, fixed)
+                .unwrap()
+                .with_star_movement(StarMovement::new(PhysicsConfig::new(), state.clone())),
+            Err(Error::InvalidValue(_))
+        ));
+
+        let mut simulation = simulation(fixed, fixed)
+            .unwrap()
+            .with_star_movement(StarMovement::new(fixed, state.clone()))
+            .unwrap();
+        let float = MoveCommandFactory::new(MoveConfig::new(5.0, 0.2), state.clone());
+        let fixed = MoveCommandFactory::new(MoveConfig::new(5.0, 0.2).with_fixed_point(), state);
+        assert!(matches!(
+            simulation.push_command(float.make_move_command(1, MoveInstruction::Accelerate)),
+            Err(Error::InvalidValue(_))
+        ));
+        assert_eq!(
+            simulation.push_command(fixed.make_move_command(1, MoveInstruction::Accelerate)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn floating_point_settings_rejected_in_fixed_point_mode() {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1])));
+        let fixed = PhysicsConfig::new().with_fixed_point();
+        let simulation = |gravity: PhysicsConfig, integrate: PhysicsConfig| {
+            Simulation::new(
+                SimulationConfig::new(0.1, 1),
+                Gravity::new(gravity, state.clone()),
+                Integrate::new(integrate, state.clone()),
+                state.clone(),
+            )
+        };
+        let adaptive = fixed.with_adaptive_substeps(0.1, 8);
+        let barnes_hut = fixed
+            .with_solver(GravitySolver::BarnesHut { theta: 0.5 })
+            .unwrap();
+        assert!(matches!(
+            simulation(fixed, adaptive),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(matches!(
+            simulation(barnes_hut, fixed),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(simulation(fixed, fixed).is_ok());
+    }
+
+    #[test]
+    fn fixed_point_simulation_is_deterministic() {
+        let state = Rc::new(RefCell::new(GameState::with_players(&[1, 2])));
+        state
+            .borrow_mut()
+            .add_star(StarData::new([0.0, 0.0], 1000.0, 5.0));
+        state
+            .set_player_info(vec![
+                (1, [100.0, 0.0], [0.0, 3.0], [0.0, 0.0]),
+                (2, [-60.0, 0.0], [0.0, -4.0], [0.0, 0.0]),
+            ])
+            .unwrap();
+        let physics = PhysicsConfig::new()
+            .with_fixed_point()
+            .with_player_mass(1.0)
+            .with_missile_mass(0.125)
+            .with_integrator(Integrator::VelocityVerlet)
+            .with_substeps(2);
+        let movement =
+            MoveCommandFactory::new(MoveConfig::new(5.0, 0.25).with_fixed_point(), state.clone());
+        let shooting = ShootCommandFactory::new(
+            MissileConfig::new(3, 2.0, 3.0).with_fixed_point(),
+            state.clone(),
+        );
+        let mut simulation = Simulation::new(
+            SimulationConfig::new(0.125, 1),
+            Gravity::new(physics, state.clone()),
+            Integrate::new(physics, state.clone()),
+            state.clone(),
+        )
+        .unwrap();
+
+        for tick in 0..10_000 {
+            match tick % 10 {
+                0 => simulation
+                    .push_command(movement.make_move_command(1, MoveInstruction::RotateLeft))
+                    .unwrap(),
+                5 => simulation
+                    .push_command(movement.make_move_command(1, MoveInstruction::Accelerate))
+                    .unwrap(),
+                7 => simulation
+                    .push_command(movement.make_move_command(2, MoveInstruction::RotateRight))
+                    .unwrap(),
+                _ => (),
+            }
+            if tick % 1000 == 500 {
+                simulation
+                    .push_command(shooting.make_shoot_command(2))
+                    .unwrap();
+            }
+            simulation.tick().unwrap();
+        }
+
+        assert_eq!(state.get_missile_info().unwrap().len(), 3);
+        // Any change of this value breaks lockstep compatibility with earlier versions and
+        // between builds with single and double precision
+        assert_eq!(state_hash(&state), 0xb296_1980_cf11_e0e6);
+    }
+}
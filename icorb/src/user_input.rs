@@ -4,9 +4,16 @@
 //! -  Player movement
 //! -  Missile launch
 
+use crate::Result;
+
 /// Interface for commands issued by player input
 pub trait InputCommand {
-    fn execute(&self);
+    fn execute(&self) -> Result<()>;
+
+    /// Whether the command computes with deterministic fixed-point arithmetic
+    fn is_fixed_point(&self) -> bool {
+        false
+    }
 }
 
 // Reexport player movement API
@@ -14,23 +21,32 @@ pub use movement::{MoveCommandFactory, MoveConfig, MoveInstruction, PlayerMoveme
 
 // Reexport shoot API
 pub use shooting::{
-    MissileConfig, MissileLaunchData, PlayerPosAndVelocityData, ShootCommandFactory,
-    ShootDataGateway,
+    FixedMissileLaunchData, FixedPlayerPosAndVelocityData, MissileConfig, MissileLaunchData,
+    PlayerPosAndVelocityData, ShootCommandFactory, ShootDataGateway,
 };
 
 mod movement {
     use super::InputCommand;
     use crate::{
-        entities::{trim_angle, Vec2},
-        repo_interfaces::{Marshalling, PlayerId, PlayerIdData, Vec2Data},
+        entities::{
+            fixed::{Fixed, FixedVec2},
+            trim_angle, Real, Vec2,
+        },
+        repo_interfaces::{
+            FixedData, FixedVec2Data, Marshalling, PlayerId, PlayerIdData, Vec2Data,
+        },
+        Result,
     };
     use std::rc::Rc;
 
     /// Configuration object for player movement
     #[derive(Copy, Clone, Debug)]
     pub struct MoveConfig {
-        angle_per_frame: f32,
-        acceleration: f32,
+        angle_per_frame: Real,
+        /// Angle of rotation in degree, converted to radians in fixed-point arithmetic
+        degree_per_frame: Real,
+        acceleration: Real,
+        fixed_point: bool,
     }
 
     impl MoveConfig {
@@ -39,22 +55,36 @@ mod movement {
         /// `angle` is the angle of rotation in degree per frame to rotate.
         /// `acceleration` is the scalar aceleration in the direction in which the
         /// Player points.
-        pub fn new(angle: impl Into<f32>, acceleration: impl Into<f32>) -> Self {
+        pub fn new(angle: impl Into<Real>, acceleration: impl Into<Real>) -> Self {
+            let degree_per_frame = angle.into();
             MoveConfig {
-                angle_per_frame: angle.into().to_radians(),
+                angle_per_frame: degree_per_frame.to_radians(),
+                degree_per_frame,
                 acceleration: acceleration.into(),
+                fixed_point: false,
             }
         }
 
+        /// Rotate and accelerate with deterministic fixed-point arithmetic
+        pub fn with_fixed_point(mut self) -> Self {
+            self.fixed_point = true;
+            self
+        }
+
         /// Change of player orientation per frame in radians
-        pub fn get_angle_per_frame(&self) -> f32 {
+        pub fn get_angle_per_frame(&self) -> Real {
             self.angle_per_frame
         }
 
         /// Scalar acceleration in direction of player orientation
-        pub fn get_acceleration(&self) -> f32 {
+        pub fn get_acceleration(&self) -> Real {
             self.acceleration
         }
+
+        /// Whether rotation and acceleration use deterministic fixed-point arithmetic
+        pub fn is_fixed_point(&self) -> bool {
+            self.fixed_point
+        }
     }
 
     /// Possible commands for player movement
@@ -100,39 +130,71 @@ mod movement {
     }
 
     impl InputCommand for MoveCommand {
-        fn execute(&self) {
+        fn execute(&self) -> Result<()> {
             match self.instruction {
                 MoveInstruction::RotateLeft => self.player_rotate_left(),
                 MoveInstruction::RotateRight => self.player_rotate_right(),
                 MoveInstruction::Accelerate => self.player_accelerate(),
             }
         }
+
+        fn is_fixed_point(&self) -> bool {
+            self.config.fixed_point
+        }
     }
 
     impl MoveCommand {
-        /// Rotate player by angle
-        fn player_rotate(&self, angle: f32) {
-            let orientation = self.repo.get_player_orientation(&self.player_id).convert();
-            let new_orientation = trim_angle(orientation + angle);
+        /// Rotate player by the configured angle, to the left for `sign` 1 and to the right for
+        /// `sign` -1
+        fn player_rotate(&self, sign: Real) -> Result<()> {
+            if self.config.fixed_point {
+                let orientation: Fixed = self
+                    .repo
+                    .get_fixed_player_orientation(&self.player_id)?
+                    .convert();
+                let angle = Fixed::from(sign * self.config.degree_per_frame) * Fixed::PI
+                    / Fixed::from(180.0);
+                let new_orientation = (orientation + angle).rem_euclid(Fixed::TWO_PI);
+                return self
+                    .repo
+                    .set_fixed_player_orientation(&self.player_id, new_orientation.convert());
+            }
+            let orientation: Real = self.repo.get_player_orientation(&self.player_id)?.convert();
+            let new_orientation = trim_angle(orientation + sign * self.config.angle_per_frame);
             self.repo
-                .set_player_orientation(&self.player_id, new_orientation.convert());
+                .set_player_orientation(&self.player_id, new_orientation.convert())
         }
 
         /// Rotate player by fixed angle to the left
-        fn player_rotate_left(&self) {
-            let angle = self.config.angle_per_frame;
-            self.player_rotate(angle);
+        fn player_rotate_left(&self) -> Result<()> {
+            self.player_rotate(1.0)
         }
 
         /// Rotate player by fixed angle to the right
-        fn player_rotate_right(&self) {
-            let angle = -self.config.angle_per_frame;
-            self.player_rotate(angle);
+        fn player_rotate_right(&self) -> Result<()> {
+            self.player_rotate(-1.0)
         }
         /// accelerate player in current diretion by fixed amount
-        fn player_accelerate(&self) {
-            let orientation = self.repo.get_player_orientation(&self.player_id).convert();
-            let acc = self.repo.get_player_acceleration(&self.player_id).convert();
+        fn player_accelerate(&self) -> Result<()> {
+            if self.config.fixed_point {
+                let orientation: Fixed = self
+                    .repo
+                    .get_fixed_player_orientation(&self.player_id)?
+                    .convert();
+                let acc: FixedVec2 = self
+                    .repo
+                    .get_fixed_player_acceleration(&self.player_id)?
+                    .convert();
+                let thrust = FixedVec2::new(self.config.acceleration, 0.0).rotate(orientation);
+                return self
+                    .repo
+                    .set_fixed_player_acceleration(&self.player_id, (thrust + acc).convert());
+            }
+            let orientation: Real = self.repo.get_player_orientation(&self.player_id)?.convert();
+            let acc: Vec2 = self
+                .repo
+                .get_player_acceleration(&self.player_id)?
+                .convert();
             let new_acc = Vec2::new(self.config.acceleration, 0.0).rotate(orientation) + acc;
             self.repo
                 .set_player_acceleration(&self.player_id, new_acc.convert())
@@ -142,11 +204,39 @@ mod movement {
     /// Interface for player data provider.
     ///
     /// Every storage backend for player data must implement this trait to be usable for providing game state data.
+    ///
+    /// The fixed-point methods default to rounding through the floating point methods. Backends
+    /// that keep the exact fixed-point state override them.
     pub trait PlayerMovementDataGateway {
-        fn get_player_orientation(&self, id: &PlayerIdData) -> f32;
-        fn set_player_orientation(&self, id: &PlayerIdData, orientation: f32);
-        fn get_player_acceleration(&self, id: &PlayerIdData) -> Vec2Data;
-        fn set_player_acceleration(&self, id: &PlayerIdData, acceleration: Vec2Data);
+        fn get_player_orientation(&self, id: &PlayerIdData) -> Result<Real>;
+        fn set_player_orientation(&self, id: &PlayerIdData, orientation: Real) -> Result<()>;
+        fn get_player_acceleration(&self, id: &PlayerIdData) -> Result<Vec2Data>;
+        fn set_player_acceleration(&self, id: &PlayerIdData, acceleration: Vec2Data) -> Result<()>;
+
+        /// Fixed-point variant of [`Self::get_player_orientation`]
+        fn get_fixed_player_orientation(&self, id: &PlayerIdData) -> Result<FixedData> {
+            Ok(self.get_player_orientation(id)?.convert())
+        }
+        /// Fixed-point variant of [`Self::set_player_orientation`]
+        fn set_fixed_player_orientation(
+            &self,
+            id: &PlayerIdData,
+            orientation: FixedData,
+        ) -> Result<()> {
+            self.set_player_orientation(id, orientation.convert())
+        }
+        /// Fixed-point variant of [`Self::get_player_acceleration`]
+        fn get_fixed_player_acceleration(&self, id: &PlayerIdData) -> Result<FixedVec2Data> {
+            Ok(self.get_player_acceleration(id)?.convert())
+        }
+        /// Fixed-point variant of [`Self::set_player_acceleration`]
+        fn set_fixed_player_acceleration(
+            &self,
+            id: &PlayerIdData,
+            acceleration: FixedVec2Data,
+        ) -> Result<()> {
+            self.set_player_acceleration(id, acceleration.convert())
+        }
     }
 
     type DataGateway = Rc<dyn PlayerMovementDataGateway>;
@@ -155,7 +245,10 @@ mod movement {
     mod test {
         use std::{cell::RefCell, rc::Rc};
 
-        use crate::entities::{Vec2, PI};
+        use crate::{
+            entities::{Real, Vec2, PI},
+            Error, Result,
+        };
 
         use super::{
             MoveCommandFactory, MoveConfig, MoveInstruction, PlayerIdData,
@@ -165,33 +258,45 @@ mod movement {
         #[derive(Default)]
         struct MockData {
             vec: (PlayerIdData, String, Vec2Data),
-            scalar: (PlayerIdData, String, f32),
+            scalar: (PlayerIdData, String, Real),
         }
 
         struct MockDataGateway {
             data: MockData,
         }
         impl PlayerMovementDataGateway for RefCell<MockDataGateway> {
-            fn get_player_orientation(&self, _id: &super::PlayerIdData) -> f32 {
-                PI / 2.0
+            fn get_player_orientation(&self, id: &super::PlayerIdData) -> Result<Real> {
+                match id {
+                    0 => Ok(PI / 2.0),
+                    _ => Err(Error::UnknownPlayer(*id)),
+                }
             }
-            fn set_player_orientation(&self, id: &super::PlayerIdData, orientation: f32) {
+            fn set_player_orientation(
+                &self,
+                id: &super::PlayerIdData,
+                orientation: Real,
+            ) -> Result<()> {
                 self.borrow_mut().data.scalar = (*id, "orientation".into(), orientation);
+                Ok(())
             }
-            fn get_player_acceleration(&self, _id: &super::PlayerIdData) -> super::Vec2Data {
-                [50.0, 100.0]
+            fn get_player_acceleration(
+                &self,
+                _id: &super::PlayerIdData,
+            ) -> Result<super::Vec2Data> {
+                Ok([50.0, 100.0])
             }
             fn set_player_acceleration(
                 &self,
                 id: &super::PlayerIdData,
                 acceleration: super::Vec2Data,
-            ) {
+            ) -> Result<()> {
                 self.borrow_mut().data.vec = (*id, "acceleration".into(), acceleration);
+                Ok(())
             }
         }
 
         fn setup_move_test() -> (MoveConfig, MoveCommandFactory, Rc<RefCell<MockDataGateway>>) {
-            let move_config: MoveConfig = MoveConfig::new(5_f32 * PI / 180.0, 100.0);
+            let move_config: MoveConfig = MoveConfig::new(5.0 * PI / 180.0, 100.0);
             let repo = Rc::new(RefCell::new(MockDataGateway {
                 data: MockData::default(),
             }));
@@ -202,10 +307,11 @@ mod movement {
         #[test]
         fn player_rotates_left() {
             let (move_config, command_factory, repo) = setup_move_test();
-            let before = repo.get_player_orientation(&0);
+            let before = repo.get_player_orientation(&0).unwrap();
             command_factory
                 .make_move_command(0, MoveInstruction::RotateLeft)
-                .execute();
+                .execute()
+                .unwrap();
             assert_eq!(
                 repo.borrow().data.scalar,
                 (
@@ -219,10 +325,11 @@ mod movement {
         #[test]
         fn player_rotate_right() {
             let (move_config, command_factory, repo) = setup_move_test();
-            let before = repo.get_player_orientation(&0);
+            let before = repo.get_player_orientation(&0).unwrap();
             command_factory
                 .make_move_command(0, MoveInstruction::RotateRight)
-                .execute();
+                .execute()
+                .unwrap();
             assert_eq!(
                 repo.borrow().data.scalar,
                 (
@@ -236,11 +343,12 @@ mod movement {
         #[test]
         fn player_accelerate() {
             let (move_config, command_factory, repo) = setup_move_test();
-            let before = repo.get_player_acceleration(&0);
-            let orientation = repo.get_player_orientation(&0);
+            let before = repo.get_player_acceleration(&0).unwrap();
+            let orientation = repo.get_player_orientation(&0).unwrap();
             command_factory
                 .make_move_command(0, MoveInstruction::Accelerate)
-                .execute();
+                .execute()
+                .unwrap();
             let acc = Vec2::new(move_config.get_acceleration(), 0.0).rotate(orientation);
             assert_eq!(
                 repo.borrow().data.vec,
@@ -251,20 +359,46 @@ mod movement {
                 )
             );
         }
+
+        #[test]
+        fn fixed_point_movement_matches_float() {
+            let (move_config, _, repo) = setup_move_test();
+            let command_factory =
+                MoveCommandFactory::new(move_config.with_fixed_point(), repo.clone());
+            let orientation = repo.get_player_orientation(&0).unwrap();
+            command_factory
+                .make_move_command(0, MoveInstruction::RotateLeft)
+                .execute()
+                .unwrap();
+            let rotated = repo.borrow().data.scalar.2;
+            assert!((rotated - orientation - move_config.get_angle_per_frame()).abs() < 1e-6);
+            command_factory
+                .make_move_command(0, MoveInstruction::Accelerate)
+                .execute()
+                .unwrap();
+            let acc = repo.borrow().data.vec.2;
+            assert!((Vec2::<Real>::new(acc[0], acc[1]) - Vec2::new(50.0, 200.0)).len() < 1e-4);
+        }
     }
 }
 
 mod shooting {
     use super::InputCommand;
-    use crate::entities::Vec2;
-    use crate::repo_interfaces::{Marshalling, PlayerId, PlayerIdData, Vec2Data};
+    use crate::entities::{
+        fixed::{Fixed, FixedVec2},
+        Real, Vec2,
+    };
+    use crate::repo_interfaces::{
+        FixedData, FixedVec2Data, Marshalling, MissileIdData, PlayerId, PlayerIdData, Vec2Data,
+    };
+    use crate::Result;
     use std::rc::Rc;
 
     /// Position, orientation and velocity of the player object
-    #[derive(Clone, Copy, PartialEq, Debug)]
+    #[derive(Clone, Copy, PartialEq, Debug, Default)]
     pub struct ObjectPosAndVelocityData {
         pub pos: Vec2Data,
-        pub angle: f32,
+        pub angle: Real,
         pub velocity: Vec2Data,
     }
 
@@ -272,7 +406,7 @@ mod shooting {
     #[derive(Clone, Copy)]
     struct ObjectPosAndVelocity {
         pos: Vec2,
-        angle: f32,
+        angle: Real,
         velocity: Vec2,
     }
     impl Marshalling<PlayerPosAndVelocity> for PlayerPosAndVelocityData {
@@ -304,40 +438,112 @@ mod shooting {
     /// Position, orientation and velocity of a missile object
     type MissileLaunch = PlayerPosAndVelocity;
 
+    /// Position, orientation and velocity of an object in fixed-point representation
+    #[derive(Clone, Copy, PartialEq, Debug, Default)]
+    pub struct FixedObjectPosAndVelocityData {
+        pub pos: FixedVec2Data,
+        pub angle: FixedData,
+        pub velocity: FixedVec2Data,
+    }
+
+    /// Position, orientation and velocity of the player object in fixed-point representation
+    pub type FixedPlayerPosAndVelocityData = FixedObjectPosAndVelocityData;
+
+    /// Position, orientation and velocity of a missile object in fixed-point representation
+    pub type FixedMissileLaunchData = FixedPlayerPosAndVelocityData;
+
     /// Missile related configuration
     #[derive(Clone, Copy)]
     pub struct MissileConfig {
         /// Maximum number of missile a player may have
         max: usize,
         /// Relative speed of missile when fired
-        initial_speed: f32,
+        initial_speed: Real,
         /// Initial distance of missile to player object
-        initial_distance: f32,
+        initial_distance: Real,
+        /// Time after which a missile is removed
+        lifetime: Option<Real>,
+        /// Time a missile accelerates after launch
+        fuel: Option<Real>,
+        /// Scalar acceleration of a missile while it has fuel
+        thrust: Real,
+        /// Whether to launch missiles with deterministic fixed-point arithmetic
+        fixed_point: bool,
     }
 
     impl MissileConfig {
         /// Create a new missile config
         pub fn new(
             max_missile: usize,
-            initial_speed: impl Into<f32>,
-            initial_distance: impl Into<f32>,
+            initial_speed: impl Into<Real>,
+            initial_distance: impl Into<Real>,
         ) -> MissileConfig {
             MissileConfig {
                 max: max_missile,
                 initial_speed: initial_speed.into(),
                 initial_distance: initial_distance.into(),
+                lifetime: None,
+                fuel: None,
+                thrust: 0.0,
+                fixed_point: false,
             }
         }
 
+        /// Limit the lifetime of missiles. Missiles are removed once they are older than `lifetime`.
+        pub fn with_lifetime(mut self, lifetime: impl Into<Real>) -> MissileConfig {
+            self.lifetime = Some(lifetime.into());
+            self
+        }
+
+        /// Equip missiles with fuel
+        ///
+        /// `fuel` is the burn time after launch during which a missile accelerates by `thrust`
+        /// in direction of its velocity.
+        pub fn with_fuel(
+            mut self,
+            fuel: impl Into<Real>,
+            thrust: impl Into<Real>,
+        ) -> MissileConfig {
+            self.fuel = Some(fuel.into());
+            self.thrust = thrust.into();
+            self
+        }
+
+        /// Compute launch position and velocity with deterministic fixed-point arithmetic
+        pub fn with_fixed_point(mut self) -> MissileConfig {
+            self.fixed_point = true;
+            self
+        }
+
         /// Maximum number of missile a player may have
         pub fn get_max_missile(&self) -> usize {
             self.max
         }
 
         /// Relative speed of missile when fired
-        pub fn get_initial_speed(&self) -> f32 {
+        pub fn get_initial_speed(&self) -> Real {
             self.initial_speed
         }
+
+        /// Time after which a missile is removed, if limited
+        pub fn get_lifetime(&self) -> Option<Real> {
+            self.lifetime
+        }
+
+        /// Burn time of a missile, if it has fuel
+        pub fn get_fuel(&self) -> Option<Real> {
+            self.fuel
+        }
+
+        /// Scalar acceleration of a missile while it has fuel
+        pub fn get_thrust(&self) -> Real {
+            self.thrust
+        }
+
+        /// Whether missiles are launched with deterministic fixed-point arithmetic
+        pub fn is_fixed_point(&self) -> bool {
+            self.fixed_point
+        }
     }
 
     /// Factory for shoot command use cases
@@ -373,67 +579,144 @@ mod shooting {
     }
 
     impl InputCommand for ShootCommand {
-        fn execute(&self) {
+        fn execute(&self) -> Result<()> {
             self.shoot()
         }
+
+        fn is_fixed_point(&self) -> bool {
+            self.config.fixed_point
+        }
     }
 
     impl ShootCommand {
         /// shoot command use case
-        fn shoot(&self) {
-            if self.player_can_shoot_missile(self.player_id) {
-                self.create_missile_for_player(self.player_id);
+        fn shoot(&self) -> Result<()> {
+            if self.player_can_shoot_missile(self.player_id)? {
+                self.create_missile_for_player(self.player_id)?;
             }
+            Ok(())
         }
 
         /// Create a new missile for a player
-        fn create_missile_for_player(&self, player_id: PlayerId) {
-            let player = self
+        fn create_missile_for_player(&self, player_id: PlayerId) -> Result<()> {
+            if self.config.fixed_point {
+                return self.create_fixed_missile_for_player(player_id);
+            }
+            let player: PlayerPosAndVelocity = self
                 .repo
-                .get_player_pos_and_velocity(&player_id.convert())
+                .get_player_pos_and_velocity(&player_id.convert())?
                 .convert();
             let missile_pos =
                 player.pos + Vec2::new(self.config.initial_distance, 0.0).rotate(player.angle);
             let missile_vel =
                 player.velocity + Vec2::new(self.config.initial_speed, 0.0).rotate(player.angle);
-            let missile_angle = missile_vel.angle();
             let new_missile = MissileLaunch {
                 pos: missile_pos,
-                angle: missile_angle,
+                angle: missile_vel.angle(),
                 velocity: missile_vel,
             };
             self.repo
-                .create_missile_for_player(&player_id.convert(), new_missile.convert());
+                .create_missile_for_player(&player_id.convert(), new_missile.convert())
         }
 
-        /// Check if player can shoot more missiles
-        fn player_can_shoot_missile(&self, player_id: PlayerId) -> bool {
-            let current_missile = self.repo.get_player_missile_count(&player_id.convert());
-            current_missile < self.config.max
+        /// Fixed-point variant of [`Self::create_missile_for_player`]
+        fn create_fixed_missile_for_player(&self, player_id: PlayerId) -> Result<()> {
+            let player = self
+                .repo
+                .get_fixed_player_pos_and_velocity(&player_id.convert())?;
+            let (pos, angle, velocity): (FixedVec2, Fixed, FixedVec2) = (
+                player.pos.convert(),
+                player.angle.convert(),
+                player.velocity.convert(),
+            );
+            let offset = FixedVec2::new(self.config.initial_distance, 0.0).rotate(angle);
+            let velocity = velocity + FixedVec2::new(self.config.initial_speed, 0.0).rotate(angle);
+            self.repo.create_fixed_missile_for_player(
+                &player_id.convert(),
+                FixedMissileLaunchData {
+                    pos: (pos + offset).convert(),
+                    angle: velocity.angle().convert(),
+                    velocity: velocity.convert(),
+                },
+            )
+        }
+
+        /// Check if player can shoot more missiles. Destroyed ships can not shoot.
+        fn player_can_shoot_missile(&self, player_id: PlayerId) -> Result<bool> {
+            if self.repo.is_player_destroyed(&player_id.convert())? {
+                return Ok(false);
+            }
+            let current_missile = self.repo.get_player_missile_count(&player_id.convert())?;
+            Ok(current_missile < self.config.max)
         }
     }
 
     /// Interface of data gateway for shoot use-case
+    ///
+    /// Fixed-point launch data is rounded through the floating point methods unless overridden.
     pub trait ShootDataGateway {
         /// Return position, orientation and velocity of player
-        fn get_player_pos_and_velocity(&self, id: &PlayerIdData) -> PlayerPosAndVelocityData;
+        fn get_player_pos_and_velocity(
+            &self,
+            id: &PlayerIdData,
+        ) -> Result<PlayerPosAndVelocityData>;
+
+        /// Check if the ship of a player is destroyed
+        fn is_player_destroyed(&self, id: &PlayerIdData) -> Result<bool>;
 
         /// Return number of active missiles of a player
-        fn get_player_missile_count(&self, id: &PlayerIdData) -> usize;
+        fn get_player_missile_count(&self, id: &PlayerIdData) -> Result<usize>;
 
         /// Safe missile for a player
-        fn create_missile_for_player(&self, id: &PlayerIdData, missile: MissileLaunchData);
+        fn create_missile_for_player(
+            &self,
+            id: &PlayerIdData,
+            missile: MissileLaunchData,
+        ) -> Result<()>;
+
+        /// Remove a missile of a player
+        ///
+        /// The ids of the remaining missiles must not change.
+        fn remove_missile(&self, id: &PlayerIdData, missile_id: &MissileIdData) -> Result<()>;
+
+        /// Fixed-point variant of [`Self::get_player_pos_and_velocity`]
+        fn get_fixed_player_pos_and_velocity(
+            &self,
+            id: &PlayerIdData,
+        ) -> Result<FixedPlayerPosAndVelocityData> {
+            let player = self.get_player_pos_and_velocity(id)?;
+            Ok(FixedPlayerPosAndVelocityData {
+                pos: player.pos.convert(),
+                angle: player.angle.convert(),
+                velocity: player.velocity.convert(),
+            })
+        }
+
+        /// Fixed-point variant of [`Self::create_missile_for_player`]
+        fn create_fixed_missile_for_player(
+            &self,
+            id: &PlayerIdData,
+            missile: FixedMissileLaunchData,
+        ) -> Result<()> {
+            let missile = MissileLaunchData {
+                pos: missile.pos.convert(),
+                angle: missile.angle.convert(),
+                velocity: missile.velocity.convert(),
+            };
+            self.create_missile_for_player(id, missile)
+        }
     }
 
     type DataGateway = Rc<dyn ShootDataGateway>;
 
     #[cfg(test)]
     mod test {
-        use std::{cell::RefCell, f32::EPSILON, rc::Rc};
+        use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 
         use crate::{
-            entities::Vec2,
-            repo_interfaces::{Marshalling, PlayerIdData},
+            entities::{Real, Vec2},
+            repo_interfaces::{Marshalling, MissileIdData, PlayerIdData},
+            Error, Result,
         };
 
         use super::{
@@ -444,47 +727,58 @@ mod shooting {
         #[derive(Default)]
         struct MockData {
             player: PlayerPosAndVelocityData,
-            player_missiles: Vec<(PlayerIdData, MissileLaunchData)>,
-        }
-
-        impl Default for PlayerPosAndVelocityData {
-            fn default() -> Self {
-                Self {
-                    pos: Default::default(),
-                    angle: Default::default(),
-                    velocity: Default::default(),
-                }
-            }
+            /// Missiles keyed by owner and missile id, ids are never reused
+            player_missiles: BTreeMap<(PlayerIdData, MissileIdData), MissileLaunchData>,
+            next_missile_id: MissileIdData,
+            destroyed: bool,
         }
 
         struct MockDataGateway {
             data: MockData,
         }
         impl ShootDataGateway for RefCell<MockDataGateway> {
-            fn get_player_missile_count(&self, id: &PlayerIdData) -> usize {
-                self.borrow()
+            fn is_player_destroyed(&self, _: &PlayerIdData) -> Result<bool> {
+                Ok(self.borrow().data.destroyed)
+            }
+
+            fn get_player_missile_count(&self, id: &PlayerIdData) -> Result<usize> {
+                Ok(self
+                    .borrow()
                     .data
                     .player_missiles
-                    .iter()
-                    .filter(|(m_id, _)| *m_id == *id)
-                    .count()
+                    .keys()
+                    .filter(|(p_id, _)| p_id == id)
+                    .count())
             }
 
             fn get_player_pos_and_velocity(
                 &self,
                 _: &PlayerIdData,
-            ) -> super::PlayerPosAndVelocityData {
-                PlayerPosAndVelocityData {
+            ) -> Result<super::PlayerPosAndVelocityData> {
+                Ok(PlayerPosAndVelocityData {
                     ..self.borrow().data.player
-                }
+                })
             }
 
             fn create_missile_for_player(
                 &self,
                 id: &PlayerIdData,
                 missile: super::MissileLaunchData,
-            ) {
-                self.borrow_mut().data.player_missiles.push((*id, missile));
+            ) -> Result<()> {
+                let data = &mut self.borrow_mut().data;
+                data.player_missiles
+                    .insert((*id, data.next_missile_id), missile);
+                data.next_missile_id += 1;
+                Ok(())
+            }
+
+            fn remove_missile(&self, id: &PlayerIdData, missile_id: &MissileIdData) -> Result<()> {
+                self.borrow_mut()
+                    .data
+                    .player_missiles
+                    .remove(&(*id, *missile_id))
+                    .map(|_| ())
+                    .ok_or(Error::UnknownMissile(*id, *missile_id))
             }
         }
 
@@ -495,8 +789,8 @@ mod shooting {
             ShootCommandFactory,
             Rc<RefCell<MockDataGateway>>,
         ) {
-            let config = MissileConfig::new(3, 100.0_f32, 500_f32);
-            let repo = Rc::new(RefCell::new(MockDataGateway { data: data }));
+            let config = MissileConfig::new(3, 100.0, 500.0);
+            let repo = Rc::new(RefCell::new(MockDataGateway { data }));
             let factory = ShootCommandFactory::new(config, repo.clone());
             (config, factory, repo)
         }
@@ -505,11 +799,14 @@ mod shooting {
         fn missile_shot_when_max_is_not_reached() {
             let (config, command_factory, repo) = setup_shoot_test(MockData::default());
             let player_id = 0;
-            let before_missile_count = repo.get_player_missile_count(&player_id);
+            let before_missile_count = repo.get_player_missile_count(&player_id).unwrap();
             assert_eq!(before_missile_count, 0);
             assert!(before_missile_count < config.get_max_missile());
-            command_factory.make_shoot_command(player_id).execute();
-            let after_missile_count = repo.get_player_missile_count(&player_id);
+            command_factory
+                .make_shoot_command(player_id)
+                .execute()
+                .unwrap();
+            let after_missile_count = repo.get_player_missile_count(&player_id).unwrap();
             assert_eq!(after_missile_count, 1);
         }
 
@@ -517,19 +814,56 @@ mod shooting {
         fn missile_not_shot_when_max_is_reached() {
             let (config, command_factory, repo) = setup_shoot_test(MockData::default());
             let player_id = 0;
-            for _ in 0..config.max {
+            for m_id in 0..config.max {
                 repo.borrow_mut()
                     .data
                     .player_missiles
-                    .push((player_id, MissileLaunchData::default()));
+                    .insert((player_id, m_id), MissileLaunchData::default());
             }
-            let before_missile_count = repo.get_player_missile_count(&player_id);
+            let before_missile_count = repo.get_player_missile_count(&player_id).unwrap();
             assert_eq!(before_missile_count, config.max);
-            command_factory.make_shoot_command(player_id).execute();
+            command_factory
+                .make_shoot_command(player_id)
+                .execute()
+                .unwrap();
             let after_missile_count = repo.borrow().data.player_missiles.len();
             assert_eq!(after_missile_count, before_missile_count);
         }
 
+        #[test]
+        fn destroyed_player_can_not_shoot() {
+            let (_, command_factory, repo) = setup_shoot_test(MockData {
+                destroyed: true,
+                ..MockData::default()
+            });
+            command_factory.make_shoot_command(0).execute().unwrap();
+            assert_eq!(repo.get_player_missile_count(&0), Ok(0));
+        }
+
+        #[test]
+        fn missile_shot_again_after_removal() {
+            let (config, command_factory, repo) = setup_shoot_test(MockData::default());
+            let player_id = 0;
+            for _ in 0..config.max {
+                command_factory
+                    .make_shoot_command(player_id)
+                    .execute()
+                    .unwrap();
+            }
+            repo.remove_missile(&player_id, &1).unwrap();
+            assert_eq!(
+                repo.remove_missile(&player_id, &1),
+                Err(Error::UnknownMissile(player_id, 1))
+            );
+            command_factory
+                .make_shoot_command(player_id)
+                .execute()
+                .unwrap();
+            assert_eq!(repo.get_player_missile_count(&player_id), Ok(config.max));
+            let ids: Vec<_> = repo.borrow().data.player_missiles.keys().copied().collect();
+            assert_eq!(ids, vec![(0, 0), (0, 2), (0, 3)]);
+        }
+
         #[test]
         fn missile_created_at_correct_location() {
             let player_pos = PlayerPosAndVelocityData {
@@ -542,12 +876,16 @@ mod shooting {
                 ..MockData::default()
             });
             let player_id = 0;
-            command_factory.make_shoot_command(player_id).execute();
-            let expected_pos = repo.borrow().data.player.pos.convert()
+            command_factory
+                .make_shoot_command(player_id)
+                .execute()
+                .unwrap();
+            let player_pos: Vec2 = repo.borrow().data.player.pos.convert();
+            let expected_pos = player_pos
                 + Vec2::new(config.initial_distance, 0.0).rotate(repo.borrow().data.player.angle);
             assert!(
-                (expected_pos - repo.borrow().data.player_missiles[0].1.pos.convert()).len()
-                    < EPSILON
+                (expected_pos - repo.borrow().data.player_missiles[&(0, 0)].pos.convert()).len()
+                    < Real::EPSILON
             );
         }
 
@@ -563,12 +901,20 @@ mod shooting {
                 ..MockData::default()
             });
             let player_id = 0;
-            command_factory.make_shoot_command(player_id).execute();
-            let expected_vel = repo.borrow().data.player.velocity.convert()
+            command_factory
+                .make_shoot_command(player_id)
+                .execute()
+                .unwrap();
+            let player_vel: Vec2 = repo.borrow().data.player.velocity.convert();
+            let expected_vel = player_vel
                 + Vec2::new(config.initial_speed, 0.0).rotate(repo.borrow().data.player.angle);
             assert!(
-                (expected_vel - repo.borrow().data.player_missiles[0].1.velocity.convert()).len()
-                    < EPSILON
+                (expected_vel
+                    - repo.borrow().data.player_missiles[&(0, 0)]
+                        .velocity
+                        .convert())
+                .len()
+                    < Real::EPSILON
             );
         }
 
@@ -584,12 +930,17 @@ mod shooting {
                 ..MockData::default()
             });
             let player_id = 0;
-            command_factory.make_shoot_command(player_id).execute();
-            let expected_vel = repo.borrow().data.player.velocity.convert()
+            command_factory
+                .make_shoot_command(player_id)
+                .execute()
+                .unwrap();
+            let player_vel: Vec2 = repo.borrow().data.player.velocity.convert();
+            let expected_vel = player_vel
                 + Vec2::new(config.initial_speed, 0.0).rotate(repo.borrow().data.player.angle);
             let expected_angle = expected_vel.angle();
             assert!(
-                (expected_angle - repo.borrow().data.player_missiles[0].1.angle).abs() < EPSILON
+                (expected_angle - repo.borrow().data.player_missiles[&(0, 0)].angle).abs()
+                    < Real::EPSILON
             );
         }
     }